@@ -1,94 +1,103 @@
-use std::error::Error;
+use std::env;
+use std::fs;
+use std::process;
 
-use binance::model::KlineSummary;
-use binance::api::*;
-use binance::general::General;
-use binance::market::Market;
-use chrono::{DateTime, Utc};
-use root::strategy::{Backtester, ChoppinessDonchianAtrStrategy, Mode};
+use chrono::Utc;
+use root::data::downloader::parse_datetime_to_unix;
+use root::data::{DownloadEvent, Downloader, Interval, RepairPolicy, Validator};
+use root::strategy::{Backtester, Parameters, StrategyConfig, StrategyRegistry};
 
-fn get_symbols_ending_with_btc() -> Vec<String> {
-    let general: General = Binance::new(None, None);
-    
-    match general.exchange_info() {
-        Ok(result) => {
-            result.symbols
-                .into_iter()
-                .filter(|symbol| symbol.symbol.ends_with("BTC"))
-                .map(|symbol| symbol.symbol)
-                .collect()
-        }
-        Err(err) => {
-            println!("Erreur : {:?}", err);
-            Vec::new() // Retourne un vecteur vide en cas d'erreur
+const USAGE: &str = "Usage:
+    app list                                 liste des stratégies et de leurs paramètres
+    app [STRATEGY] [name=value ...]          backtest d'une stratégie (choppiness_donchian_atr par défaut)
+    app --config FILE [name=value ...]       backtest d'une stratégie décrite par un fichier de configuration";
+
+// Stratégie et paramètres demandés sur la ligne de commande
+fn parse_arguments(arguments: &[String]) -> Result<StrategyConfig, String> {
+    let mut config = StrategyConfig { strategy: "choppiness_donchian_atr".to_string(), parameters: Parameters::new() };
+    let mut arguments = arguments.iter();
+
+    while let Some(argument) = arguments.next() {
+        if argument == "--config" {
+            let path = arguments.next().ok_or("--config expects a file")?;
+            let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+            let file = StrategyConfig::parse(&text).map_err(|e| e.to_string())?;
+            config.strategy = file.strategy;
+            for name in file.parameters.names() {
+                config.parameters.set(name, file.parameters.get(name).unwrap_or_default());
+            }
+        } else if argument.contains('=') {
+            config.parameters.set_assignment(argument).map_err(|e| e.to_string())?;
+        } else {
+            config.strategy = argument.clone();
         }
     }
-}
 
-fn parse_datetime_to_unix(date: &str) -> Result<u64, &'static str> {
-    let parsed = DateTime::parse_from_rfc3339(date)
-        .map_err(|_| "Invalid date format. Please use RFC3339 format (e.g., 2023-01-01T00:00:00Z)")?
-        .timestamp();
-    Ok(parsed as u64 * 1000) // Convertir en millisecondes
+    Ok(config)
 }
 
-pub fn get_klines_summary_in_range(
-    market: &Market,
-    symbol: &str,
-    interval: &str,
-    start_time: &str,
-    end_time: &str,
-) -> Result<Vec<KlineSummary>, Box<dyn Error>> {
-    let mut all_klines = Vec::new();
-    
-    // Convert the start and end time from string to UNIX timestamps
-    let start_timestamp = parse_datetime_to_unix(start_time)?;
-    let end_timestamp = parse_datetime_to_unix(end_time)?;
-    
-    let mut current_start = start_timestamp;
-    
-    while current_start < end_timestamp {
-        // Utilisez match pour gérer KlineSummaries
-        let klines = match market.get_klines(
-            symbol,
-            interval,
-            999,
-            Some(current_start),
-            None,
-        )? {
-            binance::model::KlineSummaries::AllKlineSummaries(k) => k,
-        };
-        
-        // Si aucun kline n'est retourné, arrêter la boucle
-        if klines.is_empty() {
-            break;
+fn main() {
+    let registry = StrategyRegistry::builtin();
+    let arguments: Vec<String> = env::args().skip(1).collect();
+    match arguments.first().map(String::as_str) {
+        Some("list") => {
+            print!("{}", registry.describe());
+            return;
+        }
+        Some("-h") | Some("--help") => {
+            println!("{}", USAGE);
+            return;
         }
-        
-        // Ajoute les klines récupérés à la liste complète
-        all_klines.extend(klines.clone());
-        
-        // Met à jour la valeur de current_start pour la prochaine itération
-        let last_kline_time = klines.last().unwrap().close_time as u64;
-        current_start = last_kline_time + 1;
+        _ => {}
     }
-    
-    Ok(all_klines)
-}
 
-fn main() {
-    let _symbols = get_symbols_ending_with_btc();
-    //println!("{:?}", symbols);
-    let market: Market = Binance::new(None, None);
+    let config = match parse_arguments(&arguments) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Erreur: {}\n{}", e, USAGE);
+            process::exit(1);
+        }
+    };
+    let strategy = match config.create(&registry) {
+        Ok(strategy) => strategy,
+        Err(e) => {
+            eprintln!("Erreur: {}", e);
+            process::exit(1);
+        }
+    };
+    let symbol = config.parameters.get("symbol").unwrap_or("ETHBTC");
 
-    let now = Utc::now().to_rfc3339();
+    let downloader = Downloader::new().with_report(|event| {
+        if let DownloadEvent::Retry { progress, attempt, max_retries, delay, error } = event {
+            eprintln!(
+                "Requête de klines {} en échec (tentative {}/{}), nouvel essai dans {:?} : {}",
+                progress.symbol, attempt, max_retries, delay, error
+            );
+        }
+    });
 
-    match get_klines_summary_in_range(&market, "ETHBTC", "1h", "2024-01-01T00:00:00Z", &now) {
+    let start = match parse_datetime_to_unix("2024-01-01T00:00:00Z") {
+        Ok(start) => start,
+        Err(e) => {
+            eprintln!("Erreur: {}", e);
+            process::exit(1);
+        }
+    };
+    let now = Utc::now().timestamp_millis() as u64;
+
+    match downloader.download(symbol, Interval::Hours(1), start, now) {
         Ok(klines) => {
-            let strategy = ChoppinessDonchianAtrStrategy::new(Mode::Backtest, "ETHBTC");
-            let mut backtester = Backtester::new(Box::new(strategy));
-            backtester.run(&klines);
+            let validator = Validator::new(Interval::Hours(1), RepairPolicy::ForwardFill);
+            let mut backtester = Backtester::new(strategy).with_validator(validator);
+            if let Err(e) = backtester.run(&klines) {
+                eprintln!("Erreur: {}", e);
+                process::exit(1);
+            }
+        }
+        Err(e) => {
+            eprintln!("Erreur: {:?}", e);
+            process::exit(1);
         }
-        Err(e) => eprintln!("Erreur: {:?}", e),
     }
 
     //match market.get_klines("ETHBTC", "1h", 999, None, None) {
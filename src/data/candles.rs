@@ -0,0 +1,59 @@
+use binance::model::KlineSummary;
+
+// Bougies stockées en colonnes f64 contiguës, converties une seule fois depuis les chaînes
+// Binance : format des calculs d'indicateurs par lot (recherche, balayage de paramètres).
+// Un champ invalide vaut NaN, comme `PriceSource::extract`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Candles {
+    pub open_time: Vec<i64>,
+    pub open: Vec<f64>,
+    pub high: Vec<f64>,
+    pub low: Vec<f64>,
+    pub close: Vec<f64>,
+    pub volume: Vec<f64>,
+    pub taker_buy_volume: Vec<f64>, // Volume acheteur (taker buy), en actif de base
+}
+
+impl Candles {
+    pub fn from_klines(klines: &[KlineSummary]) -> Self {
+        let parse = |value: &str| value.parse::<f64>().unwrap_or(f64::NAN);
+        let mut candles = Self::with_capacity(klines.len());
+
+        for kline in klines {
+            candles.open_time.push(kline.open_time);
+            candles.open.push(parse(&kline.open));
+            candles.high.push(parse(&kline.high));
+            candles.low.push(parse(&kline.low));
+            candles.close.push(parse(&kline.close));
+            candles.volume.push(parse(&kline.volume));
+            candles.taker_buy_volume.push(parse(&kline.taker_buy_base_asset_volume));
+        }
+        candles
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            open_time: Vec::with_capacity(capacity),
+            open: Vec::with_capacity(capacity),
+            high: Vec::with_capacity(capacity),
+            low: Vec::with_capacity(capacity),
+            close: Vec::with_capacity(capacity),
+            volume: Vec::with_capacity(capacity),
+            taker_buy_volume: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.open_time.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.open_time.is_empty()
+    }
+}
+
+impl From<&[KlineSummary]> for Candles {
+    fn from(klines: &[KlineSummary]) -> Self {
+        Self::from_klines(klines)
+    }
+}
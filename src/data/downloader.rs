@@ -0,0 +1,386 @@
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use binance::api::Binance;
+use binance::config::Config;
+use binance::errors::ErrorKind;
+use binance::market::Market;
+use binance::model::{KlineSummaries, KlineSummary};
+use chrono::DateTime;
+
+use super::Interval;
+
+// Poids d'une requête /api/v3/klines, quel que soit `limit`
+const KLINES_REQUEST_WEIGHT: u32 = 2;
+const MAX_PAGE_LIMIT: u16 = 1000;
+
+pub fn parse_datetime_to_unix(date: &str) -> Result<u64, &'static str> {
+    let parsed = DateTime::parse_from_rfc3339(date)
+        .map_err(|_| "Invalid date format. Please use RFC3339 format (e.g., 2023-01-01T00:00:00Z)")?
+        .timestamp_millis();
+    u64::try_from(parsed).map_err(|_| "Dates before 1970-01-01 are not supported")
+}
+
+// Avancement d'un téléchargement, permettant de le reprendre après une erreur
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DownloadProgress {
+    pub symbol: String,
+    pub interval: Interval,
+    pub next_start: u64, // Prochaine heure d'ouverture à télécharger (ms)
+    pub end: u64,        // Borne incluse (ms)
+}
+
+impl DownloadProgress {
+    pub fn new(symbol: &str, interval: Interval, start: u64, end: u64) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            interval,
+            next_start: start,
+            end,
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.next_start > self.end
+    }
+}
+
+// Erreur définitive après épuisement des tentatives : contient les bougies déjà
+// récupérées et l'avancement pour reprendre avec `Downloader::resume`
+#[derive(Debug)]
+pub struct DownloadError {
+    pub source: binance::errors::Error,
+    pub progress: DownloadProgress,
+    pub klines: Vec<KlineSummary>,
+}
+
+impl fmt::Display for DownloadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Download of {} {} stopped at {} after {} klines: {}",
+            self.progress.symbol,
+            self.progress.interval,
+            self.progress.next_start,
+            self.klines.len(),
+            self.source
+        )
+    }
+}
+
+impl std::error::Error for DownloadError {}
+
+// Événements d'un téléchargement, transmis au rapport d'avancement (`Downloader::with_report`)
+#[derive(Debug)]
+pub enum DownloadEvent<'a> {
+    // Page reçue ; `progress.next_start` pointe déjà vers la page suivante
+    Page { progress: &'a DownloadProgress, klines: usize },
+    // Requête en échec, réessayée après `delay`
+    Retry {
+        progress: &'a DownloadProgress,
+        attempt: u32,
+        max_retries: u32,
+        delay: Duration,
+        error: &'a binance::errors::Error,
+    },
+}
+
+type Report = Box<dyn Fn(&DownloadEvent) + Send + Sync>;
+
+// Nature d'une requête en échec, qui décide de la suite du téléchargement
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Failure {
+    Fatal,       // Requête invalide ou adresse IP bannie : ne pas réessayer
+    RateLimited, // 429 : suspendre tous les téléchargements
+    Transient,   // Erreur réseau ou du serveur : réessayer avec backoff
+}
+
+impl Failure {
+    // Le client Binance ne donne le code HTTP que dans le message ("Received response: 429", 500 et
+    // 503 ayant leur propre message). Un code illisible est fatal : mieux vaut s'arrêter que risquer
+    // de prolonger un bannissement.
+    fn of(error: &binance::errors::Error) -> Self {
+        if let ErrorKind::BinanceError(_) = error.kind() {
+            return Failure::Fatal;
+        }
+        // Sans code HTTP : erreur réseau, de décodage ou du serveur
+        let ErrorKind::Msg(message) = error.kind() else {
+            return Failure::Transient;
+        };
+        let Some(status) = message.strip_prefix("Received response: ") else {
+            return Failure::Transient;
+        };
+        match status.trim().parse::<u16>() {
+            Ok(429) => Failure::RateLimited,
+            Ok(status) if status >= 500 => Failure::Transient,
+            _ => Failure::Fatal,
+        }
+    }
+}
+
+// Budget de poids des requêtes sur une fenêtre glissante d'une minute, partagé entre les threads
+#[derive(Debug)]
+struct WeightBudget {
+    limit: u32,
+    used: u32,
+    window_start: Instant,
+    blocked_until: Option<Instant>, // Après une réponse 429, plus aucune requête avant cet instant
+}
+
+impl WeightBudget {
+    const WINDOW: Duration = Duration::from_secs(60);
+
+    fn new(limit: u32) -> Self {
+        Self {
+            limit,
+            used: 0,
+            window_start: Instant::now(),
+            blocked_until: None,
+        }
+    }
+
+    fn block_for(&mut self, duration: Duration) {
+        let until = Instant::now() + duration;
+        self.blocked_until = Some(self.blocked_until.map_or(until, |blocked| blocked.max(until)));
+    }
+
+    // Réserver le poids d'une requête, ou retourner le temps à attendre avant de réessayer
+    fn try_acquire(&mut self, weight: u32) -> Result<(), Duration> {
+        if let Some(until) = self.blocked_until {
+            let remaining = until.saturating_duration_since(Instant::now());
+            if !remaining.is_zero() {
+                return Err(remaining);
+            }
+            self.blocked_until = None;
+        }
+
+        let elapsed = self.window_start.elapsed();
+        if elapsed >= Self::WINDOW {
+            self.used = 0;
+            self.window_start = Instant::now();
+        }
+
+        if self.used + weight > self.limit && self.used > 0 {
+            return Err(Self::WINDOW.saturating_sub(elapsed));
+        }
+
+        self.used += weight;
+        Ok(())
+    }
+}
+
+pub struct Downloader {
+    config: Config,
+    page_limit: u16,
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    rate_limit_backoff: Duration,
+    max_concurrency: usize,
+    budget: Mutex<WeightBudget>,
+    report: Option<Report>,
+}
+
+impl Downloader {
+    pub fn new() -> Self {
+        Self::with_config(Config::default())
+    }
+
+    // Utile pour pointer vers le testnet ou un serveur local
+    pub fn with_config(config: Config) -> Self {
+        Self {
+            config,
+            page_limit: MAX_PAGE_LIMIT,
+            max_retries: 5,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            // Binance ne précise pas l'attente après un 429 : on attend la fin de la fenêtre d'une minute
+            rate_limit_backoff: WeightBudget::WINDOW,
+            max_concurrency: 4,
+            // Binance autorise 6000 de poids par minute, on garde une marge pour les autres appels
+            budget: Mutex::new(WeightBudget::new(5000)),
+            report: None,
+        }
+    }
+
+    pub fn with_page_limit(mut self, page_limit: u16) -> Self {
+        self.page_limit = page_limit.clamp(1, MAX_PAGE_LIMIT);
+        self
+    }
+
+    pub fn with_retries(mut self, max_retries: u32, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        self.max_retries = max_retries;
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    // Attente imposée à tous les threads après une réponse 429 (limite de requêtes dépassée)
+    pub fn with_rate_limit_backoff(mut self, backoff: Duration) -> Self {
+        self.rate_limit_backoff = backoff;
+        self
+    }
+
+    // Rapport d'avancement appelé à chaque page reçue et à chaque nouvelle tentative
+    pub fn with_report(mut self, report: impl Fn(&DownloadEvent) + Send + Sync + 'static) -> Self {
+        self.report = Some(Box::new(report));
+        self
+    }
+
+    pub fn with_weight_limit(mut self, weight_per_minute: u32) -> Self {
+        self.budget = Mutex::new(WeightBudget::new(weight_per_minute));
+        self
+    }
+
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency.max(1);
+        self
+    }
+
+    // Télécharger toutes les bougies dont l'heure d'ouverture est dans [start, end]
+    pub fn download(&self, symbol: &str, interval: Interval, start: u64, end: u64) -> Result<Vec<KlineSummary>, DownloadError> {
+        self.resume(DownloadProgress::new(symbol, interval, start, end), Vec::new())
+    }
+
+    // Reprendre un téléchargement interrompu en complétant les bougies déjà récupérées
+    pub fn resume(&self, mut progress: DownloadProgress, mut klines: Vec<KlineSummary>) -> Result<Vec<KlineSummary>, DownloadError> {
+        let market: Market = Binance::new_with_config(None, None, &self.config);
+
+        while !progress.is_complete() {
+            let page = match self.fetch_page(&market, &progress) {
+                Ok(page) => page,
+                Err(source) => return Err(DownloadError { source, progress, klines }),
+            };
+
+            // Binance ne renvoie rien au-delà de la dernière bougie disponible
+            let last_close_time = match page.last() {
+                Some(last) => last.close_time,
+                None => break,
+            };
+
+            klines.extend(page.into_iter().filter(|kline| {
+                kline.open_time >= progress.next_start as i64 && kline.open_time <= progress.end as i64
+            }));
+            // Garde-fou contre une réponse qui ne ferait pas avancer le curseur
+            let next_start = last_close_time as u64 + 1;
+            if next_start <= progress.next_start {
+                break;
+            }
+            progress.next_start = next_start;
+            self.notify(&DownloadEvent::Page { progress: &progress, klines: klines.len() });
+        }
+
+        Ok(klines)
+    }
+
+    // Télécharger plusieurs symboles en parallèle, en partageant le budget de poids
+    pub fn download_many(
+        &self,
+        symbols: &[&str],
+        interval: Interval,
+        start: u64,
+        end: u64,
+    ) -> Vec<(String, Result<Vec<KlineSummary>, DownloadError>)> {
+        let next = AtomicUsize::new(0);
+        let results = Mutex::new(Vec::with_capacity(symbols.len()));
+
+        thread::scope(|scope| {
+            for _ in 0..self.max_concurrency.min(symbols.len()) {
+                scope.spawn(|| loop {
+                    let index = next.fetch_add(1, Ordering::SeqCst);
+                    let Some(symbol) = symbols.get(index) else {
+                        break;
+                    };
+
+                    let result = self.download(symbol, interval, start, end);
+                    if let Ok(mut results) = results.lock() {
+                        results.push((index, symbol.to_string(), result));
+                    }
+                });
+            }
+        });
+
+        // Conserver l'ordre des symboles demandés
+        let mut results = results.into_inner().unwrap_or_default();
+        results.sort_by_key(|(index, _, _)| *index);
+        results.into_iter().map(|(_, symbol, result)| (symbol, result)).collect()
+    }
+
+    fn fetch_page(&self, market: &Market, progress: &DownloadProgress) -> binance::errors::Result<Vec<KlineSummary>> {
+        let mut attempt = 0;
+        let mut backoff = self.initial_backoff;
+
+        loop {
+            self.acquire_weight(KLINES_REQUEST_WEIGHT);
+
+            let result = market.get_klines(
+                progress.symbol.as_str(),
+                progress.interval.to_string(),
+                self.page_limit,
+                progress.next_start,
+                progress.end,
+            );
+
+            match result {
+                Ok(KlineSummaries::AllKlineSummaries(page)) => return Ok(page),
+                Err(error) => {
+                    // Les erreurs de requête (symbole inconnu, paramètres invalides) ne sont pas réessayées,
+                    // ni un 418 : l'adresse IP est bannie et chaque nouvelle requête allonge le bannissement
+                    let failure = Failure::of(&error);
+                    if failure == Failure::Fatal || attempt >= self.max_retries {
+                        return Err(error);
+                    }
+
+                    let delay = if failure == Failure::RateLimited {
+                        // Limite dépassée : suspendre tous les téléchargements en cours
+                        if let Ok(mut budget) = self.budget.lock() {
+                            budget.block_for(self.rate_limit_backoff);
+                        }
+                        self.rate_limit_backoff
+                    } else {
+                        backoff
+                    };
+                    attempt += 1;
+                    self.notify(&DownloadEvent::Retry {
+                        progress,
+                        attempt,
+                        max_retries: self.max_retries,
+                        delay,
+                        error: &error,
+                    });
+                    thread::sleep(delay);
+                    backoff = (backoff * 2).min(self.max_backoff);
+                }
+            }
+        }
+    }
+
+    fn notify(&self, event: &DownloadEvent) {
+        if let Some(report) = &self.report {
+            report(event);
+        }
+    }
+
+    fn acquire_weight(&self, weight: u32) {
+        loop {
+            let wait = match self.budget.lock() {
+                Ok(mut budget) => budget.try_acquire(weight),
+                Err(_) => return,
+            };
+
+            match wait {
+                Ok(()) => return,
+                Err(duration) => thread::sleep(duration),
+            }
+        }
+    }
+}
+
+impl Default for Downloader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
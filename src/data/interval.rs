@@ -0,0 +1,133 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, NaiveDate};
+
+const MINUTE_MS: i64 = 60 * 1000;
+const HOUR_MS: i64 = 60 * MINUTE_MS;
+const DAY_MS: i64 = 24 * HOUR_MS;
+const WEEK_MS: i64 = 7 * DAY_MS;
+
+// Le 1970-01-05 est le premier lundi après l'époque UNIX (Binance aligne les semaines sur le lundi)
+const FIRST_MONDAY_MS: i64 = 4 * DAY_MS;
+
+// Intervalle de bougies au format Binance ("1m", "4h", "1d", "1w", "1M", ...)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Interval {
+    Minutes(u32),
+    Hours(u32),
+    Days(u32),
+    Weeks(u32),
+    Months(u32),
+}
+
+impl Interval {
+    // Durée fixe en millisecondes (None pour les mois, dont la durée varie)
+    pub fn duration_ms(&self) -> Option<i64> {
+        match *self {
+            Interval::Minutes(n) => Some(n as i64 * MINUTE_MS),
+            Interval::Hours(n) => Some(n as i64 * HOUR_MS),
+            Interval::Days(n) => Some(n as i64 * DAY_MS),
+            Interval::Weeks(n) => Some(n as i64 * WEEK_MS),
+            Interval::Months(_) => None,
+        }
+    }
+
+    // Durée approximative, utile pour comparer deux intervalles entre eux
+    pub fn approx_duration_ms(&self) -> i64 {
+        match *self {
+            Interval::Months(n) => n as i64 * 30 * DAY_MS,
+            _ => self.duration_ms().unwrap_or(0),
+        }
+    }
+
+    // Début de la bougie (en ms UTC) qui contient le timestamp donné
+    pub fn bucket_start(&self, timestamp: i64) -> i64 {
+        match *self {
+            Interval::Weeks(n) => {
+                let length = n as i64 * WEEK_MS;
+                timestamp - (timestamp - FIRST_MONDAY_MS).rem_euclid(length)
+            }
+            Interval::Months(n) => {
+                let date = match DateTime::from_timestamp_millis(timestamp) {
+                    Some(datetime) => datetime.date_naive(),
+                    None => return timestamp,
+                };
+                let index = date.year() as i64 * 12 + date.month0() as i64;
+                let bucket = index - index.rem_euclid(n as i64);
+                month_start_ms(bucket)
+            }
+            _ => {
+                let length = self.duration_ms().unwrap_or(1);
+                timestamp - timestamp.rem_euclid(length)
+            }
+        }
+    }
+
+    // Début de la bougie suivante, à partir du début d'une bougie
+    pub fn next_bucket_start(&self, bucket_start: i64) -> i64 {
+        match *self {
+            Interval::Months(n) => {
+                let date = match DateTime::from_timestamp_millis(bucket_start) {
+                    Some(datetime) => datetime.date_naive(),
+                    None => return bucket_start,
+                };
+                let index = date.year() as i64 * 12 + date.month0() as i64;
+                month_start_ms(index + n as i64)
+            }
+            _ => bucket_start + self.duration_ms().unwrap_or(0),
+        }
+    }
+
+    // Heure de clôture (close_time Binance) de la bougie qui contient le timestamp donné
+    pub fn close_time(&self, timestamp: i64) -> i64 {
+        self.next_bucket_start(self.bucket_start(timestamp)) - 1
+    }
+}
+
+fn month_start_ms(month_index: i64) -> i64 {
+    let year = month_index.div_euclid(12) as i32;
+    let month = month_index.rem_euclid(12) as u32 + 1;
+    NaiveDate::from_ymd_opt(year, month, 1)
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|datetime| datetime.and_utc().timestamp_millis())
+        .unwrap_or(0)
+}
+
+impl FromStr for Interval {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.len() < 2 {
+            return Err(format!("Invalid interval: {}", value));
+        }
+
+        let (count, unit) = value.split_at(value.len() - 1);
+        let count = count
+            .parse::<u32>()
+            .ok()
+            .filter(|count| *count > 0)
+            .ok_or_else(|| format!("Invalid interval: {}", value))?;
+
+        match unit {
+            "m" => Ok(Interval::Minutes(count)),
+            "h" => Ok(Interval::Hours(count)),
+            "d" => Ok(Interval::Days(count)),
+            "w" => Ok(Interval::Weeks(count)),
+            "M" => Ok(Interval::Months(count)),
+            _ => Err(format!("Invalid interval: {}", value)),
+        }
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Interval::Minutes(n) => write!(f, "{}m", n),
+            Interval::Hours(n) => write!(f, "{}h", n),
+            Interval::Days(n) => write!(f, "{}d", n),
+            Interval::Weeks(n) => write!(f, "{}w", n),
+            Interval::Months(n) => write!(f, "{}M", n),
+        }
+    }
+}
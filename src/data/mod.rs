@@ -0,0 +1,19 @@
+pub mod interval;
+pub use interval::Interval;
+
+pub mod resampler;
+pub use resampler::Resampler;
+
+pub mod transform;
+
+pub mod ring_buffer;
+pub use ring_buffer::{RetentionPolicy, RingBuffer};
+
+pub mod validator;
+pub use validator::{RepairPolicy, ValidationReport, Validator};
+
+pub mod downloader;
+pub use downloader::{DownloadError, DownloadEvent, DownloadProgress, Downloader};
+
+pub mod candles;
+pub use candles::Candles;
@@ -0,0 +1,93 @@
+use binance::model::KlineSummary;
+
+use super::Interval;
+
+// Agrégation de bougies vers une unité de temps supérieure (alignée sur le calendrier UTC
+// pour les jours, semaines et mois, comme le fait Binance). Une période n'est publiée que si elle
+// contient toutes ses bougies de base : la période en cours au début de l'historique et les
+// périodes avec un trou sont abandonnées.
+#[derive(Debug, Clone)]
+pub struct Resampler {
+    interval: Interval,
+    partial: Option<KlineSummary>, // Bougie en cours d'agrégation
+    bars: usize,                   // Nombre de bougies de base dans la bougie en cours
+}
+
+impl Resampler {
+    pub fn new(interval: Interval) -> Self {
+        Self { interval, partial: None, bars: 0 }
+    }
+
+    pub fn interval(&self) -> Interval {
+        self.interval
+    }
+
+    // Bougie en cours, pas encore clôturée
+    pub fn partial(&self) -> Option<&KlineSummary> {
+        self.partial.as_ref()
+    }
+
+    // Agréger toutes les bougies et retourner uniquement les bougies clôturées
+    pub fn resample(interval: Interval, klines: &[KlineSummary]) -> Vec<KlineSummary> {
+        let mut resampler = Self::new(interval);
+        klines.iter().flat_map(|kline| resampler.push(kline)).collect()
+    }
+
+    // Agréger toutes les bougies, en incluant la dernière bougie même incomplète
+    pub fn resample_with_partial(interval: Interval, klines: &[KlineSummary]) -> Vec<KlineSummary> {
+        let mut resampler = Self::new(interval);
+        let mut resampled: Vec<KlineSummary> = klines.iter().flat_map(|kline| resampler.push(kline)).collect();
+        resampled.extend(resampler.flush());
+        resampled
+    }
+
+    // Intégrer une bougie dans la bougie en cours et retourner la bougie clôturée, si elle est complète
+    pub fn push(&mut self, kline: &KlineSummary) -> Option<KlineSummary> {
+        let bucket_start = self.interval.bucket_start(kline.open_time);
+
+        // Un trou dans les données laisse la bougie en cours incomplète : elle est abandonnée
+        self.partial.take_if(|current| current.open_time != bucket_start);
+
+        match &mut self.partial {
+            Some(current) => {
+                merge_kline(current, kline);
+                self.bars += 1;
+            }
+            None => {
+                let mut current = kline.clone();
+                current.open_time = bucket_start;
+                current.close_time = self.interval.next_bucket_start(bucket_start) - 1;
+                self.partial = Some(current);
+                self.bars = 1;
+            }
+        }
+
+        let current = self.partial.take_if(|current| kline.close_time >= current.close_time)?;
+        // Autant de bougies de base que la période peut en contenir
+        let duration = (kline.close_time + 1 - kline.open_time).max(1);
+        let expected = (current.close_time + 1 - current.open_time) / duration;
+        (self.bars as i64 == expected).then_some(current)
+    }
+
+    // Retourner la bougie en cours, même incomplète
+    pub fn flush(&mut self) -> Option<KlineSummary> {
+        self.partial.take()
+    }
+}
+
+// Combiner une bougie dans une bougie agrégée : OHLC, volumes, nombre de trades et volumes acheteurs
+pub fn merge_kline(current: &mut KlineSummary, kline: &KlineSummary) {
+    let parse = |value: &str| value.parse::<f64>().unwrap_or(0.0);
+
+    current.high = parse(&current.high).max(parse(&kline.high)).to_string();
+    current.low = parse(&current.low).min(parse(&kline.low)).to_string();
+    current.close = kline.close.clone();
+    current.close_time = current.close_time.max(kline.close_time);
+    current.volume = (parse(&current.volume) + parse(&kline.volume)).to_string();
+    current.quote_asset_volume = (parse(&current.quote_asset_volume) + parse(&kline.quote_asset_volume)).to_string();
+    current.number_of_trades += kline.number_of_trades;
+    current.taker_buy_base_asset_volume =
+        (parse(&current.taker_buy_base_asset_volume) + parse(&kline.taker_buy_base_asset_volume)).to_string();
+    current.taker_buy_quote_asset_volume =
+        (parse(&current.taker_buy_quote_asset_volume) + parse(&kline.taker_buy_quote_asset_volume)).to_string();
+}
@@ -0,0 +1,152 @@
+use std::ops::{Deref, DerefMut};
+
+// Nombre minimal d'éléments retirés d'un coup, pour que le compactage reste amorti
+const MIN_COMPACTION: usize = 64;
+
+// Série à mémoire bornée : au-delà de la capacité, les plus anciens éléments sont oubliés.
+// Le contenu reste contigu (`&[T]`) : les éléments sortis ne sont libérés que par blocs,
+// la mémoire utilisée reste inférieure à deux fois la capacité.
+#[derive(Debug, Clone)]
+pub struct RingBuffer<T> {
+    values: Vec<T>,
+    start: usize,             // Index du plus ancien élément conservé
+    capacity: Option<usize>, // None : pas de limite
+}
+
+impl<T> RingBuffer<T> {
+    // Série sans limite de taille
+    pub fn new() -> Self {
+        Self {
+            values: Vec::new(),
+            start: 0,
+            capacity: None,
+        }
+    }
+
+    // Série limitée aux `capacity` derniers éléments (None : sans limite)
+    pub fn bounded(capacity: Option<usize>) -> Self {
+        let mut buffer = Self::new();
+        buffer.set_capacity(capacity);
+        buffer
+    }
+
+    pub fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    // Changer la capacité (None : sans limite) et oublier les éléments en trop
+    pub fn set_capacity(&mut self, capacity: Option<usize>) {
+        self.capacity = capacity.map(|capacity| capacity.max(1));
+        self.trim();
+    }
+
+    pub fn push(&mut self, value: T) {
+        self.values.push(value);
+        self.trim();
+    }
+
+    // Élément d'il y a `n` positions (0 = le plus récent)
+    pub fn ago(&self, n: usize) -> Option<&T> {
+        let slice = self.as_slice();
+        slice.len().checked_sub(n + 1).map(|index| &slice[index])
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.values[self.start..]
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.values[self.start..]
+    }
+
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.start = 0;
+    }
+
+    fn trim(&mut self) {
+        let Some(capacity) = self.capacity else {
+            return;
+        };
+
+        let len = self.values.len() - self.start;
+        if len > capacity {
+            self.start += len - capacity;
+        }
+        if self.start >= capacity.max(MIN_COMPACTION) {
+            self.values.drain(..self.start);
+            self.start = 0;
+        }
+    }
+}
+
+impl<T> Default for RingBuffer<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Deref for RingBuffer<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T> DerefMut for RingBuffer<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T: PartialEq> PartialEq for RingBuffer<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T> From<Vec<T>> for RingBuffer<T> {
+    fn from(values: Vec<T>) -> Self {
+        Self {
+            values,
+            start: 0,
+            capacity: None,
+        }
+    }
+}
+
+impl<T> FromIterator<T> for RingBuffer<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<T>>())
+    }
+}
+
+impl<T> Extend<T> for RingBuffer<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RingBuffer<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
+// Politique de rétention des bougies et des séries d'indicateurs d'un `KlineManager`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RetentionPolicy {
+    // Tout l'historique est conservé (backtests)
+    #[default]
+    Unbounded,
+    // Nombre fixe de bougies et de valeurs conservées
+    Capacity(usize),
+    // Le plus grand historique demandé par les indicateurs, plus une marge pour la stratégie
+    MaxLookback { margin: usize },
+}
@@ -0,0 +1,142 @@
+use binance::model::KlineSummary;
+
+use super::resampler::merge_kline;
+
+fn parse(value: &str) -> f64 {
+    value.parse::<f64>().unwrap_or(0.0)
+}
+
+// Bougies Heikin-Ashi : mêmes horaires et volumes, OHLC lissés
+pub fn heikin_ashi(klines: &[KlineSummary]) -> Vec<KlineSummary> {
+    let mut transformed: Vec<KlineSummary> = Vec::with_capacity(klines.len());
+    let mut previous: Option<(f64, f64)> = None; // (open, close) Heikin-Ashi précédents
+
+    for kline in klines {
+        let open = parse(&kline.open);
+        let high = parse(&kline.high);
+        let low = parse(&kline.low);
+        let close = parse(&kline.close);
+
+        let ha_close = (open + high + low + close) / 4.0;
+        let ha_open = match previous {
+            Some((previous_open, previous_close)) => (previous_open + previous_close) / 2.0,
+            None => (open + close) / 2.0,
+        };
+
+        let mut bar = kline.clone();
+        bar.open = ha_open.to_string();
+        bar.high = high.max(ha_open).max(ha_close).to_string();
+        bar.low = low.min(ha_open).min(ha_close).to_string();
+        bar.close = ha_close.to_string();
+        transformed.push(bar);
+
+        previous = Some((ha_open, ha_close));
+    }
+
+    transformed
+}
+
+// Briques Renko construites sur les clôtures. Une brique est émise à chaque mouvement de
+// `brick_size` dans le sens de la tendance, un retournement nécessite deux briques.
+// Le volume des bougies (la première comprise) est attribué à la première brique qu'elles forment.
+// Les briques formées par une même bougie se partagent sa durée, pour garder des heures
+// d'ouverture strictement croissantes (`KlineManager`, indicateurs incrémentaux).
+pub fn renko(klines: &[KlineSummary], brick_size: f64) -> Vec<KlineSummary> {
+    let mut bricks = Vec::new();
+    if brick_size <= 0.0 {
+        return bricks;
+    }
+
+    let first = match klines.first() {
+        Some(first) => first,
+        None => return bricks,
+    };
+
+    let mut last_close = parse(&first.close);
+    let mut direction = 0.0; // 1.0 haussier, -1.0 baissier, 0.0 aucune brique
+    let mut pending: Option<KlineSummary> = Some(first.clone()); // Volumes accumulés depuis la dernière brique
+
+    for kline in klines.iter().skip(1) {
+        match &mut pending {
+            Some(current) => merge_kline(current, kline),
+            None => pending = Some(kline.clone()),
+        }
+
+        let close = parse(&kline.close);
+        let mut formed = Vec::new();
+        loop {
+            let up = close - last_close >= brick_size * if direction < 0.0 { 2.0 } else { 1.0 };
+            let down = last_close - close >= brick_size * if direction > 0.0 { 2.0 } else { 1.0 };
+            if !up && !down {
+                break;
+            }
+
+            let step = if up { 1.0 } else { -1.0 };
+            // Un retournement part de l'extrémité opposée de la dernière brique
+            let open = if direction != 0.0 && step != direction {
+                last_close + step * brick_size
+            } else {
+                last_close
+            };
+            let brick_close = open + step * brick_size;
+
+            let mut brick = match pending.take() {
+                Some(accumulated) => accumulated,
+                None => empty_like(kline),
+            };
+            brick.open = open.to_string();
+            brick.close = brick_close.to_string();
+            brick.high = open.max(brick_close).to_string();
+            brick.low = open.min(brick_close).to_string();
+            formed.push(brick);
+
+            last_close = brick_close;
+            direction = step;
+        }
+
+        // Durée de la bougie répartie entre ses briques
+        let duration = kline.close_time - kline.open_time + 1;
+        let count = formed.len() as i64;
+        for (index, mut brick) in (0..).zip(formed) {
+            brick.open_time = kline.open_time + duration * index / count;
+            brick.close_time = kline.open_time + duration * (index + 1) / count - 1;
+            bricks.push(brick);
+        }
+    }
+
+    bricks
+}
+
+// Barres de range : une barre se clôture dès que son amplitude atteint `range`. Les bougies sont
+// fusionnées entières (leur chemin intra-bougie est inconnu) : l'amplitude d'une barre peut donc
+// dépasser `range` (au plus `range` plus l'amplitude de la dernière bougie ajoutée).
+pub fn range_bars(klines: &[KlineSummary], range: f64) -> Vec<KlineSummary> {
+    let mut bars = Vec::new();
+    if range <= 0.0 {
+        return bars;
+    }
+
+    let mut current: Option<KlineSummary> = None;
+    for kline in klines {
+        match &mut current {
+            Some(bar) => merge_kline(bar, kline),
+            None => current = Some(kline.clone()),
+        }
+
+        if let Some(bar) = current.take_if(|bar| parse(&bar.high) - parse(&bar.low) >= range) {
+            bars.push(bar);
+        }
+    }
+
+    bars
+}
+
+fn empty_like(kline: &KlineSummary) -> KlineSummary {
+    let mut empty = kline.clone();
+    empty.volume = "0".to_string();
+    empty.quote_asset_volume = "0".to_string();
+    empty.number_of_trades = 0;
+    empty.taker_buy_base_asset_volume = "0".to_string();
+    empty.taker_buy_quote_asset_volume = "0".to_string();
+    empty
+}
@@ -0,0 +1,327 @@
+use std::fmt;
+
+use binance::model::KlineSummary;
+
+use super::Interval;
+
+// Type d'anomalie détectée sur une série de bougies
+#[derive(Debug, Clone, PartialEq)]
+pub enum IssueKind {
+    MissingIntervals { missing: usize }, // Bougies manquantes avant celle-ci
+    DuplicateOpenTime,
+    OutOfOrder,
+    ZeroVolume,
+    Unparseable { field: &'static str },
+    HighBelowLow,
+    CloseOutsideRange,
+    OpenOutsideRange,
+    OutlierSpike { z_score: f64 },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Issue {
+    pub index: usize, // Position de la bougie dans la série validée
+    pub open_time: i64,
+    pub kind: IssueKind,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValidationReport {
+    pub issues: Vec<Issue>,
+}
+
+impl ValidationReport {
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn count<F>(&self, predicate: F) -> usize
+    where
+        F: Fn(&IssueKind) -> bool,
+    {
+        self.issues.iter().filter(|issue| predicate(&issue.kind)).count()
+    }
+
+    pub fn missing_intervals(&self) -> usize {
+        self.issues
+            .iter()
+            .map(|issue| match issue.kind {
+                IssueKind::MissingIntervals { missing } => missing,
+                _ => 0,
+            })
+            .sum()
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} data quality issue(s)", self.issues.len())?;
+        for issue in self.issues.iter().take(10) {
+            write!(f, "\n  #{} (open time {}): {:?}", issue.index, issue.open_time, issue.kind)?;
+        }
+        if self.issues.len() > 10 {
+            write!(f, "\n  ... and {} more", self.issues.len() - 10)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationReport {}
+
+// Politique de réparation appliquée avant un backtest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepairPolicy {
+    ForwardFill, // Combler les trous et remplacer les bougies invalides par la clôture précédente
+    Drop,        // Supprimer les bougies invalides ou suspectes (les trous restent)
+    Fail,        // Refuser la série au moindre problème
+}
+
+// Les bougies sans volume et les pics aberrants sont suspects mais cohérents : un pic peut être un
+// vrai mouvement de marché. Ils sont signalés, conservés par `ForwardFill` et supprimés par `Drop`.
+fn is_suspicious(kind: &IssueKind) -> bool {
+    matches!(kind, IssueKind::ZeroVolume | IssueKind::OutlierSpike { .. })
+}
+
+#[derive(Debug, Clone)]
+pub struct Validator {
+    interval: Interval,
+    policy: RepairPolicy,
+    spike_lookback: usize,  // Nombre de rendements utilisés pour détecter un pic
+    spike_threshold: f64,   // Z-score au-delà duquel un rendement est considéré aberrant
+}
+
+// Valeurs numériques d'une bougie valide
+struct Prices {
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+    volume: f64,
+}
+
+impl Validator {
+    pub fn new(interval: Interval, policy: RepairPolicy) -> Self {
+        Self {
+            interval,
+            policy,
+            spike_lookback: 50,
+            spike_threshold: 10.0,
+        }
+    }
+
+    pub fn with_spike_detection(mut self, lookback: usize, threshold: f64) -> Self {
+        self.spike_lookback = lookback;
+        self.spike_threshold = threshold;
+        self
+    }
+
+    pub fn policy(&self) -> RepairPolicy {
+        self.policy
+    }
+
+    // Analyser la série sans la modifier
+    pub fn validate(&self, klines: &[KlineSummary]) -> ValidationReport {
+        let mut issues = Vec::new();
+        let mut last_open_time: Option<i64> = None;
+        let mut last_close: Option<f64> = None;
+        let mut returns: Vec<f64> = Vec::new();
+
+        for (index, kline) in klines.iter().enumerate() {
+            let mut push = |kind: IssueKind| {
+                issues.push(Issue {
+                    index,
+                    open_time: kline.open_time,
+                    kind,
+                })
+            };
+
+            if let Some(previous) = last_open_time {
+                if kline.open_time == previous {
+                    push(IssueKind::DuplicateOpenTime);
+                    continue;
+                }
+                if kline.open_time < previous {
+                    push(IssueKind::OutOfOrder);
+                    continue;
+                }
+
+                let missing = self.count_missing(previous, kline.open_time);
+                if missing > 0 {
+                    push(IssueKind::MissingIntervals { missing });
+                }
+            }
+            last_open_time = Some(kline.open_time);
+
+            let prices = match Self::parse_prices(kline) {
+                Ok(prices) => prices,
+                Err(field) => {
+                    push(IssueKind::Unparseable { field });
+                    continue;
+                }
+            };
+
+            let kinds = self.check_bar(&prices, last_close, &returns);
+            // Un pic retenu devient la nouvelle référence : un changement de niveau n'est signalé qu'une fois
+            let valid = kinds.iter().all(is_suspicious);
+            for kind in kinds {
+                push(kind);
+            }
+
+            if valid {
+                if let Some(previous_close) = last_close {
+                    if previous_close > 0.0 && prices.close > 0.0 {
+                        returns.push((prices.close / previous_close).ln());
+                    }
+                }
+                last_close = Some(prices.close);
+            }
+        }
+
+        ValidationReport { issues }
+    }
+
+    // Valider puis réparer la série selon la politique configurée
+    pub fn repair(&self, klines: &[KlineSummary]) -> Result<Vec<KlineSummary>, ValidationReport> {
+        let report = self.validate(klines);
+        if report.is_clean() {
+            return Ok(klines.to_vec());
+        }
+        if self.policy == RepairPolicy::Fail {
+            return Err(report);
+        }
+
+        let mut repaired: Vec<KlineSummary> = Vec::with_capacity(klines.len());
+        let mut issues = report.issues.iter().peekable();
+
+        for (index, kline) in klines.iter().enumerate() {
+            let mut kinds = Vec::new();
+            while let Some(issue) = issues.next_if(|issue| issue.index == index) {
+                kinds.push(&issue.kind);
+            }
+
+            // Les doublons et bougies désordonnées sont toujours écartés
+            if kinds
+                .iter()
+                .any(|kind| matches!(kind, IssueKind::DuplicateOpenTime | IssueKind::OutOfOrder))
+            {
+                continue;
+            }
+
+            let invalid = kinds
+                .iter()
+                .any(|kind| !matches!(kind, IssueKind::MissingIntervals { .. }) && !is_suspicious(kind));
+            let suspicious = kinds.iter().any(|kind| is_suspicious(kind));
+
+            match self.policy {
+                RepairPolicy::Drop => {
+                    if !invalid && !suspicious {
+                        repaired.push(kline.clone());
+                    }
+                }
+                RepairPolicy::ForwardFill => {
+                    if let Some(previous) = repaired.last().cloned() {
+                        let mut open_time = self.interval.next_bucket_start(previous.open_time);
+                        while open_time < kline.open_time {
+                            repaired.push(self.flat_kline(&previous, open_time));
+                            open_time = self.interval.next_bucket_start(open_time);
+                        }
+                    }
+
+                    match (invalid, repaired.last()) {
+                        (false, _) => repaired.push(kline.clone()),
+                        (true, Some(previous)) => {
+                            let filled = self.flat_kline(previous, kline.open_time);
+                            repaired.push(filled);
+                        }
+                        // Pas de clôture précédente pour combler : la bougie est abandonnée
+                        (true, None) => {}
+                    }
+                }
+                RepairPolicy::Fail => {}
+            }
+        }
+
+        Ok(repaired)
+    }
+
+    fn count_missing(&self, previous_open_time: i64, open_time: i64) -> usize {
+        let mut missing = 0;
+        let mut expected = self.interval.next_bucket_start(previous_open_time);
+        while expected < open_time {
+            missing += 1;
+            expected = self.interval.next_bucket_start(expected);
+        }
+        missing
+    }
+
+    fn parse_prices(kline: &KlineSummary) -> Result<Prices, &'static str> {
+        let parse = |value: &str, field: &'static str| value.parse::<f64>().ok().filter(|v| v.is_finite()).ok_or(field);
+
+        Ok(Prices {
+            open: parse(&kline.open, "open")?,
+            high: parse(&kline.high, "high")?,
+            low: parse(&kline.low, "low")?,
+            close: parse(&kline.close, "close")?,
+            volume: parse(&kline.volume, "volume")?,
+        })
+    }
+
+    fn check_bar(&self, prices: &Prices, last_close: Option<f64>, returns: &[f64]) -> Vec<IssueKind> {
+        let mut kinds = Vec::new();
+
+        if prices.volume == 0.0 {
+            kinds.push(IssueKind::ZeroVolume);
+        }
+        if prices.high < prices.low {
+            kinds.push(IssueKind::HighBelowLow);
+            return kinds;
+        }
+        if prices.close > prices.high || prices.close < prices.low {
+            kinds.push(IssueKind::CloseOutsideRange);
+        }
+        if prices.open > prices.high || prices.open < prices.low {
+            kinds.push(IssueKind::OpenOutsideRange);
+        }
+
+        if let Some(previous_close) = last_close {
+            if let Some(z_score) = self.spike_z_score(previous_close, prices.close, returns) {
+                kinds.push(IssueKind::OutlierSpike { z_score });
+            }
+        }
+
+        kinds
+    }
+
+    fn spike_z_score(&self, previous_close: f64, close: f64, returns: &[f64]) -> Option<f64> {
+        if self.spike_lookback < 2 || returns.len() < self.spike_lookback || previous_close <= 0.0 || close <= 0.0 {
+            return None;
+        }
+
+        let window = &returns[returns.len() - self.spike_lookback..];
+        let mean = window.iter().sum::<f64>() / window.len() as f64;
+        let variance = window.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (window.len() - 1) as f64;
+        let std_dev = variance.sqrt();
+        if std_dev == 0.0 {
+            return None;
+        }
+
+        let z_score = ((close / previous_close).ln() - mean) / std_dev;
+        (z_score.abs() > self.spike_threshold).then_some(z_score)
+    }
+
+    // Bougie plate à la clôture précédente, sans volume
+    fn flat_kline(&self, previous: &KlineSummary, open_time: i64) -> KlineSummary {
+        let mut filled = previous.clone();
+        filled.open_time = open_time;
+        filled.close_time = self.interval.next_bucket_start(open_time) - 1;
+        filled.open = previous.close.clone();
+        filled.high = previous.close.clone();
+        filled.low = previous.close.clone();
+        filled.volume = "0".to_string();
+        filled.quote_asset_volume = "0".to_string();
+        filled.number_of_trades = 0;
+        filled.taker_buy_base_asset_volume = "0".to_string();
+        filled.taker_buy_quote_asset_volume = "0".to_string();
+        filled
+    }
+}
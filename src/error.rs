@@ -0,0 +1,83 @@
+use std::fmt;
+
+use crate::data::{DownloadError, ValidationReport};
+
+#[derive(Debug)]
+pub enum Error {
+    // Pas assez de bougies (ou de valeurs d'indicateur) pour effectuer le calcul
+    InsufficientData { required: usize, available: usize },
+    InvalidParameter { name: &'static str, reason: String },
+    MissingIndicator(&'static str),
+    // Règle de stratégie invalide (syntaxe, indicateur inconnu, type d'expression)
+    InvalidRule { rule: String, reason: String },
+    // Champ numérique d'une bougie impossible à convertir
+    InvalidNumber { field: &'static str, value: String },
+    InvalidTimestamp(i64),
+    InvalidData(ValidationReport),
+    Download(Box<DownloadError>),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    pub fn invalid_parameter(name: &'static str, reason: impl Into<String>) -> Self {
+        Error::InvalidParameter {
+            name,
+            reason: reason.into(),
+        }
+    }
+
+    pub fn invalid_rule(rule: &str, reason: impl Into<String>) -> Self {
+        Error::InvalidRule {
+            rule: rule.to_string(),
+            reason: reason.into(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InsufficientData { required, available } => {
+                write!(f, "Insufficient data: {} values required, {} available", required, available)
+            }
+            Error::InvalidParameter { name, reason } => write!(f, "Invalid parameter `{}`: {}", name, reason),
+            Error::MissingIndicator(name) => write!(f, "Indicator {} is not registered in the kline manager", name),
+            Error::InvalidRule { rule, reason } => write!(f, "Invalid rule `{}`: {}", rule, reason),
+            Error::InvalidNumber { field, value } => write!(f, "Invalid number for {}: {:?}", field, value),
+            Error::InvalidTimestamp(timestamp) => write!(f, "Invalid timestamp: {}", timestamp),
+            Error::InvalidData(report) => write!(f, "Invalid klines: {}", report),
+            Error::Download(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::InvalidData(report) => Some(report),
+            Error::Download(error) => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<ValidationReport> for Error {
+    fn from(report: ValidationReport) -> Self {
+        Error::InvalidData(report)
+    }
+}
+
+impl From<DownloadError> for Error {
+    fn from(error: DownloadError) -> Self {
+        Error::Download(Box::new(error))
+    }
+}
+
+// Convertir un champ numérique d'une bougie (les champs Binance sont des chaînes)
+pub fn parse_field(field: &'static str, value: &str) -> Result<f64> {
+    value.parse::<f64>().map_err(|_| Error::InvalidNumber {
+        field,
+        value: value.to_string(),
+    })
+}
@@ -0,0 +1,122 @@
+use std::any::Any;
+
+use binance::model::KlineSummary;
+
+use crate::data::{Candles, RingBuffer};
+use crate::strategy::interface::Observer;
+use crate::Result;
+
+use super::atr::true_range;
+use super::batch::Series;
+use super::kernels;
+use super::moving_average::{MovingAverage, Rma};
+use super::{unseen_klines, PriceSource};
+
+// Directional Movement Index : +DI, -DI et ADX (moyennes de Wilder)
+#[derive(Debug)]
+pub struct Adx {
+    pub plus_di: RingBuffer<f64>,
+    pub minus_di: RingBuffer<f64>,
+    pub adx: RingBuffer<f64>,
+    true_range: Rma,
+    plus_dm: Rma,
+    minus_dm: Rma,
+    smoothing: Rma,
+    previous: Option<(f64, f64, f64)>, // (high, low, close) de la bougie précédente
+    last_open_time: Option<i64>,
+}
+
+impl Adx {
+    pub fn new(klines: &[KlineSummary], di_length: usize, adx_smoothing: usize) -> Result<Self> {
+        let mut indicator = Self {
+            plus_di: RingBuffer::new(),
+            minus_di: RingBuffer::new(),
+            adx: RingBuffer::new(),
+            true_range: Rma::new(di_length)?,
+            plus_dm: Rma::new(di_length)?,
+            minus_dm: Rma::new(di_length)?,
+            smoothing: Rma::new(adx_smoothing)?,
+            previous: None,
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    pub fn compute(candles: &Candles, di_length: usize, adx_smoothing: usize) -> Result<Series> {
+        Self::new(&[], di_length, adx_smoothing)?;
+
+        let (high, low) = (&candles.high, &candles.low);
+        let mut true_ranges = kernels::true_range(high, low, &candles.close);
+        let mut plus_dm = vec![f64::NAN; candles.len()];
+        let mut minus_dm = vec![f64::NAN; candles.len()];
+        // Les mouvements directionnels (et le True Range) commencent à la deuxième bougie
+        if let Some(first) = true_ranges.first_mut() {
+            *first = f64::NAN;
+        }
+        for i in 1..candles.len() {
+            let up = high[i] - high[i - 1];
+            let down = low[i - 1] - low[i];
+            plus_dm[i] = if up > down && up > 0.0 { up } else { 0.0 };
+            minus_dm[i] = if down > up && down > 0.0 { down } else { 0.0 };
+        }
+
+        let range = kernels::rma(&true_ranges, di_length);
+        let plus_di = kernels::zip_with(&kernels::rma(&plus_dm, di_length), &range, |dm, range| 100.0 * dm / range);
+        let minus_di = kernels::zip_with(&kernels::rma(&minus_dm, di_length), &range, |dm, range| 100.0 * dm / range);
+        let dx = kernels::zip_with(&plus_di, &minus_di, |plus, minus| {
+            let sum = plus + minus;
+            (plus - minus).abs() / if sum == 0.0 { 1.0 } else { sum }
+        });
+        let adx = kernels::rma(&dx, adx_smoothing).into_iter().map(|adx| 100.0 * adx).collect();
+
+        Ok(Series::new(vec![("plus_di", plus_di), ("minus_di", minus_di), ("adx", adx)]))
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            let high = PriceSource::High.extract(kline);
+            let low = PriceSource::Low.extract(kline);
+            let close = PriceSource::Close.extract(kline);
+
+            let (plus_di, minus_di, adx) = match self.previous.replace((high, low, close)) {
+                Some((previous_high, previous_low, previous_close)) => {
+                    let up = high - previous_high;
+                    let down = previous_low - low;
+                    let plus_dm = if up > down && up > 0.0 { up } else { 0.0 };
+                    let minus_dm = if down > up && down > 0.0 { down } else { 0.0 };
+
+                    let range = self.true_range.next(true_range(kline, Some(previous_close)));
+                    let plus_di = 100.0 * self.plus_dm.next(plus_dm) / range;
+                    let minus_di = 100.0 * self.minus_dm.next(minus_dm) / range;
+
+                    let sum = plus_di + minus_di;
+                    let adx = self.smoothing.next((plus_di - minus_di).abs() / if sum == 0.0 { 1.0 } else { sum });
+                    (plus_di, minus_di, 100.0 * adx)
+                }
+                None => (f64::NAN, f64::NAN, f64::NAN),
+            };
+
+            self.plus_di.push(plus_di);
+            self.minus_di.push(minus_di);
+            self.adx.push(adx);
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+}
+
+impl Observer for Adx {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.plus_di.set_capacity(capacity);
+        self.minus_di.set_capacity(capacity);
+        self.adx.set_capacity(capacity);
+    }
+}
@@ -0,0 +1,94 @@
+use std::any::Any;
+
+use binance::model::KlineSummary;
+
+use crate::data::{Candles, RingBuffer};
+use crate::strategy::interface::Observer;
+use crate::{Error, Result};
+
+use super::batch::Series;
+use super::kernels;
+use super::window::RollingWindow;
+use super::{unseen_klines, PriceSource};
+
+// Aroon : ancienneté du plus haut et du plus bas sur `length` bougies, de 0 à 100
+#[derive(Debug)]
+pub struct Aroon {
+    pub up: RingBuffer<f64>,
+    pub down: RingBuffer<f64>,
+    pub oscillator: RingBuffer<f64>, // up - down
+    highs: RollingWindow,
+    lows: RollingWindow,
+    length: usize,
+    last_open_time: Option<i64>,
+}
+
+impl Aroon {
+    pub fn new(klines: &[KlineSummary], length: usize) -> Result<Self> {
+        if length == 0 {
+            return Err(Error::invalid_parameter("length", "must be greater than 0"));
+        }
+
+        let mut indicator = Self {
+            up: RingBuffer::new(),
+            down: RingBuffer::new(),
+            oscillator: RingBuffer::new(),
+            // La bougie courante plus les `length` précédentes
+            highs: RollingWindow::new(length + 1),
+            lows: RollingWindow::new(length + 1),
+            length,
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    pub fn compute(candles: &Candles, length: usize) -> Result<Series> {
+        Self::new(&[], length)?;
+
+        let score = |since: f64| 100.0 * (length as f64 - since) / length as f64;
+        let up: Vec<f64> = kernels::rolling_bars_since_max(&candles.high, length + 1).into_iter().map(score).collect();
+        let down: Vec<f64> = kernels::rolling_bars_since_min(&candles.low, length + 1).into_iter().map(score).collect();
+        let oscillator = kernels::zip_with(&up, &down, |up, down| up - down);
+        Ok(Series::new(vec![("up", up), ("down", down), ("oscillator", oscillator)]))
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            self.highs.push(PriceSource::High.extract(kline));
+            self.lows.push(PriceSource::Low.extract(kline));
+
+            let (up, down) = match (self.highs.position_of_max(), self.lows.position_of_min()) {
+                (Some(since_high), Some(since_low)) if self.highs.is_full() => {
+                    let length = self.length as f64;
+                    (
+                        100.0 * (length - since_high as f64) / length,
+                        100.0 * (length - since_low as f64) / length,
+                    )
+                }
+                _ => (f64::NAN, f64::NAN),
+            };
+
+            self.up.push(up);
+            self.down.push(down);
+            self.oscillator.push(up - down);
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+}
+
+impl Observer for Aroon {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.up.set_capacity(capacity);
+        self.down.set_capacity(capacity);
+        self.oscillator.set_capacity(capacity);
+    }
+}
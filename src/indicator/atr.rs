@@ -0,0 +1,93 @@
+use std::any::Any;
+
+use binance::model::KlineSummary;
+
+use crate::data::{Candles, RingBuffer};
+use crate::strategy::interface::Observer;
+use crate::Result;
+
+use super::batch::Series;
+use super::kernels;
+use super::moving_average::{MovingAverage, Rma};
+use super::{unseen_klines, PriceSource};
+
+// True Range d'une bougie ; sans clôture précédente, c'est simplement high - low (comme `ta.tr(true)`)
+pub(crate) fn true_range(kline: &KlineSummary, previous_close: Option<f64>) -> f64 {
+    let high = PriceSource::High.extract(kline);
+    let low = PriceSource::Low.extract(kline);
+
+    match previous_close {
+        Some(previous_close) => (high - low)
+            .max((high - previous_close).abs())
+            .max((low - previous_close).abs()),
+        None => high - low,
+    }
+}
+
+// Calcul incrémental de l'ATR (RMA du True Range), réutilisé par les canaux et les stops
+#[derive(Debug, Clone)]
+pub(crate) struct AtrCalculator {
+    rma: Rma,
+    previous_close: Option<f64>,
+}
+
+impl AtrCalculator {
+    pub(crate) fn new(length: usize) -> Result<Self> {
+        Ok(Self {
+            rma: Rma::new(length)?,
+            previous_close: None,
+        })
+    }
+
+    pub(crate) fn next(&mut self, kline: &KlineSummary) -> f64 {
+        let tr = true_range(kline, self.previous_close);
+        self.previous_close = Some(PriceSource::Close.extract(kline));
+        self.rma.next(tr)
+    }
+}
+
+// Average True Range, une valeur par bougie (NaN pendant l'initialisation)
+#[derive(Debug)]
+pub struct Atr {
+    pub values: RingBuffer<f64>,
+    atr: AtrCalculator,
+    last_open_time: Option<i64>,
+}
+
+impl Atr {
+    pub fn new(klines: &[KlineSummary], length: usize) -> Result<Self> {
+        let mut indicator = Self {
+            values: RingBuffer::new(),
+            atr: AtrCalculator::new(length)?,
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    pub fn compute(candles: &Candles, length: usize) -> Result<Series> {
+        AtrCalculator::new(length)?;
+        Ok(Series::single(kernels::atr(&candles.high, &candles.low, &candles.close, length)))
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            self.values.push(self.atr.next(kline));
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+}
+
+impl Observer for Atr {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.values.set_capacity(capacity);
+    }
+}
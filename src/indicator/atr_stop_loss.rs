@@ -1,89 +1,72 @@
 use binance::model::KlineSummary;
+use crate::data::{Candles, RingBuffer};
 use crate::strategy::interface::Observer;
+use crate::{Error, Result};
+
+use super::atr::AtrCalculator;
+use super::batch::Series;
+use super::kernels;
+use super::stop::{last_stop, Side, StopIndicator};
+use super::unseen_klines;
 use std::any::Any;
 
 #[derive(Debug)]
 pub struct ATRStopLoss {
-    pub stop_losses: Vec<f64>, // Vecteur pour stocker plusieurs valeurs de Stop Loss
-    length: usize,             // Longueur pour le calcul de l'ATR
+    pub stop_losses: RingBuffer<f64>, // Vecteur pour stocker plusieurs valeurs de Stop Loss
+    pub short_stop_losses: RingBuffer<f64>, // Stop Loss des positions courtes (clôture + ATR × multiplicateur)
     multiplier: f64,           // Multiplicateur pour le calcul du Stop Loss
+    atr: AtrCalculator,        // ATR de TradingView (RMA des True Ranges), mise à jour bougie par bougie
+    last_open_time: Option<i64>,
 }
 
 impl ATRStopLoss {
-    pub fn new(klines: &[KlineSummary], length: usize, multiplier: f64) -> Self {
-        let mut stop_losses = Vec::new();
-        
-        // Calculer les valeurs initiales pour chaque période possible
-        for i in length..=klines.len() {
-            let atr = Self::calculate_atr(&klines[0..i], length);
-            let stop_loss = Self::calculate_stop_loss(&klines[0..i], atr, multiplier);
-            stop_losses.push(stop_loss);
+    pub fn new(klines: &[KlineSummary], length: usize, multiplier: f64) -> Result<Self> {
+        if length == 0 {
+            return Err(Error::invalid_parameter("length", "must be greater than 0"));
         }
-
-        Self { stop_losses, length, multiplier }
-    }
-
-    // Ajouter une nouvelle valeur basée sur les données actuelles de klines
-    pub fn add(&mut self, klines: &[KlineSummary]) {
-        if klines.len() < self.length {
-            return; // Ne pas calculer si les données sont insuffisantes
+        if !multiplier.is_finite() || multiplier < 0.0 {
+            return Err(Error::invalid_parameter("multiplier", "must be a positive number"));
         }
 
-        let atr = Self::calculate_atr(klines, self.length);
-        let stop_loss = Self::calculate_stop_loss(klines, atr, self.multiplier);
-        self.stop_losses.push(stop_loss); // Ajouter la nouvelle valeur de Stop Loss calculée au vecteur
-    }
-
-    fn calculate_true_range(current: &KlineSummary, previous_close: f64) -> f64 {
-        let high = current.high.parse::<f64>().unwrap_or(0.0);
-        let low = current.low.parse::<f64>().unwrap_or(0.0);
-        (high - low)
-            .max((high - previous_close).abs())
-            .max((low - previous_close).abs())
+        let mut indicator = Self {
+            stop_losses: RingBuffer::new(),
+            short_stop_losses: RingBuffer::new(),
+            multiplier,
+            atr: AtrCalculator::new(length)?,
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
     }
 
-    fn calculate_atr(klines: &[KlineSummary], length: usize) -> f64 {
-        if klines.len() < length {
-            return 0.0;
-        }
-
-        let mut true_ranges = Vec::new();
+    // Calcul par lot : sorties "long" et "short"
+    pub fn compute(candles: &Candles, length: usize, multiplier: f64) -> Result<Series> {
+        Self::new(&[], length, multiplier)?;
 
-        // Calculate True Ranges
-        for i in 1..klines.len() {
-            let current = &klines[i];
-            let previous_close = klines[i - 1].close.parse::<f64>().unwrap_or(0.0);
-            let tr = Self::calculate_true_range(current, previous_close);
-            true_ranges.push(tr);
-        }
-
-        // Calculate RMA
-        Self::calculate_rma(&true_ranges, length)
+        let atr = kernels::atr(&candles.high, &candles.low, &candles.close, length);
+        let long = kernels::zip_with(&candles.close, &atr, |close, atr| Self::calculate_stop_loss(close, atr, multiplier));
+        let short = kernels::zip_with(&candles.close, &atr, |close, atr| Self::calculate_stop_loss(close, atr, -multiplier));
+        Ok(Series::new(vec![("long", long), ("short", short)]))
     }
 
-    fn calculate_rma(values: &[f64], length: usize) -> f64 {
-        if values.is_empty() || length == 0 {
-            return 0.0;
-        }
+    // Intégrer les klines pas encore traitées : l'ATR est incrémental, l'historique complet
+    // n'a pas besoin d'être conservé
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            let close = kline.close.parse::<f64>().unwrap_or(0.0);
 
-        // Start with the Simple Moving Average for the first 'length' periods
-        let mut rma = values.iter().take(length).sum::<f64>() / (length as f64);
-        let alpha = 1.0 / (length as f64);
+            // Comme `ta.atr` : le premier True Range vaut high - low (NaN tant que `length` valeurs ne sont pas connues)
+            let atr = self.atr.next(kline);
+            self.last_open_time = Some(kline.open_time);
 
-        // Apply RMA formula: RMA = (Previous RMA * (length - 1) + Current TR) / length
-        for value in values.iter().skip(length) {
-            rma = (value * alpha) + (rma * (1.0 - alpha));
+            // NaN tant que l'ATR n'est pas initialisé : une valeur par bougie
+            self.stop_losses.push(Self::calculate_stop_loss(close, atr, self.multiplier));
+            self.short_stop_losses.push(Self::calculate_stop_loss(close, atr, -self.multiplier));
         }
-
-        rma
     }
 
-    fn calculate_stop_loss(klines: &[KlineSummary], atr: f64, multiplier: f64) -> f64 {
-        if let Some(last_kline) = klines.last() {
-            let close = last_kline.close.parse::<f64>().unwrap_or(0.0);
-            return close - (atr * multiplier); // Stop Loss based on ATR multiplier
-        }
-        0.0
+    fn calculate_stop_loss(close: f64, atr: f64, multiplier: f64) -> f64 {
+        close - (atr * multiplier) // Stop Loss based on ATR multiplier (multiplicateur négatif pour un short)
     }
 }
 
@@ -95,6 +78,24 @@ impl Observer for ATRStopLoss {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.stop_losses.set_capacity(capacity);
+        self.short_stop_losses.set_capacity(capacity);
+    }
+
+    fn as_stop_indicator(&self) -> Option<&dyn StopIndicator> {
+        Some(self)
+    }
+}
+
+impl StopIndicator for ATRStopLoss {
+    fn stop(&self, side: Side) -> Option<f64> {
+        match side {
+            Side::Long => last_stop(&self.stop_losses),
+            Side::Short => last_stop(&self.short_stop_losses),
+        }
+    }
 }
 
 
@@ -0,0 +1,394 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use crate::data::Candles;
+use crate::strategy::interface::Observer;
+use crate::Result;
+
+use super::*;
+
+// Résultat d'un calcul par lot : une ou plusieurs colonnes nommées, une valeur par bougie
+// (mêmes noms de sorties que les nœuds de séries, "value" pour un indicateur à une sortie)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Series {
+    outputs: Vec<(&'static str, Vec<f64>)>,
+}
+
+impl Series {
+    pub fn new(outputs: Vec<(&'static str, Vec<f64>)>) -> Self {
+        Self { outputs }
+    }
+
+    // Série à une seule sortie, nommée "value"
+    pub fn single(values: Vec<f64>) -> Self {
+        Self::new(vec![("value", values)])
+    }
+
+    pub fn get(&self, output: &str) -> Option<&[f64]> {
+        self.outputs.iter().find(|(name, _)| *name == output).map(|(_, values)| values.as_slice())
+    }
+
+    // Première sortie (la valeur principale de l'indicateur)
+    pub fn value(&self) -> &[f64] {
+        self.outputs.first().map_or(&[], |(_, values)| values.as_slice())
+    }
+
+    pub fn outputs(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.outputs.iter().map(|(name, _)| *name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.value().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value().is_empty()
+    }
+}
+
+// Indicateur et paramètres calculables par lot. Restent uniquement incrémentaux :
+// - `ZigZag` et `SupportResistance`, dont le résultat n'est pas une valeur par bougie (swings datés
+//   a posteriori, ensemble de zones fusionnées au fil des touches) ;
+// - `PivotPoints`, dont les niveaux suivent la clôture des bougies de l'unité supérieure par le
+//   `Resampler` ;
+// - `RollingCorrelation`, qui apparie les bougies d'un second symbole.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IndicatorSpec {
+    MovingAverage { kind: MovingAverageKind, period: usize, source: PriceSource },
+    Rsi { period: usize, source: PriceSource },
+    Macd { fast_length: usize, slow_length: usize, signal_length: usize, source: PriceSource },
+    Stochastic { length: usize, smooth_k: usize, smooth_d: usize },
+    StochasticRsi { rsi_length: usize, stoch_length: usize, smooth_k: usize, smooth_d: usize, source: PriceSource },
+    Cci { length: usize, source: PriceSource },
+    WilliamsR { length: usize },
+    RateOfChange { length: usize, source: PriceSource },
+    Atr { length: usize },
+    BollingerBands { length: usize, multiplier: f64, source: PriceSource },
+    KeltnerChannel { length: usize, atr_length: usize, multiplier: f64, source: PriceSource },
+    AtrBands { atr_length: usize, multiplier: f64, source: PriceSource },
+    StandardDeviation { length: usize, source: PriceSource },
+    ZScore { length: usize, source: PriceSource },
+    DonchianChannel { length: usize, offset: usize },
+    ChoppinessIndex { length: usize },
+    AtrStopLoss { length: usize, multiplier: f64 },
+    Adx { di_length: usize, adx_smoothing: usize },
+    Aroon { length: usize },
+    Vortex { length: usize },
+    SuperTrend { atr_length: usize, factor: f64 },
+    LinearRegression { length: usize, source: PriceSource },
+    Obv,
+    Mfi { length: usize },
+    AccumulationDistribution,
+    ChaikinMoneyFlow { length: usize },
+    Ichimoku { conversion_length: usize, base_length: usize, leading_b_length: usize, displacement: usize },
+    ChandelierExit { length: usize, atr_length: usize, multiplier: f64 },
+    ParabolicSar { start: f64, increment: f64, maximum: f64 },
+    SwingStop { left: usize, right: usize },
+    Fractals { left: usize, right: usize },
+    Squeeze { length: usize, bollinger_multiplier: f64, keltner_multiplier: f64 },
+    TakerBuySellRatio { length: usize },
+    Vwap { anchor: VwapAnchor, multiplier: f64, source: PriceSource },
+    VolumeProfile { length: usize, bins: usize, value_area: f64 },
+    Autocorrelation { length: usize, lag: usize, source: PriceSource },
+    HurstExponent { length: usize, source: PriceSource },
+    CandlestickPatterns { tolerance: PatternTolerance },
+}
+
+impl IndicatorSpec {
+    pub fn compute(&self, candles: &Candles) -> Result<Series> {
+        match *self {
+            IndicatorSpec::MovingAverage { kind, period, source } => kind.compute(candles, period, source),
+            IndicatorSpec::Rsi { period, source } => Rsi::compute(candles, period, source),
+            IndicatorSpec::Macd { fast_length, slow_length, signal_length, source } => {
+                Macd::compute(candles, fast_length, slow_length, signal_length, source)
+            }
+            IndicatorSpec::Stochastic { length, smooth_k, smooth_d } => Stochastic::compute(candles, length, smooth_k, smooth_d),
+            IndicatorSpec::StochasticRsi { rsi_length, stoch_length, smooth_k, smooth_d, source } => {
+                StochasticRsi::compute(candles, rsi_length, stoch_length, smooth_k, smooth_d, source)
+            }
+            IndicatorSpec::Cci { length, source } => Cci::compute(candles, length, source),
+            IndicatorSpec::WilliamsR { length } => WilliamsR::compute(candles, length),
+            IndicatorSpec::RateOfChange { length, source } => RateOfChange::compute(candles, length, source),
+            IndicatorSpec::Atr { length } => Atr::compute(candles, length),
+            IndicatorSpec::BollingerBands { length, multiplier, source } => {
+                BollingerBands::compute(candles, length, multiplier, source)
+            }
+            IndicatorSpec::KeltnerChannel { length, atr_length, multiplier, source } => {
+                KeltnerChannel::compute(candles, length, atr_length, multiplier, source)
+            }
+            IndicatorSpec::AtrBands { atr_length, multiplier, source } => AtrBands::compute(candles, atr_length, multiplier, source),
+            IndicatorSpec::StandardDeviation { length, source } => StandardDeviation::compute(candles, length, source),
+            IndicatorSpec::ZScore { length, source } => ZScore::compute(candles, length, source),
+            IndicatorSpec::DonchianChannel { length, offset } => DonchianChannel::compute(candles, length, offset),
+            IndicatorSpec::ChoppinessIndex { length } => ChoppinessIndex::compute(candles, length),
+            IndicatorSpec::AtrStopLoss { length, multiplier } => ATRStopLoss::compute(candles, length, multiplier),
+            IndicatorSpec::Adx { di_length, adx_smoothing } => Adx::compute(candles, di_length, adx_smoothing),
+            IndicatorSpec::Aroon { length } => Aroon::compute(candles, length),
+            IndicatorSpec::Vortex { length } => Vortex::compute(candles, length),
+            IndicatorSpec::SuperTrend { atr_length, factor } => SuperTrend::compute(candles, atr_length, factor),
+            IndicatorSpec::LinearRegression { length, source } => LinearRegression::compute(candles, length, source),
+            IndicatorSpec::Obv => Ok(Obv::compute(candles)),
+            IndicatorSpec::Mfi { length } => Mfi::compute(candles, length),
+            IndicatorSpec::AccumulationDistribution => Ok(AccumulationDistribution::compute(candles)),
+            IndicatorSpec::ChaikinMoneyFlow { length } => ChaikinMoneyFlow::compute(candles, length),
+            IndicatorSpec::Ichimoku { conversion_length, base_length, leading_b_length, displacement } => {
+                Ichimoku::compute(candles, conversion_length, base_length, leading_b_length, displacement)
+            }
+            IndicatorSpec::ChandelierExit { length, atr_length, multiplier } => {
+                ChandelierExit::compute(candles, length, atr_length, multiplier)
+            }
+            IndicatorSpec::ParabolicSar { start, increment, maximum } => ParabolicSar::compute(candles, start, increment, maximum),
+            IndicatorSpec::SwingStop { left, right } => SwingStop::compute(candles, left, right),
+            IndicatorSpec::Fractals { left, right } => Fractals::compute(candles, left, right),
+            IndicatorSpec::Squeeze { length, bollinger_multiplier, keltner_multiplier } => {
+                Squeeze::compute(candles, length, bollinger_multiplier, keltner_multiplier)
+            }
+            IndicatorSpec::TakerBuySellRatio { length } => TakerBuySellRatio::compute(candles, length),
+            IndicatorSpec::Vwap { anchor, multiplier, source } => Vwap::compute(candles, anchor, multiplier, source),
+            IndicatorSpec::VolumeProfile { length, bins, value_area } => VolumeProfile::compute(candles, length, bins, value_area),
+            IndicatorSpec::Autocorrelation { length, lag, source } => Autocorrelation::compute(candles, length, lag, source),
+            IndicatorSpec::HurstExponent { length, source } => HurstExponent::compute(candles, length, source),
+            IndicatorSpec::CandlestickPatterns { tolerance } => CandlestickPatterns::compute(candles, tolerance),
+        }
+    }
+
+    // Indicateur incrémental équivalent, sous forme de nœud du graphe de séries (mêmes noms de
+    // sorties que `compute`)
+    pub fn node(&self) -> Result<Box<dyn SeriesNode>> {
+        fn node<T: Observer + Debug + 'static>(
+            indicator: T,
+            outputs: &'static [&'static str],
+            last: fn(&T, &mut [f64]),
+        ) -> Box<dyn SeriesNode> {
+            Box::new(IndicatorNode::new(indicator, outputs, last))
+        }
+        fn last(series: &[f64]) -> f64 {
+            series.last().copied().unwrap_or(f64::NAN)
+        }
+        fn last_flag(series: &[bool]) -> f64 {
+            series.last().map_or(f64::NAN, |flag| f64::from(u8::from(*flag)))
+        }
+
+        let value: &'static [&'static str] = &["value"];
+        let bands: &'static [&'static str] = &["basis", "upper", "lower"];
+        Ok(match *self {
+            IndicatorSpec::MovingAverage { kind, period, source } => {
+                node(MovingAverageIndicator::new(&[], kind.build(period)?, source), value, |i, o| o[0] = last(&i.values))
+            }
+            IndicatorSpec::Rsi { period, source } => node(Rsi::new(&[], period, source)?, value, |i, o| o[0] = last(&i.values)),
+            IndicatorSpec::Macd { fast_length, slow_length, signal_length, source } => node(
+                Macd::new(&[], fast_length, slow_length, signal_length, source)?,
+                &["macd", "signal", "histogram"],
+                |i, o| o.copy_from_slice(&[last(&i.macd), last(&i.signal), last(&i.histogram)]),
+            ),
+            IndicatorSpec::Stochastic { length, smooth_k, smooth_d } => {
+                node(Stochastic::new(&[], length, smooth_k, smooth_d)?, &["k", "d"], |i, o| {
+                    o.copy_from_slice(&[last(&i.k), last(&i.d)])
+                })
+            }
+            IndicatorSpec::StochasticRsi { rsi_length, stoch_length, smooth_k, smooth_d, source } => node(
+                StochasticRsi::new(&[], rsi_length, stoch_length, smooth_k, smooth_d, source)?,
+                &["k", "d"],
+                |i, o| o.copy_from_slice(&[last(&i.k), last(&i.d)]),
+            ),
+            IndicatorSpec::Cci { length, source } => node(Cci::new(&[], length, source)?, value, |i, o| o[0] = last(&i.values)),
+            IndicatorSpec::WilliamsR { length } => node(WilliamsR::new(&[], length)?, value, |i, o| o[0] = last(&i.values)),
+            IndicatorSpec::RateOfChange { length, source } => {
+                node(RateOfChange::new(&[], length, source)?, value, |i, o| o[0] = last(&i.values))
+            }
+            IndicatorSpec::Atr { length } => node(Atr::new(&[], length)?, value, |i, o| o[0] = last(&i.values)),
+            IndicatorSpec::BollingerBands { length, multiplier, source } => node(
+                BollingerBands::new(&[], length, multiplier, source)?,
+                &["basis", "upper", "lower", "percent_b", "bandwidth"],
+                |i, o| {
+                    o.copy_from_slice(&[
+                        last(&i.basis),
+                        last(&i.upper_band),
+                        last(&i.lower_band),
+                        last(&i.percent_b),
+                        last(&i.bandwidth),
+                    ])
+                },
+            ),
+            IndicatorSpec::KeltnerChannel { length, atr_length, multiplier, source } => {
+                node(KeltnerChannel::new(&[], length, atr_length, multiplier, source)?, bands, |i, o| {
+                    o.copy_from_slice(&[last(&i.basis), last(&i.upper_band), last(&i.lower_band)])
+                })
+            }
+            IndicatorSpec::AtrBands { atr_length, multiplier, source } => {
+                node(AtrBands::new(&[], atr_length, multiplier, source)?, bands, |i, o| {
+                    o.copy_from_slice(&[last(&i.basis), last(&i.upper_band), last(&i.lower_band)])
+                })
+            }
+            IndicatorSpec::StandardDeviation { length, source } => {
+                node(StandardDeviation::new(&[], length, source)?, value, |i, o| o[0] = last(&i.values))
+            }
+            IndicatorSpec::ZScore { length, source } => node(ZScore::new(&[], length, source)?, value, |i, o| o[0] = last(&i.values)),
+            IndicatorSpec::DonchianChannel { length, offset } => {
+                node(DonchianChannel::new(&[], length, offset)?, &["upper", "lower", "basis"], |i, o| {
+                    o.copy_from_slice(&[last(&i.upper_band), last(&i.lower_band), last(&i.basis)])
+                })
+            }
+            IndicatorSpec::ChoppinessIndex { length } => {
+                node(ChoppinessIndex::new(&[], length)?, value, |i, o| o[0] = last(&i.values))
+            }
+            IndicatorSpec::AtrStopLoss { length, multiplier } => {
+                node(ATRStopLoss::new(&[], length, multiplier)?, &["long", "short"], |i, o| {
+                    o.copy_from_slice(&[last(&i.stop_losses), last(&i.short_stop_losses)])
+                })
+            }
+            IndicatorSpec::Adx { di_length, adx_smoothing } => {
+                node(Adx::new(&[], di_length, adx_smoothing)?, &["plus_di", "minus_di", "adx"], |i, o| {
+                    o.copy_from_slice(&[last(&i.plus_di), last(&i.minus_di), last(&i.adx)])
+                })
+            }
+            IndicatorSpec::Aroon { length } => node(Aroon::new(&[], length)?, &["up", "down", "oscillator"], |i, o| {
+                o.copy_from_slice(&[last(&i.up), last(&i.down), last(&i.oscillator)])
+            }),
+            IndicatorSpec::Vortex { length } => node(Vortex::new(&[], length)?, &["plus", "minus"], |i, o| {
+                o.copy_from_slice(&[last(&i.plus), last(&i.minus)])
+            }),
+            IndicatorSpec::SuperTrend { atr_length, factor } => {
+                node(SuperTrend::new(&[], atr_length, factor)?, &["value", "direction"], |i, o| {
+                    o.copy_from_slice(&[last(&i.values), i.direction.last().map_or(f64::NAN, |direction| f64::from(*direction))])
+                })
+            }
+            IndicatorSpec::LinearRegression { length, source } => node(
+                LinearRegression::new(&[], length, source)?,
+                &["value", "slope", "intercept", "r_squared"],
+                |i, o| o.copy_from_slice(&[last(&i.values), last(&i.slope), last(&i.intercept), last(&i.r_squared)]),
+            ),
+            IndicatorSpec::Obv => node(Obv::new(&[]), value, |i, o| o[0] = last(&i.values)),
+            IndicatorSpec::Mfi { length } => node(Mfi::new(&[], length)?, value, |i, o| o[0] = last(&i.values)),
+            IndicatorSpec::AccumulationDistribution => {
+                node(AccumulationDistribution::new(&[]), value, |i, o| o[0] = last(&i.values))
+            }
+            IndicatorSpec::ChaikinMoneyFlow { length } => {
+                node(ChaikinMoneyFlow::new(&[], length)?, value, |i, o| o[0] = last(&i.values))
+            }
+            IndicatorSpec::Ichimoku { conversion_length, base_length, leading_b_length, displacement } => node(
+                Ichimoku::new(&[], conversion_length, base_length, leading_b_length, displacement)?,
+                &["tenkan_sen", "kijun_sen", "senkou_span_a", "senkou_span_b"],
+                |i, o| {
+                    o.copy_from_slice(&[last(&i.tenkan_sen), last(&i.kijun_sen), last(&i.senkou_span_a), last(&i.senkou_span_b)])
+                },
+            ),
+            IndicatorSpec::ChandelierExit { length, atr_length, multiplier } => {
+                node(ChandelierExit::new(&[], length, atr_length, multiplier)?, &["long", "short"], |i, o| {
+                    o.copy_from_slice(&[last(&i.long_stop), last(&i.short_stop)])
+                })
+            }
+            IndicatorSpec::ParabolicSar { start, increment, maximum } => {
+                node(ParabolicSar::new(&[], start, increment, maximum)?, &["value", "long"], |i, o| {
+                    o.copy_from_slice(&[last(&i.values), last_flag(&i.long)])
+                })
+            }
+            IndicatorSpec::SwingStop { left, right } => node(SwingStop::new(&[], left, right)?, &["long", "short"], |i, o| {
+                o.copy_from_slice(&[last(&i.long_stop), last(&i.short_stop)])
+            }),
+            IndicatorSpec::Fractals { left, right } => node(Fractals::new(&[], left, right)?, &["high", "low"], |i, o| {
+                let confirmed = |kind| i.confirmed(kind).unwrap_or(f64::NAN);
+                o.copy_from_slice(&[confirmed(SwingKind::High), confirmed(SwingKind::Low)])
+            }),
+            IndicatorSpec::Squeeze { length, bollinger_multiplier, keltner_multiplier } => node(
+                Squeeze::new(&[], length, bollinger_multiplier, keltner_multiplier)?,
+                &["squeeze_on", "released"],
+                |i, o| o.copy_from_slice(&[last_flag(&i.squeeze_on), last_flag(&i.released)]),
+            ),
+            IndicatorSpec::TakerBuySellRatio { length } => {
+                node(TakerBuySellRatio::new(&[], length)?, &["ratio", "buy_share"], |i, o| {
+                    o.copy_from_slice(&[last(&i.ratio), last(&i.buy_share)])
+                })
+            }
+            IndicatorSpec::Vwap { anchor, multiplier, source } => node(
+                Vwap::new(&[], anchor, multiplier, source)?,
+                &["value", "upper", "lower", "deviation"],
+                |i, o| o.copy_from_slice(&[last(&i.values), last(&i.upper_band), last(&i.lower_band), last(&i.deviation)]),
+            ),
+            IndicatorSpec::VolumeProfile { length, bins, value_area } => node(
+                VolumeProfile::new(&[], length, bins, value_area)?,
+                &["poc", "value_area_high", "value_area_low"],
+                |i, o| o.copy_from_slice(&[last(&i.poc), last(&i.value_area_high), last(&i.value_area_low)]),
+            ),
+            IndicatorSpec::Autocorrelation { length, lag, source } => {
+                node(Autocorrelation::new(&[], length, lag, source)?, value, |i, o| o[0] = last(&i.values))
+            }
+            IndicatorSpec::HurstExponent { length, source } => {
+                node(HurstExponent::new(&[], length, source)?, value, |i, o| o[0] = last(&i.values))
+            }
+            IndicatorSpec::CandlestickPatterns { tolerance } => {
+                node(CandlestickPatterns::new(&[], tolerance)?, &CandlePattern::NAMES, |i, o| {
+                    let flags = i.last();
+                    for (output, pattern) in o.iter_mut().zip(CandlePattern::ALL) {
+                        *output = f64::from(u8::from(flags.contains(pattern)));
+                    }
+                })
+            }
+        })
+    }
+
+    // Clé de cache : les paramètres f64 empêchent `Hash`, mais leur écriture `Debug` est exacte
+    pub fn key(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+// Cache des séries calculées sur un jeu de bougies : lors d'un balayage de paramètres de
+// stratégie, les indicateurs dont les paramètres ne changent pas ne sont calculés qu'une fois.
+#[derive(Debug)]
+pub struct IndicatorCache {
+    candles: Candles,
+    series: HashMap<String, Arc<Series>>,
+    hits: usize,
+    misses: usize,
+}
+
+impl IndicatorCache {
+    pub fn new(candles: Candles) -> Self {
+        Self {
+            candles,
+            series: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    pub fn candles(&self) -> &Candles {
+        &self.candles
+    }
+
+    // Série de l'indicateur, calculée au premier appel puis partagée
+    pub fn get(&mut self, spec: &IndicatorSpec) -> Result<Arc<Series>> {
+        let key = spec.key();
+        if let Some(series) = self.series.get(&key) {
+            self.hits += 1;
+            return Ok(Arc::clone(series));
+        }
+
+        let series = Arc::new(spec.compute(&self.candles)?);
+        self.misses += 1;
+        self.series.insert(key, Arc::clone(&series));
+        Ok(series)
+    }
+
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    pub fn len(&self) -> usize {
+        self.series.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.series.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.series.clear();
+    }
+}
@@ -0,0 +1,114 @@
+use std::any::Any;
+
+use binance::model::KlineSummary;
+
+use crate::data::{Candles, RingBuffer};
+use crate::strategy::interface::Observer;
+use crate::{Error, Result};
+
+use super::batch::Series;
+use super::kernels;
+use super::window::RollingWindow;
+use super::{unseen_klines, PriceSource};
+
+// Bandes de Bollinger : SMA ± `multiplier` écarts-types, avec %B et largeur de bande
+#[derive(Debug)]
+pub struct BollingerBands {
+    pub upper_band: RingBuffer<f64>,
+    pub lower_band: RingBuffer<f64>,
+    pub basis: RingBuffer<f64>,
+    pub percent_b: RingBuffer<f64>, // Position de la source dans les bandes (0 = bande basse, 1 = bande haute)
+    pub bandwidth: RingBuffer<f64>, // (upper - lower) / basis
+    window: RollingWindow,
+    multiplier: f64,
+    source: PriceSource,
+    last_open_time: Option<i64>,
+}
+
+impl BollingerBands {
+    pub fn new(klines: &[KlineSummary], length: usize, multiplier: f64, source: PriceSource) -> Result<Self> {
+        if length == 0 {
+            return Err(Error::invalid_parameter("length", "must be greater than 0"));
+        }
+        if !multiplier.is_finite() || multiplier <= 0.0 {
+            return Err(Error::invalid_parameter("multiplier", "must be a positive number"));
+        }
+
+        let mut indicator = Self {
+            upper_band: RingBuffer::new(),
+            lower_band: RingBuffer::new(),
+            basis: RingBuffer::new(),
+            percent_b: RingBuffer::new(),
+            bandwidth: RingBuffer::new(),
+            window: RollingWindow::new(length),
+            multiplier,
+            source,
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    pub fn compute(candles: &Candles, length: usize, multiplier: f64, source: PriceSource) -> Result<Series> {
+        Self::new(&[], length, multiplier, source)?;
+
+        let values = source.column(candles);
+        let (basis, std_dev) = kernels::rolling_mean_std(&values, length);
+        let upper = kernels::zip_with(&basis, &std_dev, |basis, std_dev| basis + multiplier * std_dev);
+        let lower = kernels::zip_with(&basis, &std_dev, |basis, std_dev| basis - multiplier * std_dev);
+
+        let width = kernels::zip_with(&upper, &lower, |upper, lower| upper - lower);
+        let percent_b = (0..values.len())
+            .map(|i| if width[i] == 0.0 { f64::NAN } else { (values[i] - lower[i]) / width[i] })
+            .collect();
+        let bandwidth = kernels::zip_with(&width, &basis, |width, basis| if basis == 0.0 { f64::NAN } else { width / basis });
+
+        Ok(Series::new(vec![
+            ("basis", basis),
+            ("upper", upper),
+            ("lower", lower),
+            ("percent_b", percent_b),
+            ("bandwidth", bandwidth),
+        ]))
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            let value = self.source.extract(kline);
+            self.window.push(value);
+
+            let (basis, deviation) = if self.window.is_full() {
+                (self.window.mean(), self.multiplier * self.window.std_dev())
+            } else {
+                (f64::NAN, f64::NAN)
+            };
+            let upper = basis + deviation;
+            let lower = basis - deviation;
+
+            self.basis.push(basis);
+            self.upper_band.push(upper);
+            self.lower_band.push(lower);
+            self.percent_b.push(if upper - lower == 0.0 { f64::NAN } else { (value - lower) / (upper - lower) });
+            self.bandwidth.push(if basis == 0.0 { f64::NAN } else { (upper - lower) / basis });
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+}
+
+impl Observer for BollingerBands {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.upper_band.set_capacity(capacity);
+        self.lower_band.set_capacity(capacity);
+        self.basis.set_capacity(capacity);
+        self.percent_b.set_capacity(capacity);
+        self.bandwidth.set_capacity(capacity);
+    }
+}
@@ -0,0 +1,349 @@
+use std::any::Any;
+
+use binance::model::KlineSummary;
+
+use crate::data::{Candles, RingBuffer};
+use crate::strategy::interface::Observer;
+use crate::{Error, Result};
+
+use super::batch::Series;
+use super::{unseen_klines, PriceSource};
+
+// Figures reconnues. Seule la forme des bougies est testée, pas la tendance qui les précède.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CandlePattern {
+    Doji,
+    Hammer,
+    ShootingStar,
+    BullishEngulfing,
+    BearishEngulfing,
+    InsideBar,
+    OutsideBar,
+    MorningStar,
+    EveningStar,
+    ThreeWhiteSoldiers,
+    ThreeBlackCrows,
+}
+
+impl CandlePattern {
+    pub const ALL: [CandlePattern; 11] = [
+        CandlePattern::Doji,
+        CandlePattern::Hammer,
+        CandlePattern::ShootingStar,
+        CandlePattern::BullishEngulfing,
+        CandlePattern::BearishEngulfing,
+        CandlePattern::InsideBar,
+        CandlePattern::OutsideBar,
+        CandlePattern::MorningStar,
+        CandlePattern::EveningStar,
+        CandlePattern::ThreeWhiteSoldiers,
+        CandlePattern::ThreeBlackCrows,
+    ];
+
+    // Nom de la sortie du calcul par lot
+    pub const fn name(self) -> &'static str {
+        match self {
+            CandlePattern::Doji => "doji",
+            CandlePattern::Hammer => "hammer",
+            CandlePattern::ShootingStar => "shooting_star",
+            CandlePattern::BullishEngulfing => "bullish_engulfing",
+            CandlePattern::BearishEngulfing => "bearish_engulfing",
+            CandlePattern::InsideBar => "inside_bar",
+            CandlePattern::OutsideBar => "outside_bar",
+            CandlePattern::MorningStar => "morning_star",
+            CandlePattern::EveningStar => "evening_star",
+            CandlePattern::ThreeWhiteSoldiers => "three_white_soldiers",
+            CandlePattern::ThreeBlackCrows => "three_black_crows",
+        }
+    }
+
+    // Noms des sorties, dans l'ordre de `ALL`
+    pub const NAMES: [&'static str; 11] = {
+        let mut names = [""; 11];
+        let mut index = 0;
+        while index < names.len() {
+            names[index] = Self::ALL[index].name();
+            index += 1;
+        }
+        names
+    };
+
+    fn bit(self) -> u16 {
+        1 << self as u16
+    }
+}
+
+// Ensemble des figures détectées sur une bougie
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PatternFlags(u16);
+
+impl PatternFlags {
+    pub fn contains(&self, pattern: CandlePattern) -> bool {
+        self.0 & pattern.bit() != 0
+    }
+
+    pub fn insert(&mut self, pattern: CandlePattern) {
+        self.0 |= pattern.bit();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = CandlePattern> + '_ {
+        CandlePattern::ALL.into_iter().filter(|pattern| self.contains(*pattern))
+    }
+}
+
+// Tolérances des figures, en proportion du range (plus haut - plus bas) de la bougie
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PatternTolerance {
+    pub doji_body: f64,  // Corps maximal d'un doji
+    pub small_body: f64, // Corps maximal d'une petite bougie (marteau, étoile du matin/soir)
+    pub long_wick: f64,  // Mèche minimale d'un marteau, en multiple du corps
+    pub short_wick: f64, // Mèche maximale du côté opposé (marteau, trois soldats/corbeaux)
+}
+
+impl Default for PatternTolerance {
+    fn default() -> Self {
+        Self {
+            doji_body: 0.1,
+            small_body: 0.3,
+            long_wick: 2.0,
+            short_wick: 0.15,
+        }
+    }
+}
+
+impl PatternTolerance {
+    fn validate(&self) -> Result<()> {
+        let ratios = [("doji_body", self.doji_body), ("small_body", self.small_body), ("short_wick", self.short_wick)];
+        for (name, ratio) in ratios {
+            if !(0.0..=1.0).contains(&ratio) {
+                return Err(Error::invalid_parameter(name, "must be between 0 and 1"));
+            }
+        }
+        if !self.long_wick.is_finite() || self.long_wick < 0.0 {
+            return Err(Error::invalid_parameter("long_wick", "must be a non-negative number"));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Candle {
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+}
+
+impl Candle {
+    fn from_kline(kline: &KlineSummary) -> Self {
+        Self {
+            open: PriceSource::Open.extract(kline),
+            high: PriceSource::High.extract(kline),
+            low: PriceSource::Low.extract(kline),
+            close: PriceSource::Close.extract(kline),
+        }
+    }
+
+    // Ouverture et clôture comprises entre le plus bas et le plus haut
+    fn is_consistent(&self) -> bool {
+        self.low <= self.open.min(self.close) && self.open.max(self.close) <= self.high
+    }
+
+    fn body(&self) -> f64 {
+        (self.close - self.open).abs()
+    }
+
+    fn range(&self) -> f64 {
+        self.high - self.low
+    }
+
+    fn upper_wick(&self) -> f64 {
+        self.high - self.open.max(self.close)
+    }
+
+    fn lower_wick(&self) -> f64 {
+        self.open.min(self.close) - self.low
+    }
+
+    fn body_middle(&self) -> f64 {
+        (self.open + self.close) / 2.0
+    }
+
+    fn is_bullish(&self) -> bool {
+        self.close > self.open
+    }
+
+    fn is_bearish(&self) -> bool {
+        self.close < self.open
+    }
+}
+
+// Détection des figures de chandeliers, un ensemble de figures par bougie.
+// Une figure sur plusieurs bougies est signalée sur sa dernière bougie.
+#[derive(Debug)]
+pub struct CandlestickPatterns {
+    pub patterns: RingBuffer<PatternFlags>,
+    tolerance: PatternTolerance,
+    previous: Vec<Candle>, // Les deux bougies précédentes
+    last_open_time: Option<i64>,
+}
+
+impl CandlestickPatterns {
+    pub fn new(klines: &[KlineSummary], tolerance: PatternTolerance) -> Result<Self> {
+        tolerance.validate()?;
+
+        let mut indicator = Self {
+            patterns: RingBuffer::new(),
+            tolerance,
+            previous: Vec::with_capacity(3),
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    // Calcul par lot : une colonne par figure (dans l'ordre de `CandlePattern::ALL`), 1 si la
+    // figure est détectée sur la bougie, 0 sinon
+    pub fn compute(candles: &Candles, tolerance: PatternTolerance) -> Result<Series> {
+        let mut indicator = Self::new(&[], tolerance)?;
+        for i in 0..candles.len() {
+            indicator.next(Candle { open: candles.open[i], high: candles.high[i], low: candles.low[i], close: candles.close[i] });
+        }
+
+        let outputs = CandlePattern::ALL
+            .iter()
+            .map(|pattern| {
+                let flags = indicator.patterns.iter().map(|flags| f64::from(u8::from(flags.contains(*pattern))));
+                (pattern.name(), flags.collect())
+            })
+            .collect();
+        Ok(Series::new(outputs))
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            self.next(Candle::from_kline(kline));
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+
+    fn next(&mut self, candle: Candle) {
+        self.patterns.push(self.detect(&candle));
+        self.previous.push(candle);
+        if self.previous.len() > 2 {
+            self.previous.remove(0);
+        }
+    }
+
+    // Figures de la dernière bougie
+    pub fn last(&self) -> PatternFlags {
+        self.patterns.last().copied().unwrap_or_default()
+    }
+
+    pub fn is_detected(&self, index: usize, pattern: CandlePattern) -> bool {
+        self.patterns.get(index).is_some_and(|flags| flags.contains(pattern))
+    }
+
+    fn detect(&self, current: &Candle) -> PatternFlags {
+        let tolerance = &self.tolerance;
+        let mut flags = PatternFlags::default();
+        // Une bougie incohérente (données corrompues) ne forme aucune figure
+        if !current.is_consistent() {
+            return flags;
+        }
+        let range = current.range();
+
+        if range > 0.0 {
+            let small_body = current.body() <= tolerance.small_body * range;
+            let short_wick = tolerance.short_wick * range;
+
+            if current.body() <= tolerance.doji_body * range {
+                flags.insert(CandlePattern::Doji);
+            }
+            if small_body && current.lower_wick() >= tolerance.long_wick * current.body() && current.upper_wick() <= short_wick {
+                flags.insert(CandlePattern::Hammer);
+            }
+            if small_body && current.upper_wick() >= tolerance.long_wick * current.body() && current.lower_wick() <= short_wick {
+                flags.insert(CandlePattern::ShootingStar);
+            }
+        }
+
+        if let [.., previous] = self.previous.as_slice() {
+            if previous.is_bearish()
+                && current.is_bullish()
+                && current.open <= previous.close
+                && current.close >= previous.open
+                && current.body() > previous.body()
+            {
+                flags.insert(CandlePattern::BullishEngulfing);
+            }
+            if previous.is_bullish()
+                && current.is_bearish()
+                && current.open >= previous.close
+                && current.close <= previous.open
+                && current.body() > previous.body()
+            {
+                flags.insert(CandlePattern::BearishEngulfing);
+            }
+            if current.high < previous.high && current.low > previous.low {
+                flags.insert(CandlePattern::InsideBar);
+            }
+            if current.high > previous.high && current.low < previous.low {
+                flags.insert(CandlePattern::OutsideBar);
+            }
+        }
+
+        if let [first, second] = self.previous.as_slice() {
+            let long_body = |candle: &Candle| candle.body() > tolerance.small_body * candle.range();
+            let small_body = second.body() <= tolerance.small_body * second.range();
+
+            if first.is_bearish() && long_body(first) && small_body && current.is_bullish() && current.close > first.body_middle() {
+                flags.insert(CandlePattern::MorningStar);
+            }
+            if first.is_bullish() && long_body(first) && small_body && current.is_bearish() && current.close < first.body_middle() {
+                flags.insert(CandlePattern::EveningStar);
+            }
+
+            let candles = [first, second, current];
+            let soldiers = candles.iter().all(|candle| {
+                candle.is_bullish() && long_body(candle) && candle.upper_wick() <= tolerance.short_wick * candle.range()
+            }) && candles.windows(2).all(|pair| {
+                let (previous, candle) = (pair[0], pair[1]);
+                candle.close > previous.close && (previous.open..=previous.close).contains(&candle.open)
+            });
+            if soldiers {
+                flags.insert(CandlePattern::ThreeWhiteSoldiers);
+            }
+
+            let crows = candles.iter().all(|candle| {
+                candle.is_bearish() && long_body(candle) && candle.lower_wick() <= tolerance.short_wick * candle.range()
+            }) && candles.windows(2).all(|pair| {
+                let (previous, candle) = (pair[0], pair[1]);
+                candle.close < previous.close && (previous.close..=previous.open).contains(&candle.open)
+            });
+            if crows {
+                flags.insert(CandlePattern::ThreeBlackCrows);
+            }
+        }
+
+        flags
+    }
+}
+
+impl Observer for CandlestickPatterns {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.patterns.set_capacity(capacity);
+    }
+}
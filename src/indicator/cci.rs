@@ -0,0 +1,88 @@
+use std::any::Any;
+
+use binance::model::KlineSummary;
+
+use crate::data::{Candles, RingBuffer};
+use crate::strategy::interface::Observer;
+use crate::{Error, Result};
+
+use super::batch::Series;
+use super::window::RollingWindow;
+use super::{unseen_klines, PriceSource};
+
+// Commodity Channel Index : écart à la moyenne rapporté à l'écart moyen absolu
+#[derive(Debug)]
+pub struct Cci {
+    pub values: RingBuffer<f64>,
+    window: RollingWindow,
+    source: PriceSource,
+    last_open_time: Option<i64>,
+}
+
+impl Cci {
+    pub fn new(klines: &[KlineSummary], length: usize, source: PriceSource) -> Result<Self> {
+        if length == 0 {
+            return Err(Error::invalid_parameter("length", "must be greater than 0"));
+        }
+
+        let mut indicator = Self {
+            values: RingBuffer::new(),
+            window: RollingWindow::new(length),
+            source,
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    pub fn compute(candles: &Candles, length: usize, source: PriceSource) -> Result<Series> {
+        Self::new(&[], length, source)?;
+
+        let values = source.column(candles);
+        let mut cci = vec![f64::NAN; values.len()];
+        for end in length..=values.len() {
+            let window = &values[end - length..end];
+            let mean = window.iter().sum::<f64>() / length as f64;
+            let deviation = window.iter().map(|v| (v - mean).abs()).sum::<f64>() / length as f64;
+            cci[end - 1] = if deviation == 0.0 { 0.0 } else { (values[end - 1] - mean) / (0.015 * deviation) };
+        }
+        Ok(Series::single(cci))
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            let value = self.source.extract(kline);
+            self.window.push(value);
+            self.values.push(self.calculate_cci(value));
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+
+    fn calculate_cci(&self, value: f64) -> f64 {
+        if !self.window.is_full() {
+            return f64::NAN;
+        }
+
+        let mean = self.window.mean();
+        let deviation = self.window.iter().map(|v| (v - mean).abs()).sum::<f64>() / self.window.len() as f64;
+        if deviation == 0.0 {
+            return 0.0;
+        }
+
+        (value - mean) / (0.015 * deviation)
+    }
+}
+
+impl Observer for Cci {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.values.set_capacity(capacity);
+    }
+}
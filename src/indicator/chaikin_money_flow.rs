@@ -0,0 +1,146 @@
+use std::any::Any;
+
+use binance::model::KlineSummary;
+
+use crate::data::{Candles, RingBuffer};
+use crate::strategy::interface::Observer;
+use crate::{Error, Result};
+
+use super::batch::Series;
+use super::kernels;
+use super::window::RollingWindow;
+use super::{unseen_klines, PriceSource};
+
+// Volume pondéré par la position de la clôture dans le range de la bougie (entre -volume et +volume)
+fn money_flow_volume(kline: &KlineSummary) -> f64 {
+    let high = PriceSource::High.extract(kline);
+    let low = PriceSource::Low.extract(kline);
+    let close = PriceSource::Close.extract(kline);
+    let volume = PriceSource::Volume.extract(kline);
+    flow_volume(high, low, close, volume)
+}
+
+fn flow_volume(high: f64, low: f64, close: f64, volume: f64) -> f64 {
+    if high - low == 0.0 {
+        return 0.0;
+    }
+    ((close - low) - (high - close)) / (high - low) * volume
+}
+
+fn money_flow_volumes(candles: &Candles) -> Vec<f64> {
+    (0..candles.len())
+        .map(|i| flow_volume(candles.high[i], candles.low[i], candles.close[i], candles.volume[i]))
+        .collect()
+}
+
+// Ligne Accumulation/Distribution : cumul du volume pondéré
+#[derive(Debug, Default)]
+pub struct AccumulationDistribution {
+    pub values: RingBuffer<f64>,
+    last_open_time: Option<i64>,
+}
+
+impl AccumulationDistribution {
+    pub fn new(klines: &[KlineSummary]) -> Self {
+        let mut indicator = Self::default();
+        indicator.add(klines);
+        indicator
+    }
+
+    pub fn compute(candles: &Candles) -> Series {
+        Series::single(kernels::cumulative_sum(&money_flow_volumes(candles)))
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            let previous = self.values.last().copied().unwrap_or(0.0);
+            self.values.push(previous + money_flow_volume(kline));
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+}
+
+impl Observer for AccumulationDistribution {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.values.set_capacity(capacity);
+    }
+}
+
+// Chaikin Money Flow : volume pondéré rapporté au volume total sur `length` bougies
+#[derive(Debug)]
+pub struct ChaikinMoneyFlow {
+    pub values: RingBuffer<f64>,
+    flows: RollingWindow,
+    volumes: RollingWindow,
+    last_open_time: Option<i64>,
+}
+
+impl ChaikinMoneyFlow {
+    pub fn new(klines: &[KlineSummary], length: usize) -> Result<Self> {
+        if length == 0 {
+            return Err(Error::invalid_parameter("length", "must be greater than 0"));
+        }
+
+        let mut indicator = Self {
+            values: RingBuffer::new(),
+            flows: RollingWindow::new(length),
+            volumes: RollingWindow::new(length),
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    pub fn compute(candles: &Candles, length: usize) -> Result<Series> {
+        Self::new(&[], length)?;
+
+        let flows = kernels::rolling_sum(&money_flow_volumes(candles), length);
+        let volumes = kernels::rolling_sum(&candles.volume, length);
+        Ok(Series::single(kernels::zip_with(&flows, &volumes, |flow, volume| {
+            if volume == 0.0 {
+                f64::NAN
+            } else {
+                flow / volume
+            }
+        })))
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            self.flows.push(money_flow_volume(kline));
+            self.volumes.push(PriceSource::Volume.extract(kline));
+
+            let volume = self.volumes.sum();
+            let value = if self.volumes.is_full() && volume != 0.0 {
+                self.flows.sum() / volume
+            } else {
+                f64::NAN
+            };
+
+            self.values.push(value);
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+}
+
+impl Observer for ChaikinMoneyFlow {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.values.set_capacity(capacity);
+    }
+}
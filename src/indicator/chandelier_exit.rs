@@ -0,0 +1,107 @@
+use std::any::Any;
+
+use binance::model::KlineSummary;
+
+use crate::data::{Candles, RingBuffer};
+use crate::strategy::interface::Observer;
+use crate::{Error, Result};
+
+use super::atr::AtrCalculator;
+use super::batch::Series;
+use super::kernels;
+use super::stop::{last_stop, Side, StopIndicator};
+use super::window::RollingWindow;
+use super::{unseen_klines, PriceSource};
+
+// Chandelier Exit : plus haut de la période - ATR × multiplicateur pour un long,
+// plus bas de la période + ATR × multiplicateur pour un short
+#[derive(Debug)]
+pub struct ChandelierExit {
+    pub long_stop: RingBuffer<f64>,
+    pub short_stop: RingBuffer<f64>,
+    highs: RollingWindow,
+    lows: RollingWindow,
+    atr: AtrCalculator,
+    multiplier: f64,
+    last_open_time: Option<i64>,
+}
+
+impl ChandelierExit {
+    pub fn new(klines: &[KlineSummary], length: usize, atr_length: usize, multiplier: f64) -> Result<Self> {
+        if length == 0 {
+            return Err(Error::invalid_parameter("length", "must be greater than 0"));
+        }
+        if !multiplier.is_finite() || multiplier <= 0.0 {
+            return Err(Error::invalid_parameter("multiplier", "must be a positive number"));
+        }
+
+        let mut indicator = Self {
+            long_stop: RingBuffer::new(),
+            short_stop: RingBuffer::new(),
+            highs: RollingWindow::new(length),
+            lows: RollingWindow::new(length),
+            atr: AtrCalculator::new(atr_length)?,
+            multiplier,
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    pub fn compute(candles: &Candles, length: usize, atr_length: usize, multiplier: f64) -> Result<Series> {
+        Self::new(&[], length, atr_length, multiplier)?;
+
+        let range: Vec<f64> = kernels::atr(&candles.high, &candles.low, &candles.close, atr_length)
+            .into_iter()
+            .map(|atr| multiplier * atr)
+            .collect();
+        let long = kernels::zip_with(&kernels::rolling_max(&candles.high, length), &range, |high, range| high - range);
+        let short = kernels::zip_with(&kernels::rolling_min(&candles.low, length), &range, |low, range| low + range);
+        Ok(Series::new(vec![("long", long), ("short", short)]))
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            self.highs.push(PriceSource::High.extract(kline));
+            self.lows.push(PriceSource::Low.extract(kline));
+            let range = self.multiplier * self.atr.next(kline);
+
+            if self.highs.is_full() {
+                self.long_stop.push(self.highs.max() - range);
+                self.short_stop.push(self.lows.min() + range);
+            } else {
+                self.long_stop.push(f64::NAN);
+                self.short_stop.push(f64::NAN);
+            }
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+}
+
+impl StopIndicator for ChandelierExit {
+    fn stop(&self, side: Side) -> Option<f64> {
+        match side {
+            Side::Long => last_stop(&self.long_stop),
+            Side::Short => last_stop(&self.short_stop),
+        }
+    }
+}
+
+impl Observer for ChandelierExit {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.long_stop.set_capacity(capacity);
+        self.short_stop.set_capacity(capacity);
+    }
+
+    fn as_stop_indicator(&self) -> Option<&dyn StopIndicator> {
+        Some(self)
+    }
+}
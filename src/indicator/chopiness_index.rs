@@ -1,66 +1,94 @@
 use binance::model::KlineSummary;
+use crate::data::{Candles, RingBuffer};
 use crate::strategy::interface::Observer;
+use crate::{Error, Result};
+
+use super::atr::true_range;
+use super::batch::Series;
+use super::kernels;
+use super::unseen_klines;
 use std::any::Any;
 
+// Une valeur par bougie, NaN tant que `length` bougies ne sont pas disponibles
 #[derive(Debug)]
 pub struct ChoppinessIndex {
-    pub values: Vec<f64>,  // Vecteur pour stocker plusieurs valeurs de Choppiness Index
+    pub values: RingBuffer<f64>,  // Vecteur pour stocker plusieurs valeurs de Choppiness Index
     length: usize,         // Longueur pour le calcul
+    count: usize,          // Nombre de bougies traitées
+    last_open_time: Option<i64>,
 }
 
 impl ChoppinessIndex {
-    pub fn new(klines: &[KlineSummary], length: usize) -> Self {
-        let mut values = Vec::new();
-        
-        // Ajouter des zéros pour les premières valeurs jusqu'à ce que nous ayons suffisamment de données
-        for _ in 0..(length - 1).min(klines.len()) {
-            values.push(0.0);
+    pub fn new(klines: &[KlineSummary], length: usize) -> Result<Self> {
+        // log10(length) est au dénominateur : il faut au moins deux bougies
+        if length < 2 {
+            return Err(Error::invalid_parameter("length", "must be at least 2"));
         }
 
-        // Commencer le calcul seulement après avoir assez de données
-        for i in (length - 1)..klines.len() {
-            let atr_sum = Self::calculate_atr_sum(&klines[(i + 1 - length)..=i], length);
-            let highest = Self::get_highest(&klines[(i + 1 - length)..=i], length);
-            let lowest = Self::get_lowest(&klines[(i + 1 - length)..=i], length);
-            let value = Self::calculate_choppiness_index(atr_sum, highest, lowest, length);
-            values.push(value);
-        }
+        let mut indicator = Self {
+            values: RingBuffer::new(),
+            length,
+            count: 0,
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
 
-        Self { values, length }
+    // Calcul par lot sur des bougies en colonnes
+    pub fn compute(candles: &Candles, length: usize) -> Result<Series> {
+        Self::new(&[], length)?;
+
+        let atr_sums = kernels::rolling_sum(&kernels::true_range(&candles.high, &candles.low, &candles.close), length);
+        let highest = kernels::rolling_max(&candles.high, length);
+        let lowest = kernels::rolling_min(&candles.low, length);
+        let values = (0..candles.len())
+            .map(|i| {
+                if atr_sums[i].is_nan() {
+                    f64::NAN
+                } else {
+                    Self::calculate_choppiness_index(atr_sums[i], highest[i], lowest[i], length)
+                }
+            })
+            .collect();
+        Ok(Series::single(values))
     }
 
-    // Ajouter une nouvelle valeur basée sur les données actuelles de klines
+    // Ajouter une valeur pour chaque bougie pas encore traitée
     pub fn add(&mut self, klines: &[KlineSummary]) {
-        if klines.len() < self.length {
-            self.values.push(0.0); // Ajouter 0 si les données sont insuffisantes
-            return;
+        let first = klines.len() - unseen_klines(klines, self.last_open_time).len();
+
+        for end in (first + 1)..=klines.len() {
+            self.count += 1;
+            self.last_open_time = Some(klines[end - 1].open_time);
+
+            // Valeur indéfinie si les données sont insuffisantes
+            if self.count < self.length || end < self.length {
+                self.values.push(f64::NAN);
+                continue;
+            }
+
+            let window = &klines[(end - self.length)..end];
+            // Comme `ta.atr(1)` sur TradingView : le True Range de la première bougie de la fenêtre
+            // utilise la clôture précédente (high - low s'il n'y en a pas)
+            let previous = end.checked_sub(self.length + 1).map(|index| &klines[index]);
+            let atr_sum = Self::calculate_atr_sum(window, previous);
+            let highest = Self::get_highest(window, self.length);
+            let lowest = Self::get_lowest(window, self.length);
+            let value = Self::calculate_choppiness_index(atr_sum, highest, lowest, self.length);
+
+            self.values.push(value); // Ajouter la nouvelle valeur calculée au vecteur
         }
-
-        let atr_sum = Self::calculate_atr_sum(&klines[(klines.len() - self.length)..], self.length);
-        let highest = Self::get_highest(&klines[(klines.len() - self.length)..], self.length);
-        let lowest = Self::get_lowest(&klines[(klines.len() - self.length)..], self.length);
-        let value = Self::calculate_choppiness_index(atr_sum, highest, lowest, self.length);
-
-        self.values.push(value); // Ajouter la nouvelle valeur calculée au vecteur
     }
 
-    fn calculate_true_range(current: &KlineSummary, previous_close: f64) -> f64 {
-        let high = current.high.parse::<f64>().unwrap_or(0.0);
-        let low = current.low.parse::<f64>().unwrap_or(0.0);
-        (high - low)
-            .max((high - previous_close).abs())
-            .max((low - previous_close).abs())
-    }
-
-    fn calculate_atr_sum(klines: &[KlineSummary], _length: usize) -> f64 {
-        let mut true_ranges = Vec::new();
-        for i in 1..klines.len() {
-            let current = &klines[i];
-            let previous_close = klines[i - 1].close.parse::<f64>().unwrap_or(0.0);
-            let tr = Self::calculate_true_range(current, previous_close);
-            true_ranges.push(tr);
+    fn calculate_atr_sum(klines: &[KlineSummary], previous: Option<&KlineSummary>) -> f64 {
+        let mut previous_close = previous.map(|kline| kline.close.parse::<f64>().unwrap_or(0.0));
+        let mut atr_sum = 0.0;
+        for kline in klines {
+            atr_sum += true_range(kline, previous_close);
+            previous_close = Some(kline.close.parse::<f64>().unwrap_or(0.0));
         }
-        true_ranges.iter().sum()
+        atr_sum
     }
 
     fn get_highest(klines: &[KlineSummary], length: usize) -> f64 {
@@ -98,5 +126,14 @@ impl Observer for ChoppinessIndex {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    // La bougie qui précède la fenêtre fournit la clôture du premier True Range
+    fn lookback(&self) -> usize {
+        self.length + 1
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.values.set_capacity(capacity);
+    }
 }
 
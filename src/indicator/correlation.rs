@@ -0,0 +1,223 @@
+use std::any::Any;
+use std::collections::BTreeMap;
+
+use binance::model::KlineSummary;
+
+use crate::data::{Candles, RingBuffer};
+use crate::strategy::interface::Observer;
+use crate::{Error, Result};
+
+use super::batch::Series;
+use super::kernels;
+use super::window::RollingWindow;
+use super::{unseen_klines, PriceSource};
+
+// Corrélation de Pearson et covariance / variance de `y` entre deux fenêtres de même taille
+fn pearson(x: &RollingWindow, y: &RollingWindow) -> (f64, f64) {
+    let (mean_x, mean_y) = (x.mean(), y.mean());
+    let (sxx, syy, sxy) = x.iter().zip(y.iter()).fold((0.0, 0.0, 0.0), |(sxx, syy, sxy), (x, y)| {
+        let (dx, dy) = (x - mean_x, y - mean_y);
+        (sxx + dx * dx, syy + dy * dy, sxy + dx * dy)
+    });
+
+    let correlation = if sxx > 0.0 && syy > 0.0 { sxy / (sxx * syy).sqrt() } else { f64::NAN };
+    let beta = if syy > 0.0 { sxy / syy } else { f64::NAN };
+    (correlation, beta)
+}
+
+fn simple_return(value: f64, previous: f64) -> f64 {
+    if previous == 0.0 {
+        return f64::NAN;
+    }
+    value / previous - 1.0
+}
+
+// Autocorrélation glissante des rendements : corrélation entre le rendement et celui d'il y a `lag`
+// bougies, sur les `length` derniers couples
+#[derive(Debug)]
+pub struct Autocorrelation {
+    pub values: RingBuffer<f64>,
+    returns: RollingWindow,
+    current: RollingWindow,
+    lagged: RollingWindow,
+    lag: usize,
+    previous: Option<f64>,
+    source: PriceSource,
+    last_open_time: Option<i64>,
+}
+
+impl Autocorrelation {
+    pub fn new(klines: &[KlineSummary], length: usize, lag: usize, source: PriceSource) -> Result<Self> {
+        if length < 2 {
+            return Err(Error::invalid_parameter("length", "must be at least 2"));
+        }
+        if lag == 0 {
+            return Err(Error::invalid_parameter("lag", "must be greater than 0"));
+        }
+
+        let mut indicator = Self {
+            values: RingBuffer::new(),
+            returns: RollingWindow::new(lag + 1),
+            current: RollingWindow::new(length),
+            lagged: RollingWindow::new(length),
+            lag,
+            previous: None,
+            source,
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    // Calcul par lot : corrélation glissante entre la colonne des rendements et la même colonne
+    // décalée de `lag`, alignée sur la bougie du rendement le plus récent
+    pub fn compute(candles: &Candles, length: usize, lag: usize, source: PriceSource) -> Result<Series> {
+        Self::new(&[], length, lag, source)?;
+
+        let values = source.column(candles);
+        let returns: Vec<f64> = (1..values.len()).map(|i| simple_return(values[i], values[i - 1])).collect();
+        let mut out = vec![f64::NAN; values.len()];
+        if returns.len() > lag {
+            let (correlation, _) = kernels::rolling_correlation(&returns[lag..], &returns[..returns.len() - lag], length);
+            out[lag + 1..].copy_from_slice(&correlation);
+        }
+        Ok(Series::single(out))
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            let value = self.source.extract(kline);
+            if let Some(previous) = self.previous {
+                self.returns.push(simple_return(value, previous));
+            }
+            self.previous = Some(value);
+
+            if let (true, Some(current), Some(lagged)) = (self.returns.is_full(), self.returns.last(), self.returns.first()) {
+                self.current.push(current);
+                self.lagged.push(lagged);
+            }
+
+            let value = if self.current.is_full() { pearson(&self.current, &self.lagged).0 } else { f64::NAN };
+            self.values.push(value);
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+
+    pub fn lag(&self) -> usize {
+        self.lag
+    }
+}
+
+impl Observer for Autocorrelation {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.values.set_capacity(capacity);
+    }
+}
+
+// Corrélation et bêta glissants des rendements du symbole observé par rapport à un symbole de référence
+// (par exemple ETHBTC contre une autre paire BTC). Les bougies sont appariées par heure d'ouverture :
+// les bougies de référence doivent être ajoutées avec `add_reference` avant les bougies observées
+// (dans un `KlineManager`, via `get_indicator_mut`). Une bougie sans référence vide les fenêtres :
+// les valeurs restent NaN jusqu'à `length` nouveaux couples de rendements.
+#[derive(Debug)]
+pub struct RollingCorrelation {
+    pub correlation: RingBuffer<f64>,
+    pub beta: RingBuffer<f64>, // Covariance / variance des rendements de référence
+    reference: BTreeMap<i64, f64>,
+    returns: RollingWindow,
+    reference_returns: RollingWindow,
+    previous: Option<(i64, f64, f64)>, // (heure d'ouverture, valeur, valeur de référence)
+    source: PriceSource,
+    last_open_time: Option<i64>,
+}
+
+impl RollingCorrelation {
+    pub fn new(klines: &[KlineSummary], reference: &[KlineSummary], length: usize, source: PriceSource) -> Result<Self> {
+        if length < 2 {
+            return Err(Error::invalid_parameter("length", "must be at least 2"));
+        }
+
+        let mut indicator = Self {
+            correlation: RingBuffer::new(),
+            beta: RingBuffer::new(),
+            reference: BTreeMap::new(),
+            returns: RollingWindow::new(length),
+            reference_returns: RollingWindow::new(length),
+            previous: None,
+            source,
+            last_open_time: None,
+        };
+        indicator.add_reference(reference);
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    // Ajouter des bougies du symbole de référence
+    pub fn add_reference(&mut self, klines: &[KlineSummary]) {
+        let first_unused = self.last_open_time.map_or(i64::MIN, |open_time| open_time + 1);
+        for kline in klines.iter().filter(|kline| kline.open_time >= first_unused) {
+            self.reference.insert(kline.open_time, self.source.extract(kline));
+        }
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            let value = self.source.extract(kline);
+
+            // Une bougie sans référence à la même heure interrompt l'appariement des rendements
+            match self.reference.remove(&kline.open_time) {
+                Some(reference) => {
+                    if let Some((_, previous, previous_reference)) = self.previous {
+                        self.returns.push(simple_return(value, previous));
+                        self.reference_returns.push(simple_return(reference, previous_reference));
+                    }
+                    self.previous = Some((kline.open_time, value, reference));
+                }
+                None => {
+                    self.previous = None;
+                    self.returns.clear();
+                    self.reference_returns.clear();
+                }
+            }
+            // Les références plus anciennes ne seront plus appariées
+            self.reference = self.reference.split_off(&kline.open_time);
+
+            let (correlation, beta) = if self.returns.is_full() {
+                pearson(&self.returns, &self.reference_returns)
+            } else {
+                (f64::NAN, f64::NAN)
+            };
+            self.correlation.push(correlation);
+            self.beta.push(beta);
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+}
+
+impl Observer for RollingCorrelation {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    // Les bougies de référence sont ajoutées pendant que l'indicateur est dans le gestionnaire
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+        Some(self)
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.correlation.set_capacity(capacity);
+        self.beta.set_capacity(capacity);
+    }
+}
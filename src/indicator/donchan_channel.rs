@@ -2,57 +2,76 @@ use std::any::Any;
 
 use binance::model::KlineSummary;
 
+use crate::data::{Candles, RingBuffer};
 use crate::strategy::interface::Observer;
+use crate::{Error, Result};
 
+use super::batch::Series;
+use super::kernels;
+use super::unseen_klines;
+
+// Une valeur par bougie, NaN tant que `length + offset` bougies ne sont pas disponibles
 #[derive(Debug)]
 pub struct DonchianChannel {
-    pub upper_band: Vec<f64>,  // Vecteur des bandes supérieures
-    pub lower_band: Vec<f64>,  // Vecteur des bandes inférieures
-    pub basis: Vec<f64>,       // Vecteur des lignes de base
+    pub upper_band: RingBuffer<f64>,  // Vecteur des bandes supérieures
+    pub lower_band: RingBuffer<f64>,  // Vecteur des bandes inférieures
+    pub basis: RingBuffer<f64>,       // Vecteur des lignes de base
     length: usize,             // Longueur de la période pour le calcul
     offset: usize,             // Décalage pour le calcul
+    count: usize,              // Nombre de bougies traitées
+    last_open_time: Option<i64>,
 }
 
 impl DonchianChannel {
-    pub fn new(klines: &[KlineSummary], length: usize, offset: usize) -> Self {
-        let mut upper_band = Vec::new();
-        let mut lower_band = Vec::new();
-        let mut basis = Vec::new();
-
-        // Calculer les valeurs pour chaque période en tenant compte du décalage
-        for i in offset..=klines.len() {
-            if i < length + offset {
-                continue; // Ignorer si on n'a pas encore assez de données
-            }
-
-            let current_upper = Self::get_highest_for_dc(&klines[0..i], length, offset);
-            let current_lower = Self::get_lowest_for_dc(&klines[0..i], length, offset);
-            let current_basis = Self::calculate_basis(current_upper, current_lower);
-
-            upper_band.push(current_upper);
-            lower_band.push(current_lower);
-            basis.push(current_basis);
+    pub fn new(klines: &[KlineSummary], length: usize, offset: usize) -> Result<Self> {
+        if length == 0 {
+            return Err(Error::invalid_parameter("length", "must be greater than 0"));
         }
 
-        Self {
-            upper_band,
-            lower_band,
-            basis,
+        let mut indicator = Self {
+            upper_band: RingBuffer::new(),
+            lower_band: RingBuffer::new(),
+            basis: RingBuffer::new(),
             length,
             offset,
-        }
+            count: 0,
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    // Calcul par lot : plus haut / plus bas glissants décalés de `offset` bougies
+    pub fn compute(candles: &Candles, length: usize, offset: usize) -> Result<Series> {
+        Self::new(&[], length, offset)?;
+
+        let upper = kernels::shift(&kernels::rolling_max(&candles.high, length), offset);
+        let lower = kernels::shift(&kernels::rolling_min(&candles.low, length), offset);
+        let basis = kernels::zip_with(&upper, &lower, Self::calculate_basis);
+        Ok(Series::new(vec![("upper", upper), ("lower", lower), ("basis", basis)]))
     }
 
+    // Ajouter une valeur pour chaque bougie pas encore traitée (NaN tant que `length + offset` bougies
+    // ne sont pas disponibles)
     pub fn add(&mut self, all_klines: &[KlineSummary]) {
-        // Recalculer avec toutes les klines disponibles
-        if all_klines.len() >= self.length + self.offset {
-            let current_upper = Self::get_highest_for_dc(all_klines, self.length, self.offset);
-            let current_lower = Self::get_lowest_for_dc(all_klines, self.length, self.offset);
-            let current_basis = Self::calculate_basis(current_upper, current_lower);
+        let first = all_klines.len() - unseen_klines(all_klines, self.last_open_time).len();
+
+        for end in (first + 1)..=all_klines.len() {
+            self.count += 1;
+            self.last_open_time = Some(all_klines[end - 1].open_time);
+
+            let (current_upper, current_lower) = if self.count >= self.length + self.offset && end >= self.length + self.offset {
+                (
+                    Self::get_highest_for_dc(&all_klines[..end], self.length, self.offset),
+                    Self::get_lowest_for_dc(&all_klines[..end], self.length, self.offset),
+                )
+            } else {
+                (f64::NAN, f64::NAN) // Ignorer si on n'a pas encore assez de données
+            };
 
             self.upper_band.push(current_upper);
             self.lower_band.push(current_lower);
-            self.basis.push(current_basis);
+            self.basis.push(Self::calculate_basis(current_upper, current_lower));
         }
     }
 
@@ -96,5 +115,15 @@ impl Observer for DonchianChannel {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn lookback(&self) -> usize {
+        self.length + self.offset
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.upper_band.set_capacity(capacity);
+        self.lower_band.set_capacity(capacity);
+        self.basis.set_capacity(capacity);
+    }
 }
 
@@ -0,0 +1,123 @@
+use std::any::Any;
+
+use binance::model::KlineSummary;
+
+use crate::data::{Candles, RingBuffer};
+use crate::strategy::interface::Observer;
+use crate::{Error, Result};
+
+use super::batch::Series;
+use super::linear_regression::least_squares;
+use super::window::RollingWindow;
+use super::{unseen_klines, PriceSource};
+
+// Taille minimale d'un bloc pour l'analyse R/S
+const MIN_CHUNK: usize = 8;
+
+// Exposant de Hurst par analyse R/S (rescaled range) sur les `length` derniers rendements logarithmiques.
+// H > 0.5 : série persistante (tendance), H < 0.5 : retour à la moyenne, H ≈ 0.5 : marche aléatoire.
+#[derive(Debug)]
+pub struct HurstExponent {
+    pub values: RingBuffer<f64>,
+    returns: RollingWindow,
+    previous: Option<f64>,
+    source: PriceSource,
+    last_open_time: Option<i64>,
+}
+
+impl HurstExponent {
+    pub fn new(klines: &[KlineSummary], length: usize, source: PriceSource) -> Result<Self> {
+        if length < 2 * MIN_CHUNK {
+            return Err(Error::invalid_parameter("length", format!("must be at least {}", 2 * MIN_CHUNK)));
+        }
+
+        let mut indicator = Self {
+            values: RingBuffer::new(),
+            returns: RollingWindow::new(length),
+            previous: None,
+            source,
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    // Calcul par lot : exposant de chaque fenêtre de `length` rendements de la colonne
+    pub fn compute(candles: &Candles, length: usize, source: PriceSource) -> Result<Series> {
+        Self::new(&[], length, source)?;
+
+        let values = source.column(candles);
+        let returns: Vec<f64> = (1..values.len()).map(|i| (values[i] / values[i - 1]).ln()).collect();
+        let mut out = vec![f64::NAN; values.len()];
+        for end in length..=returns.len() {
+            out[end] = exponent(&returns[end - length..end]);
+        }
+        Ok(Series::single(out))
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            let value = self.source.extract(kline);
+            if let Some(previous) = self.previous {
+                self.returns.push((value / previous).ln());
+            }
+            self.previous = Some(value);
+
+            let value = if self.returns.is_full() {
+                exponent(&self.returns.iter().copied().collect::<Vec<f64>>())
+            } else {
+                f64::NAN
+            };
+            self.values.push(value);
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+}
+
+// Pente de log(R/S) en fonction de log(n) pour des blocs de taille n = length, length/2, ...
+fn exponent(returns: &[f64]) -> f64 {
+    let mut points = Vec::new();
+
+    let mut size = returns.len();
+    while size >= MIN_CHUNK {
+        let ratios: Vec<f64> = returns.chunks_exact(size).filter_map(rescaled_range).collect();
+        if !ratios.is_empty() {
+            let mean = ratios.iter().sum::<f64>() / ratios.len() as f64;
+            points.push(((size as f64).ln(), mean.ln()));
+        }
+        size /= 2;
+    }
+
+    least_squares(&points).0
+}
+
+// R/S d'un bloc : étendue des écarts cumulés à la moyenne divisée par l'écart-type
+fn rescaled_range(chunk: &[f64]) -> Option<f64> {
+    let mean = chunk.iter().sum::<f64>() / chunk.len() as f64;
+    let std_dev = (chunk.iter().map(|value| (value - mean).powi(2)).sum::<f64>() / chunk.len() as f64).sqrt();
+    if std_dev == 0.0 {
+        return None;
+    }
+
+    let (mut cumulative, mut max, mut min) = (0.0_f64, f64::MIN, f64::MAX);
+    for value in chunk {
+        cumulative += value - mean;
+        max = max.max(cumulative);
+        min = min.min(cumulative);
+    }
+    Some((max - min) / std_dev)
+}
+
+impl Observer for HurstExponent {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.values.set_capacity(capacity);
+    }
+}
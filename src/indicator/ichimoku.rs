@@ -0,0 +1,201 @@
+use std::any::Any;
+
+use binance::model::KlineSummary;
+
+use crate::data::{Candles, RingBuffer};
+use crate::strategy::interface::Observer;
+use crate::{Error, Result};
+
+use super::batch::Series;
+use super::kernels;
+use super::window::RollingWindow;
+use super::{unseen_klines, PriceSource};
+
+// Milieu du plus haut et du plus bas sur une fenêtre, comme la base d'un canal de Donchian
+#[derive(Debug)]
+struct Midpoint {
+    highs: RollingWindow,
+    lows: RollingWindow,
+}
+
+impl Midpoint {
+    fn new(length: usize) -> Self {
+        Self {
+            highs: RollingWindow::new(length),
+            lows: RollingWindow::new(length),
+        }
+    }
+
+    fn next(&mut self, high: f64, low: f64) -> f64 {
+        self.highs.push(high);
+        self.lows.push(low);
+        if !self.highs.is_full() {
+            return f64::NAN;
+        }
+        (self.highs.max() + self.lows.min()) / 2.0
+    }
+}
+
+// Ichimoku Kinko Hyo. Toutes les séries publiques sont indexées par la bougie à laquelle la
+// valeur est affichée ET connue : `senkou_span_a[i]` est le nuage sous la bougie i, calculé
+// `displacement - 1` bougies plus tôt. Les valeurs projetées dans le futur et la Chikou
+// (affichée dans le passé) ne sont accessibles que par des méthodes qui excluent tout lookahead.
+#[derive(Debug)]
+pub struct Ichimoku {
+    pub tenkan_sen: RingBuffer<f64>,
+    pub kijun_sen: RingBuffer<f64>,
+    pub senkou_span_a: RingBuffer<f64>,
+    pub senkou_span_b: RingBuffer<f64>,
+    conversion: Midpoint,
+    base: Midpoint,
+    leading_b: Midpoint,
+    leading_a_values: RollingWindow, // Senkou A non décalée des `displacement` dernières bougies
+    leading_b_values: RollingWindow,
+    closes: RingBuffer<f64>,
+    offset: usize, // displacement - 1, comme le décalage affiché par TradingView
+    last_open_time: Option<i64>,
+}
+
+impl Ichimoku {
+    pub fn new(
+        klines: &[KlineSummary],
+        conversion_length: usize,
+        base_length: usize,
+        leading_b_length: usize,
+        displacement: usize,
+    ) -> Result<Self> {
+        for (name, length) in [
+            ("conversion_length", conversion_length),
+            ("base_length", base_length),
+            ("leading_b_length", leading_b_length),
+            ("displacement", displacement),
+        ] {
+            if length == 0 {
+                return Err(Error::invalid_parameter(name, "must be greater than 0"));
+            }
+        }
+
+        let mut indicator = Self {
+            tenkan_sen: RingBuffer::new(),
+            kijun_sen: RingBuffer::new(),
+            senkou_span_a: RingBuffer::new(),
+            senkou_span_b: RingBuffer::new(),
+            conversion: Midpoint::new(conversion_length),
+            base: Midpoint::new(base_length),
+            leading_b: Midpoint::new(leading_b_length),
+            leading_a_values: RollingWindow::new(displacement),
+            leading_b_values: RollingWindow::new(displacement),
+            closes: RingBuffer::new(),
+            offset: displacement - 1,
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    // Paramètres classiques (9, 26, 52, 26)
+    pub fn standard(klines: &[KlineSummary]) -> Result<Self> {
+        Self::new(klines, 9, 26, 52, 26)
+    }
+
+    // Calcul par lot : mêmes séries que l'indicateur incrémental
+    pub fn compute(
+        candles: &Candles,
+        conversion_length: usize,
+        base_length: usize,
+        leading_b_length: usize,
+        displacement: usize,
+    ) -> Result<Series> {
+        Self::new(&[], conversion_length, base_length, leading_b_length, displacement)?;
+
+        let (high, low) = (&candles.high, &candles.low);
+        let tenkan = kernels::midpoint(high, low, conversion_length);
+        let kijun = kernels::midpoint(high, low, base_length);
+        let leading_a = kernels::zip_with(&tenkan, &kijun, |tenkan, kijun| (tenkan + kijun) / 2.0);
+        let leading_b = kernels::midpoint(high, low, leading_b_length);
+        Ok(Series::new(vec![
+            ("tenkan_sen", tenkan),
+            ("kijun_sen", kijun),
+            ("senkou_span_a", kernels::shift(&leading_a, displacement - 1)),
+            ("senkou_span_b", kernels::shift(&leading_b, displacement - 1)),
+        ]))
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            let high = PriceSource::High.extract(kline);
+            let low = PriceSource::Low.extract(kline);
+
+            let tenkan = self.conversion.next(high, low);
+            let kijun = self.base.next(high, low);
+            self.leading_a_values.push((tenkan + kijun) / 2.0);
+            self.leading_b_values.push(self.leading_b.next(high, low));
+
+            // Valeur calculée il y a `offset` bougies, affichée sous la bougie courante
+            let displaced = |values: &RollingWindow| {
+                if values.is_full() {
+                    values.first().unwrap_or(f64::NAN)
+                } else {
+                    f64::NAN
+                }
+            };
+
+            self.tenkan_sen.push(tenkan);
+            self.kijun_sen.push(kijun);
+            self.senkou_span_a.push(displaced(&self.leading_a_values));
+            self.senkou_span_b.push(displaced(&self.leading_b_values));
+            self.closes.push(PriceSource::Close.extract(kline));
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+
+    // Nuage projeté pour les `displacement - 1` prochaines bougies : (senkou A, senkou B).
+    // Ces valeurs sont calculées avec les données actuelles, leur lecture est donc sans lookahead.
+    pub fn projected_cloud(&self) -> Vec<(f64, f64)> {
+        self.leading_a_values
+            .iter()
+            .zip(self.leading_b_values.iter())
+            .skip(1)
+            .map(|(a, b)| (*a, *b))
+            .collect()
+    }
+
+    // Chikou Span affichée sous la bougie `index` : la clôture `displacement - 1` bougies plus tard.
+    // None tant que cette clôture n'est pas connue, pour ne jamais lire le futur.
+    pub fn chikou_span_at(&self, index: usize) -> Option<f64> {
+        self.closes.get(index + self.offset).copied()
+    }
+
+    // Chikou Span de la dernière bougie, comparée au prix affiché `displacement - 1` bougies plus tôt
+    pub fn chikou_span_reference(&self) -> Option<(f64, f64)> {
+        let last = self.closes.len().checked_sub(1)?;
+        let reference = last.checked_sub(self.offset)?;
+        Some((self.closes[last], self.closes[reference]))
+    }
+}
+
+impl Observer for Ichimoku {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    // La Chikou compare la dernière clôture à celle d'il y a `offset` bougies
+    fn lookback(&self) -> usize {
+        self.offset + 1
+    }
+
+    // Même capacité pour toutes les séries, pour que `chikou_span_at` reste aligné sur les autres,
+    // et jamais moins que `lookback` pour garder la clôture de référence de la Chikou
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        let capacity = capacity.map(|capacity| capacity.max(self.lookback()));
+        self.tenkan_sen.set_capacity(capacity);
+        self.kijun_sen.set_capacity(capacity);
+        self.senkou_span_a.set_capacity(capacity);
+        self.senkou_span_b.set_capacity(capacity);
+        self.closes.set_capacity(capacity);
+    }
+}
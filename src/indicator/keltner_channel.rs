@@ -0,0 +1,165 @@
+use std::any::Any;
+
+use binance::model::KlineSummary;
+
+use crate::data::{Candles, RingBuffer};
+use crate::strategy::interface::Observer;
+use crate::{Error, Result};
+
+use super::atr::AtrCalculator;
+use super::batch::Series;
+use super::kernels;
+use super::moving_average::{Ema, MovingAverage};
+use super::{unseen_klines, PriceSource};
+
+fn check_multiplier(multiplier: f64) -> Result<f64> {
+    if !multiplier.is_finite() || multiplier <= 0.0 {
+        return Err(Error::invalid_parameter("multiplier", "must be a positive number"));
+    }
+    Ok(multiplier)
+}
+
+// Bandes `basis` ± `multiplier` ATR calculées par lot
+fn bands(candles: &Candles, basis: Vec<f64>, atr_length: usize, multiplier: f64) -> Series {
+    let atr = kernels::atr(&candles.high, &candles.low, &candles.close, atr_length);
+    let upper = kernels::zip_with(&basis, &atr, |basis, atr| basis + multiplier * atr);
+    let lower = kernels::zip_with(&basis, &atr, |basis, atr| basis - multiplier * atr);
+    Series::new(vec![("basis", basis), ("upper", upper), ("lower", lower)])
+}
+
+// Canal de Keltner : EMA de la source ± `multiplier` ATR
+#[derive(Debug)]
+pub struct KeltnerChannel {
+    pub upper_band: RingBuffer<f64>,
+    pub lower_band: RingBuffer<f64>,
+    pub basis: RingBuffer<f64>,
+    average: Ema,
+    atr: AtrCalculator,
+    multiplier: f64,
+    source: PriceSource,
+    last_open_time: Option<i64>,
+}
+
+impl KeltnerChannel {
+    pub fn new(
+        klines: &[KlineSummary],
+        length: usize,
+        atr_length: usize,
+        multiplier: f64,
+        source: PriceSource,
+    ) -> Result<Self> {
+        let mut indicator = Self {
+            upper_band: RingBuffer::new(),
+            lower_band: RingBuffer::new(),
+            basis: RingBuffer::new(),
+            average: Ema::new(length)?,
+            atr: AtrCalculator::new(atr_length)?,
+            multiplier: check_multiplier(multiplier)?,
+            source,
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    pub fn compute(
+        candles: &Candles,
+        length: usize,
+        atr_length: usize,
+        multiplier: f64,
+        source: PriceSource,
+    ) -> Result<Series> {
+        Self::new(&[], length, atr_length, multiplier, source)?;
+        let basis = kernels::ema(&source.column(candles), length);
+        Ok(bands(candles, basis, atr_length, multiplier))
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            let basis = self.average.next(self.source.extract(kline));
+            let range = self.multiplier * self.atr.next(kline);
+
+            self.basis.push(basis);
+            self.upper_band.push(basis + range);
+            self.lower_band.push(basis - range);
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+}
+
+impl Observer for KeltnerChannel {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.upper_band.set_capacity(capacity);
+        self.lower_band.set_capacity(capacity);
+        self.basis.set_capacity(capacity);
+    }
+}
+
+// Bandes ATR : source de la bougie ± `multiplier` ATR
+#[derive(Debug)]
+pub struct AtrBands {
+    pub upper_band: RingBuffer<f64>,
+    pub lower_band: RingBuffer<f64>,
+    pub basis: RingBuffer<f64>,
+    atr: AtrCalculator,
+    multiplier: f64,
+    source: PriceSource,
+    last_open_time: Option<i64>,
+}
+
+impl AtrBands {
+    pub fn new(klines: &[KlineSummary], atr_length: usize, multiplier: f64, source: PriceSource) -> Result<Self> {
+        let mut indicator = Self {
+            upper_band: RingBuffer::new(),
+            lower_band: RingBuffer::new(),
+            basis: RingBuffer::new(),
+            atr: AtrCalculator::new(atr_length)?,
+            multiplier: check_multiplier(multiplier)?,
+            source,
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    pub fn compute(candles: &Candles, atr_length: usize, multiplier: f64, source: PriceSource) -> Result<Series> {
+        Self::new(&[], atr_length, multiplier, source)?;
+        Ok(bands(candles, source.column(candles).into_owned(), atr_length, multiplier))
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            let basis = self.source.extract(kline);
+            let range = self.multiplier * self.atr.next(kline);
+
+            self.basis.push(basis);
+            self.upper_band.push(basis + range);
+            self.lower_band.push(basis - range);
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+}
+
+impl Observer for AtrBands {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.upper_band.set_capacity(capacity);
+        self.lower_band.set_capacity(capacity);
+        self.basis.set_capacity(capacity);
+    }
+}
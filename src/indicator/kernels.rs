@@ -0,0 +1,445 @@
+// Calculs par lot sur des colonnes f64 contiguës, une valeur par élément d'entrée (NaN pendant
+// l'initialisation). Les résultats sont identiques à ceux des indicateurs incrémentaux : les
+// moyennes mobiles ignorent les NaN en entrée, les fenêtres glissantes sont positionnelles.
+
+use std::collections::VecDeque;
+
+
+// Appliquer une moyenne aux seules valeurs définies, en conservant les NaN à leur place
+// (comme `MovingAverage::next` qui ignore les NaN). Cas courant : des NaN en tête uniquement.
+fn defined(src: &[f64], f: impl Fn(&[f64]) -> Vec<f64>) -> Vec<f64> {
+    let start = src.iter().position(|value| !value.is_nan()).unwrap_or(src.len());
+    let mut out = vec![f64::NAN; src.len()];
+
+    if src[start..].iter().all(|value| !value.is_nan()) {
+        out[start..].copy_from_slice(&f(&src[start..]));
+        return out;
+    }
+
+    let indices: Vec<usize> = (start..src.len()).filter(|&i| !src[i].is_nan()).collect();
+    let values: Vec<f64> = indices.iter().map(|&i| src[i]).collect();
+    for (i, value) in indices.into_iter().zip(f(&values)) {
+        out[i] = value;
+    }
+    out
+}
+
+pub fn sma(src: &[f64], period: usize) -> Vec<f64> {
+    defined(src, |src| {
+        let mut out = vec![f64::NAN; src.len()];
+        let mut sum = 0.0;
+        for i in 0..src.len() {
+            sum += src[i];
+            if i >= period {
+                sum -= src[i - period];
+            }
+            if i + 1 >= period {
+                out[i] = sum / period as f64;
+            }
+        }
+        out
+    })
+}
+
+// Moyenne exponentielle initialisée par la SMA des `period` premières valeurs
+fn exponential(src: &[f64], period: usize, alpha: f64) -> Vec<f64> {
+    defined(src, |src| {
+        let mut out = sma(&src[..period.min(src.len())], period);
+        out.resize(src.len(), f64::NAN);
+        for i in period..src.len() {
+            out[i] = alpha * src[i] + (1.0 - alpha) * out[i - 1];
+        }
+        out
+    })
+}
+
+pub fn ema(src: &[f64], period: usize) -> Vec<f64> {
+    exponential(src, period, 2.0 / (period as f64 + 1.0))
+}
+
+pub fn rma(src: &[f64], period: usize) -> Vec<f64> {
+    exponential(src, period, 1.0 / period as f64)
+}
+
+// Somme pondérée glissante : chaque pas ajoute `period` fois la nouvelle valeur et retire une fois
+// chaque valeur de la fenêtre précédente (sa somme)
+pub fn wma(src: &[f64], period: usize) -> Vec<f64> {
+    let weights = (period * (period + 1)) as f64 / 2.0;
+    defined(src, |src| {
+        let mut out = vec![f64::NAN; src.len()];
+        let (mut sum, mut weighted) = (0.0, 0.0);
+        for (end, exact) in sliding_windows(src, period) {
+            let window = &src[end - period..end];
+            if exact {
+                sum = window.iter().sum();
+                weighted = window.iter().enumerate().map(|(i, v)| (i + 1) as f64 * v).sum();
+            } else {
+                weighted += period as f64 * src[end - 1] - sum;
+                sum += src[end - 1] - src[end - period - 1];
+            }
+            out[end - 1] = weighted / weights;
+        }
+        out
+    })
+}
+
+pub fn hma(src: &[f64], period: usize) -> Vec<f64> {
+    let half = wma(src, (period / 2).max(1));
+    let full = wma(src, period);
+    let raw = zip_with(&half, &full, |half, full| 2.0 * half - full);
+    wma(&raw, ((period as f64).sqrt().floor() as usize).max(1))
+}
+
+pub fn dema(src: &[f64], period: usize) -> Vec<f64> {
+    let first = ema(src, period);
+    let second = ema(&first, period);
+    zip_with(&first, &second, |first, second| 2.0 * first - second)
+}
+
+pub fn tema(src: &[f64], period: usize) -> Vec<f64> {
+    let first = ema(src, period);
+    let second = ema(&first, period);
+    let third = ema(&second, period);
+    (0..src.len()).map(|i| 3.0 * first[i] - 3.0 * second[i] + third[i]).collect()
+}
+
+// Moyenne adaptative de Kaufman (`fast` et `slow` en nombre de bougies)
+pub fn kama(src: &[f64], period: usize, fast: usize, slow: usize) -> Vec<f64> {
+    let fast = 2.0 / (fast as f64 + 1.0);
+    let slow = 2.0 / (slow as f64 + 1.0);
+    defined(src, |src| {
+        let mut out = vec![f64::NAN; src.len()];
+        for i in period..src.len() {
+            let window = &src[i - period..=i];
+            let change = (src[i] - window[0]).abs();
+            let volatility: f64 = window.windows(2).map(|pair| (pair[1] - pair[0]).abs()).sum();
+            let efficiency = if volatility == 0.0 { 0.0 } else { change / volatility };
+            let smoothing = (efficiency * (fast - slow) + slow).powi(2);
+
+            let previous = if i == period { src[i] } else { out[i - 1] };
+            out[i] = previous + smoothing * (src[i] - previous);
+        }
+        out
+    })
+}
+
+// Moyenne pondérée par le volume (valeur précédente conservée si le volume de la fenêtre est nul)
+pub fn vwma(src: &[f64], volume: &[f64], period: usize) -> Vec<f64> {
+    let mut out = vec![f64::NAN; src.len()];
+    let indices: Vec<usize> = (0..src.len()).filter(|&i| !src[i].is_nan() && !volume[i].is_nan()).collect();
+    let mut value = f64::NAN;
+
+    for (n, &i) in indices.iter().enumerate() {
+        if n + 1 >= period {
+            let window = &indices[n + 1 - period..=n];
+            let total: f64 = window.iter().map(|&j| volume[j]).sum();
+            if total > 0.0 {
+                value = window.iter().map(|&j| src[j] * volume[j]).sum::<f64>() / total;
+            }
+        }
+        out[i] = value;
+    }
+    out
+}
+
+// Combiner deux colonnes élément par élément
+pub fn zip_with(a: &[f64], b: &[f64], f: impl Fn(f64, f64) -> f64) -> Vec<f64> {
+    a.iter().zip(b).map(|(a, b)| f(*a, *b)).collect()
+}
+
+// Décaler une colonne de `offset` éléments vers le futur (NaN en tête)
+pub fn shift(src: &[f64], offset: usize) -> Vec<f64> {
+    let offset = offset.min(src.len());
+    let mut out = vec![f64::NAN; offset];
+    out.extend_from_slice(&src[..src.len() - offset]);
+    out
+}
+
+// Fins des fenêtres glissantes de `length` valeurs, et si l'état cumulé doit être recalculé sur la
+// fenêtre : à la première, toutes les `length` fenêtres (la dérive des arrondis reste bornée, pour
+// un coût total en O(n)) et tant qu'une valeur non finie est dans la fenêtre ou vient d'en sortir
+fn sliding_windows(src: &[f64], length: usize) -> impl Iterator<Item = (usize, bool)> + '_ {
+    let mut last_invalid: Option<usize> = None;
+    (0..src.len()).filter_map(move |i| {
+        if !src[i].is_finite() {
+            last_invalid = Some(i);
+        }
+        let start = (i + 1).checked_sub(length)?;
+        let exact = start % length.max(1) == 0 || last_invalid.is_some_and(|invalid| invalid + 1 >= start);
+        Some((i + 1, exact))
+    })
+}
+
+// Variance de population à partir des sommes des écarts `s1` et des carrés `s2` à une valeur de
+// référence. Une variance sous le bruit d'arrondi de ces sommes est nulle (fenêtre constante).
+fn variance(s1: f64, s2: f64, length: f64) -> f64 {
+    let variance = s2 / length - (s1 / length).powi(2);
+    if variance <= 1e-12 * s2 / length {
+        0.0
+    } else {
+        variance
+    }
+}
+
+pub fn rolling_sum(src: &[f64], length: usize) -> Vec<f64> {
+    let mut out = vec![f64::NAN; src.len()];
+    let mut sum = 0.0;
+    for (end, exact) in sliding_windows(src, length) {
+        if exact {
+            sum = src[end - length..end].iter().sum();
+        } else {
+            sum += src[end - 1] - src[end - length - 1];
+        }
+        out[end - 1] = sum;
+    }
+    out
+}
+
+// Plus haut glissant, en O(n) avec une file monotone
+pub fn rolling_max(src: &[f64], length: usize) -> Vec<f64> {
+    rolling_extreme(src, length, |a, b| a >= b).into_iter().map(|(_, value)| value).collect()
+}
+
+pub fn rolling_min(src: &[f64], length: usize) -> Vec<f64> {
+    rolling_extreme(src, length, |a, b| a <= b).into_iter().map(|(_, value)| value).collect()
+}
+
+// (bougies depuis l'extrême, extrême) de chaque fenêtre ; l'extrême le plus récent en cas d'égalité
+fn rolling_extreme(src: &[f64], length: usize, dominates: impl Fn(f64, f64) -> bool) -> Vec<(f64, f64)> {
+    let mut out = vec![(f64::NAN, f64::NAN); src.len()];
+    let mut candidates: VecDeque<usize> = VecDeque::with_capacity(length + 1);
+
+    for i in 0..src.len() {
+        while candidates.back().is_some_and(|&j| dominates(src[i], src[j])) {
+            candidates.pop_back();
+        }
+        candidates.push_back(i);
+        if candidates.front().is_some_and(|&j| j + length <= i) {
+            candidates.pop_front();
+        }
+        if i + 1 >= length {
+            out[i] = ((i - candidates[0]) as f64, src[candidates[0]]);
+        }
+    }
+    out
+}
+
+// Nombre de bougies depuis le plus haut (plus bas) de la fenêtre, le plus récent en cas d'égalité
+pub fn rolling_bars_since_max(src: &[f64], length: usize) -> Vec<f64> {
+    rolling_extreme(src, length, |a, b| a >= b).into_iter().map(|(bars, _)| bars).collect()
+}
+
+pub fn rolling_bars_since_min(src: &[f64], length: usize) -> Vec<f64> {
+    rolling_extreme(src, length, |a, b| a <= b).into_iter().map(|(bars, _)| bars).collect()
+}
+
+// Moyenne et écart-type de population glissants, par sommes des écarts à la première valeur de la
+// fenêtre recalculée (proche des valeurs de la fenêtre, pour limiter les pertes de précision)
+pub fn rolling_mean_std(src: &[f64], length: usize) -> (Vec<f64>, Vec<f64>) {
+    let mut mean = vec![f64::NAN; src.len()];
+    let mut std_dev = vec![f64::NAN; src.len()];
+    let (mut reference, mut s1, mut s2) = (0.0, 0.0, 0.0);
+    for (end, exact) in sliding_windows(src, length) {
+        let window = &src[end - length..end];
+        if exact {
+            reference = window[0];
+            s1 = window.iter().map(|value| value - reference).sum();
+            s2 = window.iter().map(|value| (value - reference).powi(2)).sum();
+        } else {
+            let (added, removed) = (src[end - 1] - reference, src[end - length - 1] - reference);
+            s1 += added - removed;
+            s2 += added * added - removed * removed;
+        }
+        mean[end - 1] = reference + s1 / length as f64;
+        std_dev[end - 1] = variance(s1, s2, length as f64).sqrt();
+    }
+    (mean, std_dev)
+}
+
+// True Range ; la première valeur vaut high - low (comme `ta.tr(true)`)
+pub fn true_range(high: &[f64], low: &[f64], close: &[f64]) -> Vec<f64> {
+    (0..high.len())
+        .map(|i| match i.checked_sub(1) {
+            Some(previous) => (high[i] - low[i])
+                .max((high[i] - close[previous]).abs())
+                .max((low[i] - close[previous]).abs()),
+            None => high[i] - low[i],
+        })
+        .collect()
+}
+
+pub fn atr(high: &[f64], low: &[f64], close: &[f64], length: usize) -> Vec<f64> {
+    rma(&true_range(high, low, close), length)
+}
+
+// RSI de Wilder
+pub fn rsi(src: &[f64], period: usize) -> Vec<f64> {
+    defined(src, |src| {
+        let changes: Vec<f64> = (0..src.len()).map(|i| if i == 0 { f64::NAN } else { src[i] - src[i - 1] }).collect();
+        // `f64::max` ignorerait le NaN de la première bougie : il doit rester NaN
+        let clamp = |change: f64| if change.is_nan() { f64::NAN } else { change.max(0.0) };
+        let gains = rma(&changes.iter().map(|change| clamp(*change)).collect::<Vec<_>>(), period);
+        let losses = rma(&changes.iter().map(|change| clamp(-change)).collect::<Vec<_>>(), period);
+
+        zip_with(&gains, &losses, |gain, loss| {
+            if gain.is_nan() || loss.is_nan() {
+                f64::NAN
+            } else if loss == 0.0 {
+                100.0
+            } else if gain == 0.0 {
+                0.0
+            } else {
+                100.0 - 100.0 / (1.0 + gain / loss)
+            }
+        })
+    })
+}
+
+// Position de `src` dans le range [plus bas, plus haut] glissant, en pourcentage (NaN si range nul)
+pub fn stochastic(src: &[f64], high: &[f64], low: &[f64], length: usize) -> Vec<f64> {
+    let highest = rolling_max(high, length);
+    let lowest = rolling_min(low, length);
+    (0..src.len())
+        .map(|i| {
+            let range = highest[i] - lowest[i];
+            if range == 0.0 || range.is_nan() {
+                f64::NAN
+            } else {
+                100.0 * (src[i] - lowest[i]) / range
+            }
+        })
+        .collect()
+}
+
+// Régression linéaire glissante : (valeur en fin de fenêtre, pente, ordonnée à l'origine, R²).
+// x vaut 0 pour la plus ancienne valeur : en glissant, chaque valeur restante perd 1 en abscisse,
+// la somme des x·y perd donc la somme des valeurs restantes.
+pub fn linear_regression(src: &[f64], length: usize) -> [Vec<f64>; 4] {
+    let mut out = [(); 4].map(|_| vec![f64::NAN; src.len()]);
+    if length < 2 {
+        return out;
+    }
+
+    let n = length as f64;
+    let mean_x = (n - 1.0) / 2.0;
+    let sxx = n * (n * n - 1.0) / 12.0;
+    let (mut reference, mut s1, mut s2, mut sxy) = (0.0, 0.0, 0.0, 0.0);
+    for (end, exact) in sliding_windows(src, length) {
+        let window = &src[end - length..end];
+        if exact {
+            reference = window[0];
+            let deviations = window.iter().map(|value| value - reference);
+            s1 = deviations.clone().sum();
+            s2 = deviations.clone().map(|value| value * value).sum();
+            sxy = deviations.enumerate().map(|(x, value)| x as f64 * value).sum();
+        } else {
+            let (added, removed) = (src[end - 1] - reference, src[end - length - 1] - reference);
+            sxy += (n - 1.0) * added - (s1 - removed);
+            s1 += added - removed;
+            s2 += added * added - removed * removed;
+        }
+
+        let covariance = sxy - mean_x * s1;
+        let syy = variance(s1, s2, n) * n;
+        let slope = covariance / sxx;
+        let intercept = reference + s1 / n - slope * mean_x;
+        out[0][end - 1] = intercept + slope * (n - 1.0);
+        out[1][end - 1] = slope;
+        out[2][end - 1] = intercept;
+        out[3][end - 1] = if syy > 0.0 { covariance * covariance / (sxx * syy) } else { f64::NAN };
+    }
+    out
+}
+
+pub fn cumulative_sum(src: &[f64]) -> Vec<f64> {
+    let mut total = 0.0;
+    src.iter()
+        .map(|value| {
+            total += value;
+            total
+        })
+        .collect()
+}
+
+// Milieu du plus haut et du plus bas glissants (lignes d'Ichimoku)
+pub fn midpoint(high: &[f64], low: &[f64], length: usize) -> Vec<f64> {
+    zip_with(&rolling_max(high, length), &rolling_min(low, length), |high, low| (high + low) / 2.0)
+}
+
+// Pivot confirmé à chaque bougie : la valeur d'il y a `right` bougies si elle dépasse strictement
+// les `left` précédentes et les `right` suivantes, NaN sinon
+pub fn pivot_highs(src: &[f64], left: usize, right: usize) -> Vec<f64> {
+    pivots(src, &rolling_max(src, left), &rolling_max(src, right), left, right, |candidate, other| candidate > other)
+}
+
+pub fn pivot_lows(src: &[f64], left: usize, right: usize) -> Vec<f64> {
+    pivots(src, &rolling_min(src, left), &rolling_min(src, right), left, right, |candidate, other| candidate < other)
+}
+
+fn pivots(src: &[f64], before: &[f64], after: &[f64], left: usize, right: usize, beats: fn(f64, f64) -> bool) -> Vec<f64> {
+    (0..src.len())
+        .map(|i| match i.checked_sub(right).filter(|candidate| *candidate >= left) {
+            Some(candidate) if beats(src[candidate], before[candidate - 1]) && beats(src[candidate], after[i]) => src[candidate],
+            _ => f64::NAN,
+        })
+        .collect()
+}
+
+// Dernière valeur définie à chaque position (NaN avant la première)
+pub fn forward_fill(src: &[f64]) -> Vec<f64> {
+    let mut last = f64::NAN;
+    src.iter()
+        .map(|value| {
+            if !value.is_nan() {
+                last = *value;
+            }
+            last
+        })
+        .collect()
+}
+
+// Corrélation de Pearson et covariance / variance de `y` glissantes, par sommes des écarts aux
+// premières valeurs de la fenêtre recalculée (comme `rolling_mean_std`)
+pub fn rolling_correlation(x: &[f64], y: &[f64], length: usize) -> (Vec<f64>, Vec<f64>) {
+    let mut correlation = vec![f64::NAN; x.len()];
+    let mut beta = vec![f64::NAN; x.len()];
+    // Une valeur non finie dans l'une des colonnes force le recalcul des deux
+    let pairs = zip_with(x, y, |x, y| x + y);
+    let n = length as f64;
+    let (mut reference_x, mut reference_y) = (0.0, 0.0);
+    let (mut sx, mut sy, mut sxx, mut syy, mut sxy) = (0.0, 0.0, 0.0, 0.0, 0.0);
+
+    for (end, exact) in sliding_windows(&pairs, length) {
+        let start = end - length;
+        if exact {
+            (reference_x, reference_y) = (x[start], y[start]);
+            (sx, sy, sxx, syy, sxy) = (0.0, 0.0, 0.0, 0.0, 0.0);
+            for i in start..end {
+                let (dx, dy) = (x[i] - reference_x, y[i] - reference_y);
+                sx += dx;
+                sy += dy;
+                sxx += dx * dx;
+                syy += dy * dy;
+                sxy += dx * dy;
+            }
+        } else {
+            let (added_x, added_y) = (x[end - 1] - reference_x, y[end - 1] - reference_y);
+            let (removed_x, removed_y) = (x[start - 1] - reference_x, y[start - 1] - reference_y);
+            sx += added_x - removed_x;
+            sy += added_y - removed_y;
+            sxx += added_x * added_x - removed_x * removed_x;
+            syy += added_y * added_y - removed_y * removed_y;
+            sxy += added_x * added_y - removed_x * removed_y;
+        }
+
+        let (variance_x, variance_y) = (variance(sx, sxx, n), variance(sy, syy, n));
+        let covariance = sxy / n - (sx / n) * (sy / n);
+        if variance_x > 0.0 && variance_y > 0.0 {
+            correlation[end - 1] = covariance / (variance_x * variance_y).sqrt();
+        }
+        if variance_y > 0.0 {
+            beta[end - 1] = covariance / variance_y;
+        }
+    }
+    (correlation, beta)
+}
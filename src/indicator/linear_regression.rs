@@ -0,0 +1,109 @@
+use std::any::Any;
+
+use binance::model::KlineSummary;
+
+use crate::data::{Candles, RingBuffer};
+use crate::strategy::interface::Observer;
+use crate::{Error, Result};
+
+use super::batch::Series;
+use super::kernels;
+use super::window::RollingWindow;
+use super::{unseen_klines, PriceSource};
+
+// Droite des moindres carrés y = intercept + slope × x : (slope, intercept, R²).
+// R² vaut NaN si les y sont constants.
+pub(crate) fn least_squares(points: &[(f64, f64)]) -> (f64, f64, f64) {
+    let n = points.len() as f64;
+    if n < 2.0 {
+        return (f64::NAN, f64::NAN, f64::NAN);
+    }
+
+    let (sum_x, sum_y) = points.iter().fold((0.0, 0.0), |(sx, sy), (x, y)| (sx + x, sy + y));
+    let (mean_x, mean_y) = (sum_x / n, sum_y / n);
+    let (sxx, syy, sxy) = points.iter().fold((0.0, 0.0, 0.0), |(sxx, syy, sxy), (x, y)| {
+        let (dx, dy) = (x - mean_x, y - mean_y);
+        (sxx + dx * dx, syy + dy * dy, sxy + dx * dy)
+    });
+
+    let slope = sxy / sxx;
+    let r_squared = if syy > 0.0 { sxy * sxy / (sxx * syy) } else { f64::NAN };
+    (slope, mean_y - slope * mean_x, r_squared)
+}
+
+// Régression linéaire glissante de la source sur le numéro de bougie.
+// x vaut 0 pour la plus ancienne bougie de la fenêtre : `values` est la droite évaluée
+// sur la dernière bougie (comme `ta.linreg(source, length, 0)`).
+#[derive(Debug)]
+pub struct LinearRegression {
+    pub values: RingBuffer<f64>,
+    pub slope: RingBuffer<f64>,
+    pub intercept: RingBuffer<f64>,
+    pub r_squared: RingBuffer<f64>,
+    window: RollingWindow,
+    source: PriceSource,
+    last_open_time: Option<i64>,
+}
+
+impl LinearRegression {
+    pub fn new(klines: &[KlineSummary], length: usize, source: PriceSource) -> Result<Self> {
+        if length < 2 {
+            return Err(Error::invalid_parameter("length", "must be at least 2"));
+        }
+
+        let mut indicator = Self {
+            values: RingBuffer::new(),
+            slope: RingBuffer::new(),
+            intercept: RingBuffer::new(),
+            r_squared: RingBuffer::new(),
+            window: RollingWindow::new(length),
+            source,
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    pub fn compute(candles: &Candles, length: usize, source: PriceSource) -> Result<Series> {
+        Self::new(&[], length, source)?;
+
+        let [values, slope, intercept, r_squared] = kernels::linear_regression(&source.column(candles), length);
+        Ok(Series::new(vec![("value", values), ("slope", slope), ("intercept", intercept), ("r_squared", r_squared)]))
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            self.window.push(self.source.extract(kline));
+
+            let (slope, intercept, r_squared) = if self.window.is_full() {
+                let points: Vec<(f64, f64)> = self.window.iter().enumerate().map(|(x, y)| (x as f64, *y)).collect();
+                least_squares(&points)
+            } else {
+                (f64::NAN, f64::NAN, f64::NAN)
+            };
+
+            self.values.push(intercept + slope * (self.window.capacity() - 1) as f64);
+            self.slope.push(slope);
+            self.intercept.push(intercept);
+            self.r_squared.push(r_squared);
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+}
+
+impl Observer for LinearRegression {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.values.set_capacity(capacity);
+        self.slope.set_capacity(capacity);
+        self.intercept.set_capacity(capacity);
+        self.r_squared.set_capacity(capacity);
+    }
+}
@@ -0,0 +1,97 @@
+use std::any::Any;
+
+use binance::model::KlineSummary;
+
+use crate::data::{Candles, RingBuffer};
+use crate::strategy::interface::Observer;
+use crate::{Error, Result};
+
+use super::batch::Series;
+use super::kernels;
+use super::moving_average::{Ema, MovingAverage};
+use super::{unseen_klines, PriceSource};
+
+// MACD : différence de deux EMA, ligne de signal (EMA du MACD) et histogramme
+#[derive(Debug)]
+pub struct Macd {
+    pub macd: RingBuffer<f64>,
+    pub signal: RingBuffer<f64>,
+    pub histogram: RingBuffer<f64>,
+    fast: Ema,
+    slow: Ema,
+    signal_average: Ema,
+    source: PriceSource,
+    last_open_time: Option<i64>,
+}
+
+impl Macd {
+    pub fn new(
+        klines: &[KlineSummary],
+        fast_length: usize,
+        slow_length: usize,
+        signal_length: usize,
+        source: PriceSource,
+    ) -> Result<Self> {
+        if fast_length >= slow_length {
+            return Err(Error::invalid_parameter("fast_length", "must be lower than slow_length"));
+        }
+
+        let mut indicator = Self {
+            macd: RingBuffer::new(),
+            signal: RingBuffer::new(),
+            histogram: RingBuffer::new(),
+            fast: Ema::new(fast_length)?,
+            slow: Ema::new(slow_length)?,
+            signal_average: Ema::new(signal_length)?,
+            source,
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    pub fn compute(
+        candles: &Candles,
+        fast_length: usize,
+        slow_length: usize,
+        signal_length: usize,
+        source: PriceSource,
+    ) -> Result<Series> {
+        Self::new(&[], fast_length, slow_length, signal_length, source)?;
+
+        let values = source.column(candles);
+        let macd = kernels::zip_with(&kernels::ema(&values, fast_length), &kernels::ema(&values, slow_length), |fast, slow| fast - slow);
+        let signal = kernels::ema(&macd, signal_length);
+        let histogram = kernels::zip_with(&macd, &signal, |macd, signal| macd - signal);
+        Ok(Series::new(vec![("macd", macd), ("signal", signal), ("histogram", histogram)]))
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            let value = self.source.extract(kline);
+            let macd = self.fast.next(value) - self.slow.next(value);
+            let signal = self.signal_average.next(macd);
+
+            self.macd.push(macd);
+            self.signal.push(signal);
+            self.histogram.push(macd - signal);
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+}
+
+impl Observer for Macd {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.macd.set_capacity(capacity);
+        self.signal.set_capacity(capacity);
+        self.histogram.set_capacity(capacity);
+    }
+}
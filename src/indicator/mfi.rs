@@ -0,0 +1,105 @@
+use std::any::Any;
+
+use binance::model::KlineSummary;
+
+use crate::data::{Candles, RingBuffer};
+use crate::strategy::interface::Observer;
+use crate::{Error, Result};
+
+use super::batch::Series;
+use super::kernels;
+use super::window::RollingWindow;
+use super::{unseen_klines, PriceSource};
+
+// Money Flow Index : RSI du flux monétaire (prix typique × volume)
+#[derive(Debug)]
+pub struct Mfi {
+    pub values: RingBuffer<f64>,
+    positive_flows: RollingWindow,
+    negative_flows: RollingWindow,
+    previous_price: Option<f64>,
+    last_open_time: Option<i64>,
+}
+
+impl Mfi {
+    pub fn new(klines: &[KlineSummary], length: usize) -> Result<Self> {
+        if length == 0 {
+            return Err(Error::invalid_parameter("length", "must be greater than 0"));
+        }
+
+        let mut indicator = Self {
+            values: RingBuffer::new(),
+            positive_flows: RollingWindow::new(length),
+            negative_flows: RollingWindow::new(length),
+            previous_price: None,
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    pub fn compute(candles: &Candles, length: usize) -> Result<Series> {
+        Self::new(&[], length)?;
+
+        let prices = PriceSource::HLC3.column(candles);
+        let mut positive = vec![f64::NAN; candles.len()];
+        let mut negative = vec![f64::NAN; candles.len()];
+        for i in 1..candles.len() {
+            let flow = prices[i] * candles.volume[i];
+            positive[i] = if prices[i] > prices[i - 1] { flow } else { 0.0 };
+            negative[i] = if prices[i] < prices[i - 1] { flow } else { 0.0 };
+        }
+
+        let values = kernels::zip_with(&kernels::rolling_sum(&positive, length), &kernels::rolling_sum(&negative, length), |positive, negative| {
+            if positive.is_nan() || negative.is_nan() {
+                f64::NAN
+            } else if negative == 0.0 {
+                100.0
+            } else {
+                100.0 - 100.0 / (1.0 + positive / negative)
+            }
+        });
+        Ok(Series::single(values))
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            let price = PriceSource::HLC3.extract(kline);
+            let flow = price * PriceSource::Volume.extract(kline);
+
+            if let Some(previous_price) = self.previous_price.replace(price) {
+                self.positive_flows.push(if price > previous_price { flow } else { 0.0 });
+                self.negative_flows.push(if price < previous_price { flow } else { 0.0 });
+            }
+
+            let value = if self.positive_flows.is_full() {
+                let positive = self.positive_flows.sum();
+                let negative = self.negative_flows.sum();
+                if negative == 0.0 {
+                    100.0
+                } else {
+                    100.0 - 100.0 / (1.0 + positive / negative)
+                }
+            } else {
+                f64::NAN
+            };
+
+            self.values.push(value);
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+}
+
+impl Observer for Mfi {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.values.set_capacity(capacity);
+    }
+}
@@ -1,3 +1,5 @@
+use binance::model::KlineSummary;
+
 pub mod chopiness_index;
 pub use chopiness_index::ChoppinessIndex;
 
@@ -6,3 +8,138 @@ pub use donchan_channel::DonchianChannel;
 
 pub mod atr_stop_loss;
 pub use atr_stop_loss::ATRStopLoss;
+
+pub mod moving_average;
+pub use moving_average::{
+    Dema, Ema, Hma, Kama, MovingAverage, MovingAverageIndicator, MovingAverageKind, PriceSource, Rma, Sma, Tema, Vwma,
+    Wma,
+};
+
+pub mod window;
+pub use window::RollingWindow;
+
+pub mod rsi;
+pub use rsi::Rsi;
+
+pub mod stochastic;
+pub use stochastic::{Stochastic, StochasticRsi};
+
+pub mod macd;
+pub use macd::Macd;
+
+pub mod cci;
+pub use cci::Cci;
+
+pub mod williams_r;
+pub use williams_r::WilliamsR;
+
+pub mod rate_of_change;
+pub use rate_of_change::RateOfChange;
+
+pub mod atr;
+pub use atr::Atr;
+
+pub mod bollinger_bands;
+pub use bollinger_bands::BollingerBands;
+
+pub mod keltner_channel;
+pub use keltner_channel::{AtrBands, KeltnerChannel};
+
+pub mod squeeze;
+pub use squeeze::Squeeze;
+
+pub mod adx;
+pub use adx::Adx;
+
+pub mod aroon;
+pub use aroon::Aroon;
+
+pub mod vortex;
+pub use vortex::Vortex;
+
+pub mod supertrend;
+pub use supertrend::SuperTrend;
+
+pub mod obv;
+pub use obv::Obv;
+
+pub mod vwap;
+pub use vwap::{Vwap, VwapAnchor};
+
+pub mod mfi;
+pub use mfi::Mfi;
+
+pub mod chaikin_money_flow;
+pub use chaikin_money_flow::{AccumulationDistribution, ChaikinMoneyFlow};
+
+pub mod taker_ratio;
+pub use taker_ratio::TakerBuySellRatio;
+
+pub mod volume_profile;
+pub use volume_profile::VolumeProfile;
+
+pub mod ichimoku;
+pub use ichimoku::Ichimoku;
+
+pub mod stop;
+pub use stop::{Side, StopConfig, StopIndicator};
+
+pub mod chandelier_exit;
+pub use chandelier_exit::ChandelierExit;
+
+pub mod parabolic_sar;
+pub use parabolic_sar::ParabolicSar;
+
+pub mod swing;
+pub use swing::{Fractals, Swing, SwingDetector, SwingKind, SwingStop, ZigZag};
+
+pub mod pivot_points;
+pub use pivot_points::{PivotKind, PivotLevels, PivotPoints};
+
+pub mod support_resistance;
+pub use support_resistance::{SupportResistance, Zone};
+
+pub mod candlestick;
+pub use candlestick::{CandlePattern, CandlestickPatterns, PatternFlags, PatternTolerance};
+
+pub mod statistics;
+pub use statistics::{StandardDeviation, ZScore};
+
+pub mod linear_regression;
+pub use linear_regression::LinearRegression;
+
+pub mod hurst;
+pub use hurst::HurstExponent;
+
+pub mod correlation;
+pub use correlation::{Autocorrelation, RollingCorrelation};
+
+pub mod kernels;
+
+pub mod batch;
+pub use batch::{IndicatorCache, IndicatorSpec, Series};
+
+pub mod registry;
+pub use registry::{Argument, IndicatorEntry, INDICATORS};
+
+pub mod series;
+pub use series::{IndicatorNode, ObserverNode, SeriesAverage, SeriesChannel, SeriesCombine, SeriesNode, SeriesRsi, SeriesStdDev, Source};
+
+// Bougies postérieures à la dernière bougie traitée par un indicateur incrémental
+// (les bougies sont triées par heure d'ouverture)
+pub(crate) fn unseen_klines(klines: &[KlineSummary], last_open_time: Option<i64>) -> &[KlineSummary] {
+    match last_open_time {
+        Some(last_open_time) => &klines[klines.partition_point(|kline| kline.open_time <= last_open_time)..],
+        None => klines,
+    }
+}
+
+// Valeur d'une série alignée sur `klines` (une valeur par bougie, la dernière valeur correspondant
+// à la dernière bougie) pour la bougie ouverte à `open_time`. None si la bougie n'est pas connue
+// ou si la valeur n'est pas définie (NaN pendant l'initialisation).
+pub fn value_at(klines: &[KlineSummary], series: &[f64], open_time: i64) -> Option<f64> {
+    let index = klines.binary_search_by_key(&open_time, |kline| kline.open_time).ok()?;
+    let ago = klines.len() - 1 - index;
+    let index = series.len().checked_sub(ago + 1)?;
+    Some(series[index]).filter(|value| !value.is_nan())
+}
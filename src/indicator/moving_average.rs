@@ -0,0 +1,700 @@
+use std::any::Any;
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::str::FromStr;
+
+use binance::model::KlineSummary;
+
+use crate::data::{Candles, RingBuffer};
+use crate::strategy::interface::Observer;
+use crate::{Error, Result};
+
+use super::batch::Series;
+use super::kernels;
+use super::unseen_klines;
+
+// Valeur d'une bougie utilisée comme entrée d'un indicateur
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceSource {
+    Open,
+    High,
+    Low,
+    Close,
+    Volume,
+    HL2,   // (high + low) / 2
+    HLC3,  // (high + low + close) / 3
+    OHLC4, // (open + high + low + close) / 4
+}
+
+impl PriceSource {
+    // NaN si le champ n'est pas un nombre valide
+    pub fn extract(&self, kline: &KlineSummary) -> f64 {
+        let parse = |value: &str| value.parse::<f64>().unwrap_or(f64::NAN);
+
+        match self {
+            PriceSource::Open => parse(&kline.open),
+            PriceSource::High => parse(&kline.high),
+            PriceSource::Low => parse(&kline.low),
+            PriceSource::Close => parse(&kline.close),
+            PriceSource::Volume => parse(&kline.volume),
+            PriceSource::HL2 => (parse(&kline.high) + parse(&kline.low)) / 2.0,
+            PriceSource::HLC3 => (parse(&kline.high) + parse(&kline.low) + parse(&kline.close)) / 3.0,
+            PriceSource::OHLC4 => {
+                (parse(&kline.open) + parse(&kline.high) + parse(&kline.low) + parse(&kline.close)) / 4.0
+            }
+        }
+    }
+
+    // Colonne de la source pour des bougies en colonnes (empruntée si elle existe déjà)
+    pub fn column<'a>(&self, candles: &'a Candles) -> Cow<'a, [f64]> {
+        let combine = |columns: &[&[f64]]| {
+            let count = columns.len() as f64;
+            (0..candles.len()).map(|i| columns.iter().map(|column| column[i]).sum::<f64>() / count).collect()
+        };
+
+        match self {
+            PriceSource::Open => Cow::Borrowed(&candles.open),
+            PriceSource::High => Cow::Borrowed(&candles.high),
+            PriceSource::Low => Cow::Borrowed(&candles.low),
+            PriceSource::Close => Cow::Borrowed(&candles.close),
+            PriceSource::Volume => Cow::Borrowed(&candles.volume),
+            PriceSource::HL2 => Cow::Owned(combine(&[&candles.high, &candles.low])),
+            PriceSource::HLC3 => Cow::Owned(combine(&[&candles.high, &candles.low, &candles.close])),
+            PriceSource::OHLC4 => Cow::Owned(combine(&[&candles.open, &candles.high, &candles.low, &candles.close])),
+        }
+    }
+}
+
+impl FromStr for PriceSource {
+    type Err = String;
+
+    // Noms de TradingView : open, high, low, close, volume, hl2, hlc3, ohlc4
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        match value {
+            "open" => Ok(PriceSource::Open),
+            "high" => Ok(PriceSource::High),
+            "low" => Ok(PriceSource::Low),
+            "close" => Ok(PriceSource::Close),
+            "volume" => Ok(PriceSource::Volume),
+            "hl2" => Ok(PriceSource::HL2),
+            "hlc3" => Ok(PriceSource::HLC3),
+            "ohlc4" => Ok(PriceSource::OHLC4),
+            _ => Err(format!("Invalid price source: {}", value)),
+        }
+    }
+}
+
+// Moyenne mobile incrémentale. Les valeurs NaN en entrée sont ignorées, la sortie vaut NaN
+// tant que la période n'est pas remplie.
+pub trait MovingAverage: Debug {
+    fn next(&mut self, value: f64) -> f64;
+
+    // Seule la VWMA utilise le volume, les autres moyennes l'ignorent
+    fn next_with_volume(&mut self, value: f64, _volume: f64) -> f64 {
+        self.next(value)
+    }
+
+    fn value(&self) -> f64;
+    fn period(&self) -> usize;
+    fn reset(&mut self);
+}
+
+impl MovingAverage for Box<dyn MovingAverage> {
+    fn next(&mut self, value: f64) -> f64 {
+        (**self).next(value)
+    }
+
+    fn next_with_volume(&mut self, value: f64, volume: f64) -> f64 {
+        (**self).next_with_volume(value, volume)
+    }
+
+    fn value(&self) -> f64 {
+        (**self).value()
+    }
+
+    fn period(&self) -> usize {
+        (**self).period()
+    }
+
+    fn reset(&mut self) {
+        (**self).reset()
+    }
+}
+
+fn check_period(period: usize) -> Result<usize> {
+    if period == 0 {
+        return Err(Error::invalid_parameter("period", "must be greater than 0"));
+    }
+    Ok(period)
+}
+
+// Type de moyenne, pour choisir une moyenne par configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MovingAverageKind {
+    Sma,
+    Ema,
+    Rma,
+    Wma,
+    Hma,
+    Dema,
+    Tema,
+    Kama,
+    Vwma,
+}
+
+impl MovingAverageKind {
+    pub fn build(self, period: usize) -> Result<Box<dyn MovingAverage>> {
+        Ok(match self {
+            MovingAverageKind::Sma => Box::new(Sma::new(period)?),
+            MovingAverageKind::Ema => Box::new(Ema::new(period)?),
+            MovingAverageKind::Rma => Box::new(Rma::new(period)?),
+            MovingAverageKind::Wma => Box::new(Wma::new(period)?),
+            MovingAverageKind::Hma => Box::new(Hma::new(period)?),
+            MovingAverageKind::Dema => Box::new(Dema::new(period)?),
+            MovingAverageKind::Tema => Box::new(Tema::new(period)?),
+            MovingAverageKind::Kama => Box::new(Kama::new(period, 2, 30)?),
+            MovingAverageKind::Vwma => Box::new(Vwma::new(period)?),
+        })
+    }
+
+    // Calcul par lot de la moyenne sur la colonne `source` (volume de la bougie pour la VWMA)
+    pub fn compute(self, candles: &Candles, period: usize, source: PriceSource) -> Result<Series> {
+        self.build(period)?;
+
+        let values = source.column(candles);
+        Ok(Series::single(match self {
+            MovingAverageKind::Sma => kernels::sma(&values, period),
+            MovingAverageKind::Ema => kernels::ema(&values, period),
+            MovingAverageKind::Rma => kernels::rma(&values, period),
+            MovingAverageKind::Wma => kernels::wma(&values, period),
+            MovingAverageKind::Hma => kernels::hma(&values, period),
+            MovingAverageKind::Dema => kernels::dema(&values, period),
+            MovingAverageKind::Tema => kernels::tema(&values, period),
+            MovingAverageKind::Kama => kernels::kama(&values, period, 2, 30),
+            MovingAverageKind::Vwma => kernels::vwma(&values, &candles.volume, period),
+        }))
+    }
+}
+
+// Moyenne mobile simple
+#[derive(Debug, Clone)]
+pub struct Sma {
+    period: usize,
+    window: VecDeque<f64>,
+    sum: f64,
+    value: f64,
+}
+
+impl Sma {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            period: check_period(period)?,
+            window: VecDeque::with_capacity(period + 1),
+            sum: 0.0,
+            value: f64::NAN,
+        })
+    }
+}
+
+impl MovingAverage for Sma {
+    fn next(&mut self, value: f64) -> f64 {
+        if value.is_nan() {
+            return f64::NAN;
+        }
+
+        self.window.push_back(value);
+        self.sum += value;
+        if self.window.len() > self.period {
+            self.sum -= self.window.pop_front().unwrap_or(0.0);
+        }
+
+        if self.window.len() == self.period {
+            self.value = self.sum / self.period as f64;
+        }
+        self.value
+    }
+
+    fn value(&self) -> f64 {
+        self.value
+    }
+
+    fn period(&self) -> usize {
+        self.period
+    }
+
+    fn reset(&mut self) {
+        self.window.clear();
+        self.sum = 0.0;
+        self.value = f64::NAN;
+    }
+}
+
+// Moyenne exponentielle générique, initialisée par la moyenne simple des `period` premières valeurs
+#[derive(Debug, Clone)]
+struct Exponential {
+    period: usize,
+    alpha: f64,
+    seed: Sma,
+    value: f64,
+}
+
+impl Exponential {
+    fn new(period: usize, alpha: f64) -> Result<Self> {
+        Ok(Self {
+            period,
+            alpha,
+            seed: Sma::new(period)?,
+            value: f64::NAN,
+        })
+    }
+
+    fn next(&mut self, value: f64) -> f64 {
+        if value.is_nan() {
+            return f64::NAN;
+        }
+
+        self.value = if self.value.is_nan() {
+            self.seed.next(value)
+        } else {
+            self.alpha * value + (1.0 - self.alpha) * self.value
+        };
+        self.value
+    }
+
+    fn reset(&mut self) {
+        self.seed.reset();
+        self.value = f64::NAN;
+    }
+}
+
+// Moyenne exponentielle (alpha = 2 / (période + 1))
+#[derive(Debug, Clone)]
+pub struct Ema(Exponential);
+
+impl Ema {
+    pub fn new(period: usize) -> Result<Self> {
+        let period = check_period(period)?;
+        Ok(Self(Exponential::new(period, 2.0 / (period as f64 + 1.0))?))
+    }
+}
+
+impl MovingAverage for Ema {
+    fn next(&mut self, value: f64) -> f64 {
+        self.0.next(value)
+    }
+
+    fn value(&self) -> f64 {
+        self.0.value
+    }
+
+    fn period(&self) -> usize {
+        self.0.period
+    }
+
+    fn reset(&mut self) {
+        self.0.reset()
+    }
+}
+
+// Moyenne de Wilder (RMA, alpha = 1 / période), utilisée par l'ATR et le RSI
+#[derive(Debug, Clone)]
+pub struct Rma(Exponential);
+
+impl Rma {
+    pub fn new(period: usize) -> Result<Self> {
+        let period = check_period(period)?;
+        Ok(Self(Exponential::new(period, 1.0 / period as f64)?))
+    }
+}
+
+impl MovingAverage for Rma {
+    fn next(&mut self, value: f64) -> f64 {
+        self.0.next(value)
+    }
+
+    fn value(&self) -> f64 {
+        self.0.value
+    }
+
+    fn period(&self) -> usize {
+        self.0.period
+    }
+
+    fn reset(&mut self) {
+        self.0.reset()
+    }
+}
+
+// Moyenne pondérée linéairement (poids 1 pour la plus ancienne valeur, `period` pour la plus récente)
+#[derive(Debug, Clone)]
+pub struct Wma {
+    period: usize,
+    window: VecDeque<f64>,
+    value: f64,
+}
+
+impl Wma {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            period: check_period(period)?,
+            window: VecDeque::with_capacity(period + 1),
+            value: f64::NAN,
+        })
+    }
+}
+
+impl MovingAverage for Wma {
+    fn next(&mut self, value: f64) -> f64 {
+        if value.is_nan() {
+            return f64::NAN;
+        }
+
+        self.window.push_back(value);
+        if self.window.len() > self.period {
+            self.window.pop_front();
+        }
+
+        if self.window.len() == self.period {
+            let weighted: f64 = self.window.iter().enumerate().map(|(i, v)| (i + 1) as f64 * v).sum();
+            let weights = (self.period * (self.period + 1)) as f64 / 2.0;
+            self.value = weighted / weights;
+        }
+        self.value
+    }
+
+    fn value(&self) -> f64 {
+        self.value
+    }
+
+    fn period(&self) -> usize {
+        self.period
+    }
+
+    fn reset(&mut self) {
+        self.window.clear();
+        self.value = f64::NAN;
+    }
+}
+
+// Moyenne de Hull : WMA(2 * WMA(n / 2) - WMA(n), sqrt(n))
+#[derive(Debug, Clone)]
+pub struct Hma {
+    period: usize,
+    half: Wma,
+    full: Wma,
+    smoothing: Wma,
+}
+
+impl Hma {
+    pub fn new(period: usize) -> Result<Self> {
+        let period = check_period(period)?;
+        Ok(Self {
+            period,
+            half: Wma::new((period / 2).max(1))?,
+            full: Wma::new(period)?,
+            smoothing: Wma::new(((period as f64).sqrt().floor() as usize).max(1))?,
+        })
+    }
+}
+
+impl MovingAverage for Hma {
+    fn next(&mut self, value: f64) -> f64 {
+        if value.is_nan() {
+            return f64::NAN;
+        }
+
+        let half = self.half.next(value);
+        let full = self.full.next(value);
+        self.smoothing.next(2.0 * half - full)
+    }
+
+    fn value(&self) -> f64 {
+        self.smoothing.value()
+    }
+
+    fn period(&self) -> usize {
+        self.period
+    }
+
+    fn reset(&mut self) {
+        self.half.reset();
+        self.full.reset();
+        self.smoothing.reset();
+    }
+}
+
+// Double moyenne exponentielle : 2 * EMA - EMA(EMA)
+#[derive(Debug, Clone)]
+pub struct Dema {
+    first: Ema,
+    second: Ema,
+    value: f64,
+}
+
+impl Dema {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            first: Ema::new(period)?,
+            second: Ema::new(period)?,
+            value: f64::NAN,
+        })
+    }
+}
+
+impl MovingAverage for Dema {
+    fn next(&mut self, value: f64) -> f64 {
+        if value.is_nan() {
+            return f64::NAN;
+        }
+
+        let first = self.first.next(value);
+        let second = self.second.next(first);
+        self.value = 2.0 * first - second;
+        self.value
+    }
+
+    fn value(&self) -> f64 {
+        self.value
+    }
+
+    fn period(&self) -> usize {
+        self.first.period()
+    }
+
+    fn reset(&mut self) {
+        self.first.reset();
+        self.second.reset();
+        self.value = f64::NAN;
+    }
+}
+
+// Triple moyenne exponentielle : 3 * EMA - 3 * EMA(EMA) + EMA(EMA(EMA))
+#[derive(Debug, Clone)]
+pub struct Tema {
+    first: Ema,
+    second: Ema,
+    third: Ema,
+    value: f64,
+}
+
+impl Tema {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            first: Ema::new(period)?,
+            second: Ema::new(period)?,
+            third: Ema::new(period)?,
+            value: f64::NAN,
+        })
+    }
+}
+
+impl MovingAverage for Tema {
+    fn next(&mut self, value: f64) -> f64 {
+        if value.is_nan() {
+            return f64::NAN;
+        }
+
+        let first = self.first.next(value);
+        let second = self.second.next(first);
+        let third = self.third.next(second);
+        self.value = 3.0 * first - 3.0 * second + third;
+        self.value
+    }
+
+    fn value(&self) -> f64 {
+        self.value
+    }
+
+    fn period(&self) -> usize {
+        self.first.period()
+    }
+
+    fn reset(&mut self) {
+        self.first.reset();
+        self.second.reset();
+        self.third.reset();
+        self.value = f64::NAN;
+    }
+}
+
+// Moyenne adaptative de Kaufman, lissage entre les constantes `fast` et `slow` selon l'efficience
+#[derive(Debug, Clone)]
+pub struct Kama {
+    period: usize,
+    fast: f64,
+    slow: f64,
+    window: VecDeque<f64>,
+    value: f64,
+}
+
+impl Kama {
+    pub fn new(period: usize, fast: usize, slow: usize) -> Result<Self> {
+        if fast == 0 || slow <= fast {
+            return Err(Error::invalid_parameter("fast", "must be greater than 0 and lower than slow"));
+        }
+
+        Ok(Self {
+            period: check_period(period)?,
+            fast: 2.0 / (fast as f64 + 1.0),
+            slow: 2.0 / (slow as f64 + 1.0),
+            window: VecDeque::with_capacity(period + 2),
+            value: f64::NAN,
+        })
+    }
+}
+
+impl MovingAverage for Kama {
+    fn next(&mut self, value: f64) -> f64 {
+        if value.is_nan() {
+            return f64::NAN;
+        }
+
+        self.window.push_back(value);
+        if self.window.len() > self.period + 1 {
+            self.window.pop_front();
+        }
+        if self.window.len() <= self.period {
+            return f64::NAN;
+        }
+
+        let change = (value - self.window[0]).abs();
+        let volatility: f64 = self
+            .window
+            .iter()
+            .zip(self.window.iter().skip(1))
+            .map(|(previous, current)| (current - previous).abs())
+            .sum();
+        let efficiency = if volatility == 0.0 { 0.0 } else { change / volatility };
+        let smoothing = (efficiency * (self.fast - self.slow) + self.slow).powi(2);
+
+        // Comme `nz(kama[1], src)` : la première valeur est la source
+        let previous = if self.value.is_nan() { value } else { self.value };
+        self.value = previous + smoothing * (value - previous);
+        self.value
+    }
+
+    fn value(&self) -> f64 {
+        self.value
+    }
+
+    fn period(&self) -> usize {
+        self.period
+    }
+
+    fn reset(&mut self) {
+        self.window.clear();
+        self.value = f64::NAN;
+    }
+}
+
+// Moyenne pondérée par le volume
+#[derive(Debug, Clone)]
+pub struct Vwma {
+    period: usize,
+    window: VecDeque<(f64, f64)>, // (prix, volume)
+    value: f64,
+}
+
+impl Vwma {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            period: check_period(period)?,
+            window: VecDeque::with_capacity(period + 1),
+            value: f64::NAN,
+        })
+    }
+}
+
+impl MovingAverage for Vwma {
+    // Sans volume, chaque valeur a le même poids (équivalent à une SMA)
+    fn next(&mut self, value: f64) -> f64 {
+        self.next_with_volume(value, 1.0)
+    }
+
+    fn next_with_volume(&mut self, value: f64, volume: f64) -> f64 {
+        if value.is_nan() || volume.is_nan() {
+            return f64::NAN;
+        }
+
+        self.window.push_back((value, volume));
+        if self.window.len() > self.period {
+            self.window.pop_front();
+        }
+
+        if self.window.len() == self.period {
+            let volume: f64 = self.window.iter().map(|(_, volume)| volume).sum();
+            if volume > 0.0 {
+                self.value = self.window.iter().map(|(value, volume)| value * volume).sum::<f64>() / volume;
+            }
+        }
+        self.value
+    }
+
+    fn value(&self) -> f64 {
+        self.value
+    }
+
+    fn period(&self) -> usize {
+        self.period
+    }
+
+    fn reset(&mut self) {
+        self.window.clear();
+        self.value = f64::NAN;
+    }
+}
+
+// Moyenne mobile appliquée à une source de prix, une valeur par bougie (NaN pendant l'initialisation)
+#[derive(Debug)]
+pub struct MovingAverageIndicator<M: MovingAverage> {
+    pub values: RingBuffer<f64>,
+    average: M,
+    source: PriceSource,
+    last_open_time: Option<i64>, // Dernière bougie intégrée
+}
+
+impl<M: MovingAverage> MovingAverageIndicator<M> {
+    pub fn new(klines: &[KlineSummary], average: M, source: PriceSource) -> Self {
+        let mut indicator = Self {
+            values: RingBuffer::new(),
+            average,
+            source,
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        indicator
+    }
+
+    // Intégrer les bougies qui n'ont pas encore été traitées
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            let value = self.source.extract(kline);
+            let volume = PriceSource::Volume.extract(kline);
+            self.values.push(self.average.next_with_volume(value, volume));
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+
+    pub fn average(&self) -> &M {
+        &self.average
+    }
+
+    pub fn source(&self) -> PriceSource {
+        self.source
+    }
+}
+
+impl<M: MovingAverage + 'static> Observer for MovingAverageIndicator<M> {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.values.set_capacity(capacity);
+    }
+}
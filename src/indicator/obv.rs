@@ -0,0 +1,69 @@
+use std::any::Any;
+
+use binance::model::KlineSummary;
+
+use crate::data::{Candles, RingBuffer};
+use crate::strategy::interface::Observer;
+
+use super::batch::Series;
+use super::kernels;
+use super::{unseen_klines, PriceSource};
+
+// On-Balance Volume : cumul du volume signé par la variation de clôture
+#[derive(Debug, Default)]
+pub struct Obv {
+    pub values: RingBuffer<f64>,
+    previous_close: Option<f64>,
+    last_open_time: Option<i64>,
+}
+
+impl Obv {
+    pub fn new(klines: &[KlineSummary]) -> Self {
+        let mut indicator = Self::default();
+        indicator.add(klines);
+        indicator
+    }
+
+    pub fn compute(candles: &Candles) -> Series {
+        let close = &candles.close;
+        let signed_volumes: Vec<f64> = (0..candles.len())
+            .map(|i| match i.checked_sub(1) {
+                Some(previous) if close[i] > close[previous] => candles.volume[i],
+                Some(previous) if close[i] < close[previous] => -candles.volume[i],
+                _ => 0.0,
+            })
+            .collect();
+        Series::single(kernels::cumulative_sum(&signed_volumes))
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            let close = PriceSource::Close.extract(kline);
+            let volume = PriceSource::Volume.extract(kline);
+            let previous_value = self.values.last().copied().unwrap_or(0.0);
+
+            let value = match self.previous_close.replace(close) {
+                Some(previous_close) if close > previous_close => previous_value + volume,
+                Some(previous_close) if close < previous_close => previous_value - volume,
+                _ => previous_value,
+            };
+
+            self.values.push(value);
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+}
+
+impl Observer for Obv {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.values.set_capacity(capacity);
+    }
+}
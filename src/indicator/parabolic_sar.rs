@@ -0,0 +1,159 @@
+use std::any::Any;
+
+use binance::model::KlineSummary;
+
+use crate::data::{Candles, RingBuffer};
+use crate::strategy::interface::Observer;
+use crate::{Error, Result};
+
+use super::batch::Series;
+use super::stop::{last_stop, Side, StopIndicator};
+use super::{unseen_klines, PriceSource};
+
+#[derive(Debug, Clone, Copy)]
+struct State {
+    sar: f64,
+    extreme_point: f64,
+    acceleration: f64,
+    long: bool,
+}
+
+// Parabolic SAR de Wilder. `long[i]` indique si la SAR est sous le prix (tendance haussière).
+#[derive(Debug)]
+pub struct ParabolicSar {
+    pub values: RingBuffer<f64>,
+    pub long: RingBuffer<bool>,
+    start: f64,
+    increment: f64,
+    maximum: f64,
+    state: Option<State>,
+    previous: Vec<(f64, f64, f64)>, // (high, low, close) des deux dernières bougies
+    last_open_time: Option<i64>,
+}
+
+impl ParabolicSar {
+    pub fn new(klines: &[KlineSummary], start: f64, increment: f64, maximum: f64) -> Result<Self> {
+        if !(start > 0.0 && increment >= 0.0 && maximum >= start) {
+            return Err(Error::invalid_parameter("start", "expected 0 < start <= maximum and increment >= 0"));
+        }
+
+        let mut indicator = Self {
+            values: RingBuffer::new(),
+            long: RingBuffer::new(),
+            start,
+            increment,
+            maximum,
+            state: None,
+            previous: Vec::with_capacity(3),
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    // Calcul par lot : la SAR dépend de l'état de la bougie précédente (tendance, extrême,
+    // accélération), les colonnes sont donc parcourues une fois dans l'ordre
+    pub fn compute(candles: &Candles, start: f64, increment: f64, maximum: f64) -> Result<Series> {
+        let mut indicator = Self::new(&[], start, increment, maximum)?;
+        for i in 0..candles.len() {
+            indicator.next(candles.high[i], candles.low[i], candles.close[i]);
+        }
+        let long = indicator.long.iter().map(|long| f64::from(u8::from(*long))).collect();
+        Ok(Series::new(vec![("value", indicator.values.to_vec()), ("long", long)]))
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            let high = PriceSource::High.extract(kline);
+            let low = PriceSource::Low.extract(kline);
+            let close = PriceSource::Close.extract(kline);
+            self.next(high, low, close);
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+
+    fn next(&mut self, high: f64, low: f64, close: f64) {
+        let state = match (self.state, self.previous.last()) {
+            (Some(state), _) => Some(self.next_state(state, high, low, false)),
+            // Deuxième bougie : la tendance initiale suit la variation de clôture
+            (None, Some(&(previous_high, previous_low, previous_close))) => {
+                let initial = if close > previous_close {
+                    State { sar: previous_low, extreme_point: high, acceleration: self.start, long: true }
+                } else {
+                    State { sar: previous_high, extreme_point: low, acceleration: self.start, long: false }
+                };
+                Some(self.next_state(initial, high, low, true))
+            }
+            (None, None) => None,
+        };
+
+        self.state = state;
+        self.values.push(state.map_or(f64::NAN, |state| state.sar));
+        self.long.push(state.is_some_and(|state| state.long));
+
+        self.previous.push((high, low, close));
+        if self.previous.len() > 2 {
+            self.previous.remove(0);
+        }
+    }
+
+    // Comme `ta.sar` : retournement testé avant de borner la SAR par les bougies précédentes,
+    // pas d'accélération sur la première bougie d'une tendance
+    fn next_state(&self, state: State, high: f64, low: f64, first_trend_bar: bool) -> State {
+        let sar = state.sar + state.acceleration * (state.extreme_point - state.sar);
+
+        let mut next = if state.long && sar > low {
+            State { sar: high.max(state.extreme_point), extreme_point: low, acceleration: self.start, long: false }
+        } else if !state.long && sar < high {
+            State { sar: low.min(state.extreme_point), extreme_point: high, acceleration: self.start, long: true }
+        } else {
+            let new_extreme = if state.long { high > state.extreme_point } else { low < state.extreme_point };
+            if new_extreme && !first_trend_bar {
+                State {
+                    sar,
+                    extreme_point: if state.long { high } else { low },
+                    acceleration: (state.acceleration + self.increment).min(self.maximum),
+                    long: state.long,
+                }
+            } else {
+                State { sar, ..state }
+            }
+        };
+
+        // La SAR ne peut pas pénétrer le range des deux bougies précédentes
+        for (previous_high, previous_low, _) in &self.previous {
+            next.sar = if next.long { next.sar.min(*previous_low) } else { next.sar.max(*previous_high) };
+        }
+        next
+    }
+}
+
+impl StopIndicator for ParabolicSar {
+    fn stop(&self, side: Side) -> Option<f64> {
+        let long = *self.long.last()?;
+        match side {
+            Side::Long if long => last_stop(&self.values),
+            Side::Short if !long => last_stop(&self.values),
+            _ => None,
+        }
+    }
+}
+
+impl Observer for ParabolicSar {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.values.set_capacity(capacity);
+        self.long.set_capacity(capacity);
+    }
+
+    fn as_stop_indicator(&self) -> Option<&dyn StopIndicator> {
+        Some(self)
+    }
+}
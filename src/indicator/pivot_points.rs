@@ -0,0 +1,172 @@
+use std::any::Any;
+
+use binance::model::KlineSummary;
+
+use crate::data::{Interval, Resampler};
+use crate::data::RingBuffer;
+use crate::strategy::interface::Observer;
+use crate::Result;
+
+use super::{unseen_klines, PriceSource};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PivotKind {
+    Classic,
+    Fibonacci,
+    Camarilla,
+    Woodie,
+}
+
+// Niveaux calculés sur la bougie précédente de l'unité de temps supérieure.
+// `r4`/`s4` ne sont définis que pour Camarilla.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PivotLevels {
+    pub pivot: f64,
+    pub r1: f64,
+    pub r2: f64,
+    pub r3: f64,
+    pub r4: Option<f64>,
+    pub s1: f64,
+    pub s2: f64,
+    pub s3: f64,
+    pub s4: Option<f64>,
+}
+
+impl PivotLevels {
+    // `open` est l'ouverture de la nouvelle période, utilisée par Woodie
+    pub fn compute(kind: PivotKind, high: f64, low: f64, close: f64, open: f64) -> Self {
+        let range = high - low;
+        let classic_pivot = (high + low + close) / 3.0;
+
+        match kind {
+            PivotKind::Classic => Self::classic(classic_pivot, high, low),
+            PivotKind::Woodie => Self::classic((high + low + 2.0 * open) / 4.0, high, low),
+            PivotKind::Fibonacci => Self {
+                pivot: classic_pivot,
+                r1: classic_pivot + 0.382 * range,
+                r2: classic_pivot + 0.618 * range,
+                r3: classic_pivot + range,
+                r4: None,
+                s1: classic_pivot - 0.382 * range,
+                s2: classic_pivot - 0.618 * range,
+                s3: classic_pivot - range,
+                s4: None,
+            },
+            PivotKind::Camarilla => Self {
+                pivot: classic_pivot,
+                r1: close + range * 1.1 / 12.0,
+                r2: close + range * 1.1 / 6.0,
+                r3: close + range * 1.1 / 4.0,
+                r4: Some(close + range * 1.1 / 2.0),
+                s1: close - range * 1.1 / 12.0,
+                s2: close - range * 1.1 / 6.0,
+                s3: close - range * 1.1 / 4.0,
+                s4: Some(close - range * 1.1 / 2.0),
+            },
+        }
+    }
+
+    fn classic(pivot: f64, high: f64, low: f64) -> Self {
+        Self {
+            pivot,
+            r1: 2.0 * pivot - low,
+            r2: pivot + (high - low),
+            r3: high + 2.0 * (pivot - low),
+            r4: None,
+            s1: 2.0 * pivot - high,
+            s2: pivot - (high - low),
+            s3: low - 2.0 * (high - pivot),
+            s4: None,
+        }
+    }
+
+    // Résistances puis supports, du plus proche au plus éloigné du pivot
+    pub fn resistances(&self) -> impl Iterator<Item = f64> {
+        [Some(self.r1), Some(self.r2), Some(self.r3), self.r4].into_iter().flatten()
+    }
+
+    pub fn supports(&self) -> impl Iterator<Item = f64> {
+        [Some(self.s1), Some(self.s2), Some(self.s3), self.s4].into_iter().flatten()
+    }
+}
+
+// Points pivots de l'unité de temps supérieure, une entrée par bougie de base.
+// Les niveaux ne changent qu'à la clôture d'une période supérieure (None avant la première).
+#[derive(Debug)]
+pub struct PivotPoints {
+    pub levels: RingBuffer<Option<PivotLevels>>,
+    kind: PivotKind,
+    resampler: Resampler,
+    current: Option<PivotLevels>,
+    pending: Option<KlineSummary>, // Période clôturée par la dernière bougie traitée
+    last_open_time: Option<i64>,
+}
+
+impl PivotPoints {
+    pub fn new(klines: &[KlineSummary], interval: Interval, kind: PivotKind) -> Result<Self> {
+        let mut indicator = Self {
+            levels: RingBuffer::new(),
+            kind,
+            resampler: Resampler::new(interval),
+            current: None,
+            pending: None,
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            let open = PriceSource::Open.extract(kline);
+            if let Some(closed) = self.pending.take() {
+                self.current = Some(self.compute(&closed, open));
+            }
+
+            // La dernière bougie d'une période utilise encore les niveaux de la période précédente :
+            // les nouveaux niveaux s'appliquent à partir de la bougie suivante. Une période incomplète
+            // n'est pas publiée et les niveaux précédents restent en place.
+            if let Some(closed) = self.resampler.push(kline) {
+                self.pending = Some(closed);
+            }
+            self.levels.push(self.current);
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+
+    fn compute(&self, closed: &KlineSummary, open: f64) -> PivotLevels {
+        PivotLevels::compute(
+            self.kind,
+            PriceSource::High.extract(closed),
+            PriceSource::Low.extract(closed),
+            PriceSource::Close.extract(closed),
+            open,
+        )
+    }
+
+    pub fn kind(&self) -> PivotKind {
+        self.kind
+    }
+
+    pub fn interval(&self) -> Interval {
+        self.resampler.interval()
+    }
+
+    pub fn current(&self) -> Option<&PivotLevels> {
+        self.current.as_ref()
+    }
+}
+
+impl Observer for PivotPoints {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.levels.set_capacity(capacity);
+    }
+}
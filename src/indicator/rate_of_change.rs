@@ -0,0 +1,81 @@
+use std::any::Any;
+
+use binance::model::KlineSummary;
+
+use crate::data::{Candles, RingBuffer};
+use crate::strategy::interface::Observer;
+use crate::{Error, Result};
+
+use super::batch::Series;
+use super::kernels;
+use super::window::RollingWindow;
+use super::{unseen_klines, PriceSource};
+
+// Rate of Change : variation en pourcentage par rapport à la valeur d'il y a `length` bougies
+#[derive(Debug)]
+pub struct RateOfChange {
+    pub values: RingBuffer<f64>,
+    window: RollingWindow,
+    source: PriceSource,
+    last_open_time: Option<i64>,
+}
+
+impl RateOfChange {
+    pub fn new(klines: &[KlineSummary], length: usize, source: PriceSource) -> Result<Self> {
+        if length == 0 {
+            return Err(Error::invalid_parameter("length", "must be greater than 0"));
+        }
+
+        let mut indicator = Self {
+            values: RingBuffer::new(),
+            window: RollingWindow::new(length + 1),
+            source,
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    pub fn compute(candles: &Candles, length: usize, source: PriceSource) -> Result<Series> {
+        Self::new(&[], length, source)?;
+
+        let values = source.column(candles);
+        let previous = kernels::shift(&values, length);
+        Ok(Series::single(kernels::zip_with(&previous, &values, |previous, value| {
+            if previous == 0.0 {
+                f64::NAN
+            } else {
+                100.0 * (value - previous) / previous
+            }
+        })))
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            let value = self.source.extract(kline);
+            self.window.push(value);
+
+            let value = match self.window.first() {
+                Some(previous) if self.window.is_full() && previous != 0.0 => 100.0 * (value - previous) / previous,
+                _ => f64::NAN,
+            };
+
+            self.values.push(value);
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+}
+
+impl Observer for RateOfChange {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.values.set_capacity(capacity);
+    }
+}
@@ -0,0 +1,316 @@
+use crate::{Error, Result};
+
+use super::batch::IndicatorSpec;
+use super::{MovingAverageKind, PriceSource};
+
+// Argument d'un appel d'indicateur par son nom : `donchian(20, 20)`, `rsi(14, hlc3)`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Argument {
+    Number(f64),
+    Source(PriceSource),
+}
+
+// Indicateur disponible par son nom (règles de stratégie, fichiers de configuration)
+pub struct IndicatorEntry {
+    pub name: &'static str,
+    pub parameters: &'static str, // Paramètres dans l'ordre, avec leur valeur par défaut
+    pub outputs: &'static [&'static str],
+    build: fn(&mut Arguments) -> Result<IndicatorSpec>,
+}
+
+const VALUE: &[&str] = &["value"];
+const BANDS: &[&str] = &["basis", "upper", "lower"];
+
+// Moyennes mobiles : `sma(20)`, `ema(50, hl2)`
+macro_rules! average {
+    ($name:literal, $kind:expr) => {
+        IndicatorEntry {
+            name: $name,
+            parameters: "period, source = close",
+            outputs: VALUE,
+            build: |args| {
+                Ok(IndicatorSpec::MovingAverage { kind: $kind, period: args.length("period")?, source: args.source(PriceSource::Close)? })
+            },
+        }
+    };
+}
+
+pub const INDICATORS: &[IndicatorEntry] = &[
+    average!("sma", MovingAverageKind::Sma),
+    average!("ema", MovingAverageKind::Ema),
+    average!("rma", MovingAverageKind::Rma),
+    average!("wma", MovingAverageKind::Wma),
+    average!("hma", MovingAverageKind::Hma),
+    average!("dema", MovingAverageKind::Dema),
+    average!("tema", MovingAverageKind::Tema),
+    average!("kama", MovingAverageKind::Kama),
+    average!("vwma", MovingAverageKind::Vwma),
+    IndicatorEntry {
+        name: "rsi",
+        parameters: "period, source = close",
+        outputs: VALUE,
+        build: |args| Ok(IndicatorSpec::Rsi { period: args.length("period")?, source: args.source(PriceSource::Close)? }),
+    },
+    IndicatorEntry {
+        name: "macd",
+        parameters: "fast_length, slow_length, signal_length, source = close",
+        outputs: &["macd", "signal", "histogram"],
+        build: |args| {
+            Ok(IndicatorSpec::Macd {
+                fast_length: args.length("fast_length")?,
+                slow_length: args.length("slow_length")?,
+                signal_length: args.length("signal_length")?,
+                source: args.source(PriceSource::Close)?,
+            })
+        },
+    },
+    IndicatorEntry {
+        name: "stoch",
+        parameters: "length, smooth_k, smooth_d",
+        outputs: &["k", "d"],
+        build: |args| {
+            Ok(IndicatorSpec::Stochastic {
+                length: args.length("length")?,
+                smooth_k: args.length("smooth_k")?,
+                smooth_d: args.length("smooth_d")?,
+            })
+        },
+    },
+    IndicatorEntry {
+        name: "stoch_rsi",
+        parameters: "rsi_length, stoch_length, smooth_k, smooth_d, source = close",
+        outputs: &["k", "d"],
+        build: |args| {
+            Ok(IndicatorSpec::StochasticRsi {
+                rsi_length: args.length("rsi_length")?,
+                stoch_length: args.length("stoch_length")?,
+                smooth_k: args.length("smooth_k")?,
+                smooth_d: args.length("smooth_d")?,
+                source: args.source(PriceSource::Close)?,
+            })
+        },
+    },
+    IndicatorEntry {
+        name: "cci",
+        parameters: "length, source = hlc3",
+        outputs: VALUE,
+        build: |args| Ok(IndicatorSpec::Cci { length: args.length("length")?, source: args.source(PriceSource::HLC3)? }),
+    },
+    IndicatorEntry {
+        name: "williams_r",
+        parameters: "length",
+        outputs: VALUE,
+        build: |args| Ok(IndicatorSpec::WilliamsR { length: args.length("length")? }),
+    },
+    IndicatorEntry {
+        name: "roc",
+        parameters: "length, source = close",
+        outputs: VALUE,
+        build: |args| Ok(IndicatorSpec::RateOfChange { length: args.length("length")?, source: args.source(PriceSource::Close)? }),
+    },
+    IndicatorEntry {
+        name: "atr",
+        parameters: "length",
+        outputs: VALUE,
+        build: |args| Ok(IndicatorSpec::Atr { length: args.length("length")? }),
+    },
+    IndicatorEntry {
+        name: "bollinger",
+        parameters: "length, multiplier, source = close",
+        outputs: &["basis", "upper", "lower", "percent_b", "bandwidth"],
+        build: |args| {
+            Ok(IndicatorSpec::BollingerBands {
+                length: args.length("length")?,
+                multiplier: args.number("multiplier")?,
+                source: args.source(PriceSource::Close)?,
+            })
+        },
+    },
+    IndicatorEntry {
+        name: "keltner",
+        parameters: "length, atr_length, multiplier, source = close",
+        outputs: BANDS,
+        build: |args| {
+            Ok(IndicatorSpec::KeltnerChannel {
+                length: args.length("length")?,
+                atr_length: args.length("atr_length")?,
+                multiplier: args.number("multiplier")?,
+                source: args.source(PriceSource::Close)?,
+            })
+        },
+    },
+    IndicatorEntry {
+        name: "atr_bands",
+        parameters: "atr_length, multiplier, source = close",
+        outputs: BANDS,
+        build: |args| {
+            Ok(IndicatorSpec::AtrBands {
+                atr_length: args.length("atr_length")?,
+                multiplier: args.number("multiplier")?,
+                source: args.source(PriceSource::Close)?,
+            })
+        },
+    },
+    IndicatorEntry {
+        name: "stdev",
+        parameters: "length, source = close",
+        outputs: VALUE,
+        build: |args| {
+            Ok(IndicatorSpec::StandardDeviation { length: args.length("length")?, source: args.source(PriceSource::Close)? })
+        },
+    },
+    IndicatorEntry {
+        name: "zscore",
+        parameters: "length, source = close",
+        outputs: VALUE,
+        build: |args| Ok(IndicatorSpec::ZScore { length: args.length("length")?, source: args.source(PriceSource::Close)? }),
+    },
+    IndicatorEntry {
+        name: "donchian",
+        parameters: "length, offset = 0",
+        outputs: &["upper", "lower", "basis"],
+        build: |args| Ok(IndicatorSpec::DonchianChannel { length: args.length("length")?, offset: args.count_or("offset", 0)? }),
+    },
+    IndicatorEntry {
+        name: "chop",
+        parameters: "length",
+        outputs: VALUE,
+        build: |args| Ok(IndicatorSpec::ChoppinessIndex { length: args.length("length")? }),
+    },
+    IndicatorEntry {
+        name: "atr_stop",
+        parameters: "length, multiplier",
+        outputs: &["long", "short"],
+        build: |args| Ok(IndicatorSpec::AtrStopLoss { length: args.length("length")?, multiplier: args.number("multiplier")? }),
+    },
+    IndicatorEntry {
+        name: "adx",
+        parameters: "di_length, adx_smoothing",
+        outputs: &["plus_di", "minus_di", "adx"],
+        build: |args| {
+            Ok(IndicatorSpec::Adx { di_length: args.length("di_length")?, adx_smoothing: args.length("adx_smoothing")? })
+        },
+    },
+    IndicatorEntry {
+        name: "aroon",
+        parameters: "length",
+        outputs: &["up", "down", "oscillator"],
+        build: |args| Ok(IndicatorSpec::Aroon { length: args.length("length")? }),
+    },
+    IndicatorEntry {
+        name: "vortex",
+        parameters: "length",
+        outputs: &["plus", "minus"],
+        build: |args| Ok(IndicatorSpec::Vortex { length: args.length("length")? }),
+    },
+    IndicatorEntry {
+        name: "supertrend",
+        parameters: "atr_length, factor",
+        outputs: &["value", "direction"],
+        build: |args| Ok(IndicatorSpec::SuperTrend { atr_length: args.length("atr_length")?, factor: args.number("factor")? }),
+    },
+    IndicatorEntry {
+        name: "linreg",
+        parameters: "length, source = close",
+        outputs: &["value", "slope", "intercept", "r_squared"],
+        build: |args| {
+            Ok(IndicatorSpec::LinearRegression { length: args.length("length")?, source: args.source(PriceSource::Close)? })
+        },
+    },
+    IndicatorEntry { name: "obv", parameters: "", outputs: VALUE, build: |_| Ok(IndicatorSpec::Obv) },
+    IndicatorEntry {
+        name: "mfi",
+        parameters: "length",
+        outputs: VALUE,
+        build: |args| Ok(IndicatorSpec::Mfi { length: args.length("length")? }),
+    },
+    IndicatorEntry { name: "ad", parameters: "", outputs: VALUE, build: |_| Ok(IndicatorSpec::AccumulationDistribution) },
+    IndicatorEntry {
+        name: "cmf",
+        parameters: "length",
+        outputs: VALUE,
+        build: |args| Ok(IndicatorSpec::ChaikinMoneyFlow { length: args.length("length")? }),
+    },
+];
+
+pub fn find_indicator(name: &str) -> Option<&'static IndicatorEntry> {
+    INDICATORS.iter().find(|entry| entry.name == name)
+}
+
+// Indicateur désigné par son nom et ses arguments positionnels (les paramètres sont validés par
+// les constructeurs au moment du calcul)
+pub fn resolve(name: &str, arguments: &[Argument]) -> Result<IndicatorSpec> {
+    let entry = find_indicator(name).ok_or_else(|| Error::invalid_parameter("indicator", format!("unknown indicator '{}'", name)))?;
+    let mut args = Arguments { indicator: entry.name, values: arguments, position: 0 };
+    let spec = (entry.build)(&mut args)?;
+
+    if args.position < arguments.len() {
+        return Err(Error::invalid_parameter(
+            "arguments",
+            format!("{}({}) takes at most {} arguments, got {}", entry.name, entry.parameters, args.position, arguments.len()),
+        ));
+    }
+    Ok(spec)
+}
+
+// Lecture des arguments positionnels d'un appel
+struct Arguments<'a> {
+    indicator: &'static str,
+    values: &'a [Argument],
+    position: usize,
+}
+
+impl Arguments<'_> {
+    fn next(&mut self) -> Option<Argument> {
+        let argument = self.values.get(self.position).copied();
+        self.position += 1;
+        argument
+    }
+
+    fn number(&mut self, name: &'static str) -> Result<f64> {
+        match self.next() {
+            Some(Argument::Number(value)) => Ok(value),
+            Some(Argument::Source(_)) => Err(self.error(name, "expected a number, got a price source")),
+            None => Err(self.error(name, "missing argument")),
+        }
+    }
+
+    // Entier positif (longueur de fenêtre, période)
+    fn length(&mut self, name: &'static str) -> Result<usize> {
+        let value = self.number(name)?;
+        if value.fract() != 0.0 || value < 1.0 {
+            return Err(self.error(name, format!("expected a positive integer, got {}", value)));
+        }
+        Ok(value as usize)
+    }
+
+    // Entier positif ou nul facultatif
+    fn count_or(&mut self, name: &'static str, default: usize) -> Result<usize> {
+        match self.values.get(self.position) {
+            None => {
+                self.position += 1;
+                Ok(default)
+            }
+            Some(_) => {
+                let value = self.number(name)?;
+                if value.fract() != 0.0 || value < 0.0 {
+                    return Err(self.error(name, format!("expected a non-negative integer, got {}", value)));
+                }
+                Ok(value as usize)
+            }
+        }
+    }
+
+    // Source de prix facultative
+    fn source(&mut self, default: PriceSource) -> Result<PriceSource> {
+        match self.next() {
+            Some(Argument::Source(source)) => Ok(source),
+            Some(Argument::Number(value)) => Err(self.error("source", format!("expected a price source, got {}", value))),
+            None => Ok(default),
+        }
+    }
+
+    fn error(&self, name: &'static str, reason: impl Into<String>) -> Error {
+        Error::invalid_parameter(name, format!("{}: {}", self.indicator, reason.into()))
+    }
+}
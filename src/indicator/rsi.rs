@@ -0,0 +1,108 @@
+use std::any::Any;
+
+use binance::model::KlineSummary;
+
+use crate::data::{Candles, RingBuffer};
+use crate::strategy::interface::Observer;
+use crate::{Error, Result};
+
+use super::batch::Series;
+use super::kernels;
+use super::moving_average::{MovingAverage, Rma};
+use super::{unseen_klines, PriceSource};
+
+// Calcul incrémental du RSI de Wilder, réutilisé par le Stochastic RSI
+#[derive(Debug, Clone)]
+pub(crate) struct WilderRsi {
+    average_gain: Rma,
+    average_loss: Rma,
+    previous: Option<f64>,
+}
+
+impl WilderRsi {
+    pub(crate) fn new(period: usize) -> Result<Self> {
+        if period == 0 {
+            return Err(Error::invalid_parameter("period", "must be greater than 0"));
+        }
+
+        Ok(Self {
+            average_gain: Rma::new(period)?,
+            average_loss: Rma::new(period)?,
+            previous: None,
+        })
+    }
+
+    pub(crate) fn next(&mut self, value: f64) -> f64 {
+        if value.is_nan() {
+            return f64::NAN;
+        }
+
+        let previous = match self.previous.replace(value) {
+            Some(previous) => previous,
+            None => return f64::NAN,
+        };
+
+        let change = value - previous;
+        let gain = self.average_gain.next(change.max(0.0));
+        let loss = self.average_loss.next((-change).max(0.0));
+
+        if gain.is_nan() || loss.is_nan() {
+            f64::NAN
+        } else if loss == 0.0 {
+            100.0
+        } else if gain == 0.0 {
+            0.0
+        } else {
+            100.0 - 100.0 / (1.0 + gain / loss)
+        }
+    }
+}
+
+// Relative Strength Index (moyennes de Wilder), une valeur par bougie
+#[derive(Debug)]
+pub struct Rsi {
+    pub values: RingBuffer<f64>,
+    rsi: WilderRsi,
+    source: PriceSource,
+    last_open_time: Option<i64>,
+}
+
+impl Rsi {
+    pub fn new(klines: &[KlineSummary], period: usize, source: PriceSource) -> Result<Self> {
+        let mut indicator = Self {
+            values: RingBuffer::new(),
+            rsi: WilderRsi::new(period)?,
+            source,
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    // Calcul par lot sur des bougies en colonnes (mêmes valeurs que l'historique de `new`)
+    pub fn compute(candles: &Candles, period: usize, source: PriceSource) -> Result<Series> {
+        WilderRsi::new(period)?;
+        Ok(Series::single(kernels::rsi(&source.column(candles), period)))
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            self.values.push(self.rsi.next(self.source.extract(kline)));
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+}
+
+impl Observer for Rsi {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.values.set_capacity(capacity);
+    }
+}
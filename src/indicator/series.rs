@@ -0,0 +1,290 @@
+use std::fmt::Debug;
+
+use binance::model::KlineSummary;
+
+use crate::strategy::interface::Observer;
+use crate::{Error, Result};
+
+use super::rsi::WilderRsi;
+use super::window::RollingWindow;
+use super::{MovingAverage, MovingAverageKind, PriceSource};
+
+// Entrée d'un indicateur de série : un prix de la bougie ou une sortie d'un autre indicateur
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    Price(PriceSource),
+    Series { name: String, output: &'static str },
+}
+
+impl Source {
+    // Sortie principale ("value") d'un indicateur de série
+    pub fn series(name: &str) -> Self {
+        Self::output(name, "value")
+    }
+
+    pub fn output(name: &str, output: &'static str) -> Self {
+        Source::Series { name: name.to_string(), output }
+    }
+}
+
+impl From<PriceSource> for Source {
+    fn from(source: PriceSource) -> Self {
+        Source::Price(source)
+    }
+}
+
+// Indicateur calculé bougie par bougie à partir de séries d'entrée, avec une ou plusieurs sorties
+pub trait SeriesNode: Debug {
+    // Nombre de séries d'entrée attendues
+    fn inputs(&self) -> usize {
+        1
+    }
+
+    fn outputs(&self) -> &'static [&'static str] {
+        &["value"]
+    }
+
+    // Nombre de bougies récentes nécessaires dans `klines`
+    fn lookback(&self) -> usize {
+        1
+    }
+
+    // Borner l'historique conservé par l'indicateur (None : tout l'historique)
+    fn set_retention(&mut self, _capacity: Option<usize>) {}
+
+    // `inputs` contient la valeur courante de chaque entrée (NaN pendant l'initialisation),
+    // `klines` les bougies jusqu'à la bougie courante incluse
+    fn next(&mut self, inputs: &[f64], klines: &[KlineSummary], outputs: &mut [f64]);
+}
+
+// Moyenne mobile d'une série (la VWMA est pondérée par le volume de la bougie courante)
+#[derive(Debug)]
+pub struct SeriesAverage {
+    average: Box<dyn MovingAverage>,
+}
+
+impl SeriesAverage {
+    pub fn new(average: impl MovingAverage + 'static) -> Self {
+        Self { average: Box::new(average) }
+    }
+
+    pub fn build(kind: MovingAverageKind, period: usize) -> Result<Self> {
+        Ok(Self { average: kind.build(period)? })
+    }
+}
+
+impl SeriesNode for SeriesAverage {
+    fn next(&mut self, inputs: &[f64], klines: &[KlineSummary], outputs: &mut [f64]) {
+        let volume = klines.last().map_or(0.0, |kline| PriceSource::Volume.extract(kline));
+        outputs[0] = self.average.next_with_volume(inputs[0], volume);
+    }
+}
+
+// RSI de Wilder d'une série
+#[derive(Debug)]
+pub struct SeriesRsi {
+    rsi: WilderRsi,
+}
+
+impl SeriesRsi {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self { rsi: WilderRsi::new(period)? })
+    }
+}
+
+impl SeriesNode for SeriesRsi {
+    fn next(&mut self, inputs: &[f64], _klines: &[KlineSummary], outputs: &mut [f64]) {
+        outputs[0] = self.rsi.next(inputs[0]);
+    }
+}
+
+// Canal de Donchian d'une série : plus haut, plus bas et milieu des `length` dernières valeurs
+#[derive(Debug)]
+pub struct SeriesChannel {
+    window: RollingWindow,
+}
+
+impl SeriesChannel {
+    pub fn new(length: usize) -> Result<Self> {
+        if length == 0 {
+            return Err(Error::invalid_parameter("length", "must be greater than 0"));
+        }
+        Ok(Self { window: RollingWindow::new(length) })
+    }
+}
+
+impl SeriesNode for SeriesChannel {
+    fn outputs(&self) -> &'static [&'static str] {
+        &["upper", "lower", "basis"]
+    }
+
+    fn next(&mut self, inputs: &[f64], _klines: &[KlineSummary], outputs: &mut [f64]) {
+        if !inputs[0].is_nan() {
+            self.window.push(inputs[0]);
+        }
+
+        let (upper, lower) = if self.window.is_full() {
+            (self.window.max(), self.window.min())
+        } else {
+            (f64::NAN, f64::NAN)
+        };
+        outputs.copy_from_slice(&[upper, lower, (upper + lower) / 2.0]);
+    }
+}
+
+// Écart-type glissant d'une série
+#[derive(Debug)]
+pub struct SeriesStdDev {
+    window: RollingWindow,
+}
+
+impl SeriesStdDev {
+    pub fn new(length: usize) -> Result<Self> {
+        if length < 2 {
+            return Err(Error::invalid_parameter("length", "must be at least 2"));
+        }
+        Ok(Self { window: RollingWindow::new(length) })
+    }
+}
+
+impl SeriesNode for SeriesStdDev {
+    fn next(&mut self, inputs: &[f64], _klines: &[KlineSummary], outputs: &mut [f64]) {
+        if !inputs[0].is_nan() {
+            self.window.push(inputs[0]);
+        }
+        outputs[0] = if self.window.is_full() { self.window.std_dev() } else { f64::NAN };
+    }
+}
+
+// Combinaison de deux séries bougie par bougie (écart, ratio...)
+pub struct SeriesCombine {
+    combine: fn(f64, f64) -> f64,
+}
+
+impl SeriesCombine {
+    pub fn new(combine: fn(f64, f64) -> f64) -> Self {
+        Self { combine }
+    }
+
+    pub fn difference() -> Self {
+        Self::new(|a, b| a - b)
+    }
+
+    pub fn ratio() -> Self {
+        Self::new(|a, b| a / b)
+    }
+}
+
+impl Debug for SeriesCombine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SeriesCombine").finish_non_exhaustive()
+    }
+}
+
+impl SeriesNode for SeriesCombine {
+    fn inputs(&self) -> usize {
+        2
+    }
+
+    fn next(&mut self, inputs: &[f64], _klines: &[KlineSummary], outputs: &mut [f64]) {
+        outputs[0] = (self.combine)(inputs[0], inputs[1]);
+    }
+}
+
+// Adaptateur pour utiliser un indicateur sur bougies (`Observer`) comme source d'une série :
+// la sortie est la dernière valeur de la série extraite. L'indicateur doit être créé sans historique,
+// il reçoit toutes les bougies par le graphe.
+pub struct ObserverNode<T> {
+    indicator: T,
+    extract: fn(&T) -> &[f64],
+}
+
+impl<T: Observer> ObserverNode<T> {
+    pub fn new(indicator: T, extract: fn(&T) -> &[f64]) -> Self {
+        Self { indicator, extract }
+    }
+
+    pub fn indicator(&self) -> &T {
+        &self.indicator
+    }
+}
+
+impl<T: Debug> Debug for ObserverNode<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ObserverNode").field("indicator", &self.indicator).finish_non_exhaustive()
+    }
+}
+
+impl<T: Observer + Debug> SeriesNode for ObserverNode<T> {
+    fn inputs(&self) -> usize {
+        0
+    }
+
+    fn lookback(&self) -> usize {
+        self.indicator.lookback()
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.indicator.set_retention(capacity);
+    }
+
+    fn next(&mut self, _inputs: &[f64], klines: &[KlineSummary], outputs: &mut [f64]) {
+        if let Some(kline) = klines.last() {
+            self.indicator.on_new_kline(kline, klines);
+        }
+        outputs[0] = (self.extract)(&self.indicator).last().copied().unwrap_or(f64::NAN);
+    }
+}
+
+// Adaptateur multi-sorties pour un indicateur sur bougies : `last` copie la dernière valeur de
+// chaque série de l'indicateur dans les sorties (dans l'ordre de `outputs`). Comme pour
+// `ObserverNode`, l'indicateur est créé sans historique.
+pub struct IndicatorNode<T> {
+    indicator: T,
+    outputs: &'static [&'static str],
+    last: fn(&T, &mut [f64]),
+}
+
+impl<T: Observer> IndicatorNode<T> {
+    pub fn new(indicator: T, outputs: &'static [&'static str], last: fn(&T, &mut [f64])) -> Self {
+        Self { indicator, outputs, last }
+    }
+
+    pub fn indicator(&self) -> &T {
+        &self.indicator
+    }
+}
+
+impl<T: Debug> Debug for IndicatorNode<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IndicatorNode")
+            .field("indicator", &self.indicator)
+            .field("outputs", &self.outputs)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: Observer + Debug> SeriesNode for IndicatorNode<T> {
+    fn inputs(&self) -> usize {
+        0
+    }
+
+    fn outputs(&self) -> &'static [&'static str] {
+        self.outputs
+    }
+
+    fn lookback(&self) -> usize {
+        self.indicator.lookback()
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.indicator.set_retention(capacity);
+    }
+
+    fn next(&mut self, _inputs: &[f64], klines: &[KlineSummary], outputs: &mut [f64]) {
+        if let Some(kline) = klines.last() {
+            self.indicator.on_new_kline(kline, klines);
+        }
+        (self.last)(&self.indicator, outputs);
+    }
+}
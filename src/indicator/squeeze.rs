@@ -0,0 +1,89 @@
+use std::any::Any;
+
+use binance::model::KlineSummary;
+
+use crate::data::{Candles, RingBuffer};
+use crate::strategy::interface::Observer;
+use crate::Result;
+
+use super::batch::Series;
+use super::bollinger_bands::BollingerBands;
+use super::keltner_channel::KeltnerChannel;
+use super::PriceSource;
+
+// Détection de squeeze : les bandes de Bollinger sont entièrement à l'intérieur du canal de Keltner.
+// `released` signale la bougie où le squeeze se termine, point de départ classique d'une cassure.
+#[derive(Debug)]
+pub struct Squeeze {
+    pub squeeze_on: RingBuffer<bool>,
+    pub released: RingBuffer<bool>,
+    pub bollinger_bands: BollingerBands,
+    pub keltner_channel: KeltnerChannel,
+}
+
+impl Squeeze {
+    pub fn new(
+        klines: &[KlineSummary],
+        length: usize,
+        bollinger_multiplier: f64,
+        keltner_multiplier: f64,
+    ) -> Result<Self> {
+        let mut indicator = Self {
+            squeeze_on: RingBuffer::new(),
+            released: RingBuffer::new(),
+            bollinger_bands: BollingerBands::new(&[], length, bollinger_multiplier, PriceSource::Close)?,
+            keltner_channel: KeltnerChannel::new(&[], length, length, keltner_multiplier, PriceSource::Close)?,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    // `squeeze_on` et `released` valent 1 ou 0
+    pub fn compute(candles: &Candles, length: usize, bollinger_multiplier: f64, keltner_multiplier: f64) -> Result<Series> {
+        let bollinger = BollingerBands::compute(candles, length, bollinger_multiplier, PriceSource::Close)?;
+        let keltner = KeltnerChannel::compute(candles, length, length, keltner_multiplier, PriceSource::Close)?;
+        let band = |series: &Series, output: &str| series.get(output).map(<[f64]>::to_vec).unwrap_or_default();
+        let (bollinger_upper, bollinger_lower) = (band(&bollinger, "upper"), band(&bollinger, "lower"));
+        let (keltner_upper, keltner_lower) = (band(&keltner, "upper"), band(&keltner, "lower"));
+
+        let squeeze_on: Vec<bool> = (0..candles.len())
+            .map(|i| bollinger_upper[i] < keltner_upper[i] && bollinger_lower[i] > keltner_lower[i])
+            .collect();
+        let released = (0..squeeze_on.len()).map(|i| !squeeze_on[i] && i > 0 && squeeze_on[i - 1]);
+        Ok(Series::new(vec![
+            ("squeeze_on", squeeze_on.iter().map(|on| f64::from(u8::from(*on))).collect()),
+            ("released", released.map(|released| f64::from(u8::from(released))).collect()),
+        ]))
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        self.bollinger_bands.add(klines);
+        self.keltner_channel.add(klines);
+
+        for index in self.squeeze_on.len()..self.bollinger_bands.basis.len() {
+            let squeeze_on = self.bollinger_bands.upper_band[index] < self.keltner_channel.upper_band[index]
+                && self.bollinger_bands.lower_band[index] > self.keltner_channel.lower_band[index];
+            let released = !squeeze_on && self.squeeze_on.last().copied().unwrap_or(false);
+
+            self.squeeze_on.push(squeeze_on);
+            self.released.push(released);
+        }
+    }
+}
+
+impl Observer for Squeeze {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.squeeze_on.set_capacity(capacity);
+        self.released.set_capacity(capacity);
+        self.bollinger_bands.set_retention(capacity);
+        self.keltner_channel.set_retention(capacity);
+    }
+}
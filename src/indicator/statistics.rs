@@ -0,0 +1,134 @@
+use std::any::Any;
+
+use binance::model::KlineSummary;
+
+use crate::data::{Candles, RingBuffer};
+use crate::strategy::interface::Observer;
+use crate::{Error, Result};
+
+use super::batch::Series;
+use super::kernels;
+use super::window::RollingWindow;
+use super::{unseen_klines, PriceSource};
+
+// Écart-type glissant (population, comme `ta.stdev`)
+#[derive(Debug)]
+pub struct StandardDeviation {
+    pub values: RingBuffer<f64>,
+    window: RollingWindow,
+    source: PriceSource,
+    last_open_time: Option<i64>,
+}
+
+impl StandardDeviation {
+    pub fn new(klines: &[KlineSummary], length: usize, source: PriceSource) -> Result<Self> {
+        if length < 2 {
+            return Err(Error::invalid_parameter("length", "must be at least 2"));
+        }
+
+        let mut indicator = Self {
+            values: RingBuffer::new(),
+            window: RollingWindow::new(length),
+            source,
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    pub fn compute(candles: &Candles, length: usize, source: PriceSource) -> Result<Series> {
+        Self::new(&[], length, source)?;
+        let (_, std_dev) = kernels::rolling_mean_std(&source.column(candles), length);
+        Ok(Series::single(std_dev))
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            self.window.push(self.source.extract(kline));
+            self.values.push(if self.window.is_full() { self.window.std_dev() } else { f64::NAN });
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+}
+
+impl Observer for StandardDeviation {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.values.set_capacity(capacity);
+    }
+}
+
+// Z-score : écart de la source à sa moyenne glissante, en nombre d'écarts-types
+#[derive(Debug)]
+pub struct ZScore {
+    pub values: RingBuffer<f64>,
+    window: RollingWindow,
+    source: PriceSource,
+    last_open_time: Option<i64>,
+}
+
+impl ZScore {
+    pub fn new(klines: &[KlineSummary], length: usize, source: PriceSource) -> Result<Self> {
+        if length < 2 {
+            return Err(Error::invalid_parameter("length", "must be at least 2"));
+        }
+
+        let mut indicator = Self {
+            values: RingBuffer::new(),
+            window: RollingWindow::new(length),
+            source,
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    pub fn compute(candles: &Candles, length: usize, source: PriceSource) -> Result<Series> {
+        Self::new(&[], length, source)?;
+
+        let values = source.column(candles);
+        let (mean, std_dev) = kernels::rolling_mean_std(&values, length);
+        let z_score = (0..values.len())
+            .map(|i| if std_dev[i] > 0.0 { (values[i] - mean[i]) / std_dev[i] } else { f64::NAN })
+            .collect();
+        Ok(Series::single(z_score))
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            let value = self.source.extract(kline);
+            self.window.push(value);
+
+            let std_dev = self.window.std_dev();
+            let value = if self.window.is_full() && std_dev > 0.0 {
+                (value - self.window.mean()) / std_dev
+            } else {
+                f64::NAN
+            };
+
+            self.values.push(value);
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+}
+
+impl Observer for ZScore {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.values.set_capacity(capacity);
+    }
+}
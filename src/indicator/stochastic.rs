@@ -0,0 +1,190 @@
+use std::any::Any;
+
+use binance::model::KlineSummary;
+
+use crate::data::{Candles, RingBuffer};
+use crate::strategy::interface::Observer;
+use crate::{Error, Result};
+
+use super::batch::Series;
+use super::kernels;
+use super::moving_average::{MovingAverage, Sma};
+use super::rsi::WilderRsi;
+use super::window::RollingWindow;
+use super::{unseen_klines, PriceSource};
+
+fn check_length(name: &'static str, length: usize) -> Result<usize> {
+    if length == 0 {
+        return Err(Error::invalid_parameter(name, "must be greater than 0"));
+    }
+    Ok(length)
+}
+
+// Position de `value` dans le range [lowest, highest], en pourcentage (NaN si le range est nul)
+fn stoch(value: f64, highest: f64, lowest: f64) -> f64 {
+    if highest - lowest == 0.0 {
+        return f64::NAN;
+    }
+    100.0 * (value - lowest) / (highest - lowest)
+}
+
+// Oscillateur stochastique : %K lissé par une SMA et %D moyenne de %K
+#[derive(Debug)]
+pub struct Stochastic {
+    pub k: RingBuffer<f64>,
+    pub d: RingBuffer<f64>,
+    highs: RollingWindow,
+    lows: RollingWindow,
+    smooth_k: Sma,
+    smooth_d: Sma,
+    last_open_time: Option<i64>,
+}
+
+impl Stochastic {
+    pub fn new(klines: &[KlineSummary], length: usize, smooth_k: usize, smooth_d: usize) -> Result<Self> {
+        let length = check_length("length", length)?;
+        let mut indicator = Self {
+            k: RingBuffer::new(),
+            d: RingBuffer::new(),
+            highs: RollingWindow::new(length),
+            lows: RollingWindow::new(length),
+            smooth_k: Sma::new(smooth_k)?,
+            smooth_d: Sma::new(smooth_d)?,
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    pub fn compute(candles: &Candles, length: usize, smooth_k: usize, smooth_d: usize) -> Result<Series> {
+        Self::new(&[], length, smooth_k, smooth_d)?;
+
+        let raw = kernels::stochastic(&candles.close, &candles.high, &candles.low, length);
+        let k = kernels::sma(&raw, smooth_k);
+        let d = kernels::sma(&k, smooth_d);
+        Ok(Series::new(vec![("k", k), ("d", d)]))
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            self.highs.push(PriceSource::High.extract(kline));
+            self.lows.push(PriceSource::Low.extract(kline));
+
+            let raw = if self.highs.is_full() {
+                stoch(PriceSource::Close.extract(kline), self.highs.max(), self.lows.min())
+            } else {
+                f64::NAN
+            };
+            let k = self.smooth_k.next(raw);
+            let d = self.smooth_d.next(k);
+
+            self.k.push(k);
+            self.d.push(d);
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+}
+
+impl Observer for Stochastic {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.k.set_capacity(capacity);
+        self.d.set_capacity(capacity);
+    }
+}
+
+// Stochastic RSI : oscillateur stochastique appliqué au RSI
+#[derive(Debug)]
+pub struct StochasticRsi {
+    pub k: RingBuffer<f64>,
+    pub d: RingBuffer<f64>,
+    rsi: WilderRsi,
+    rsi_values: RollingWindow,
+    smooth_k: Sma,
+    smooth_d: Sma,
+    source: PriceSource,
+    last_open_time: Option<i64>,
+}
+
+impl StochasticRsi {
+    pub fn new(
+        klines: &[KlineSummary],
+        rsi_length: usize,
+        stoch_length: usize,
+        smooth_k: usize,
+        smooth_d: usize,
+        source: PriceSource,
+    ) -> Result<Self> {
+        let stoch_length = check_length("stoch_length", stoch_length)?;
+        let mut indicator = Self {
+            k: RingBuffer::new(),
+            d: RingBuffer::new(),
+            rsi: WilderRsi::new(rsi_length)?,
+            rsi_values: RollingWindow::new(stoch_length),
+            smooth_k: Sma::new(smooth_k)?,
+            smooth_d: Sma::new(smooth_d)?,
+            source,
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    pub fn compute(
+        candles: &Candles,
+        rsi_length: usize,
+        stoch_length: usize,
+        smooth_k: usize,
+        smooth_d: usize,
+        source: PriceSource,
+    ) -> Result<Series> {
+        Self::new(&[], rsi_length, stoch_length, smooth_k, smooth_d, source)?;
+
+        let rsi = kernels::rsi(&source.column(candles), rsi_length);
+        let k = kernels::sma(&kernels::stochastic(&rsi, &rsi, &rsi, stoch_length), smooth_k);
+        let d = kernels::sma(&k, smooth_d);
+        Ok(Series::new(vec![("k", k), ("d", d)]))
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            let rsi = self.rsi.next(self.source.extract(kline));
+            let mut raw = f64::NAN;
+            if !rsi.is_nan() {
+                self.rsi_values.push(rsi);
+                if self.rsi_values.is_full() {
+                    raw = stoch(rsi, self.rsi_values.max(), self.rsi_values.min());
+                }
+            }
+
+            let k = self.smooth_k.next(raw);
+            let d = self.smooth_d.next(k);
+
+            self.k.push(k);
+            self.d.push(d);
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+}
+
+impl Observer for StochasticRsi {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.k.set_capacity(capacity);
+        self.d.set_capacity(capacity);
+    }
+}
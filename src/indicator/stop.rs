@@ -0,0 +1,92 @@
+use std::str::FromStr;
+
+use binance::model::KlineSummary;
+
+use crate::strategy::interface::Observer;
+use crate::Result;
+
+use super::{ATRStopLoss, ChandelierExit, ParabolicSar, SwingStop};
+
+// Sens d'une position
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Long,
+    Short,
+}
+
+// Indicateur qui fournit un niveau de stop pour une position longue ou courte
+pub trait StopIndicator: Observer {
+    // Niveau de stop à la dernière bougie, None si l'indicateur n'est pas encore initialisé
+    // ou s'il n'a pas de stop valide pour ce sens (Parabolic SAR dans la tendance opposée)
+    fn stop(&self, side: Side) -> Option<f64>;
+}
+
+// Dernière valeur d'une série de stops, NaN pendant l'initialisation
+pub(crate) fn last_stop(values: &[f64]) -> Option<f64> {
+    values.last().copied().filter(|value| !value.is_nan())
+}
+
+// Choix du stop par configuration
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StopConfig {
+    Atr { length: usize, multiplier: f64 },
+    Chandelier { length: usize, atr_length: usize, multiplier: f64 },
+    ParabolicSar { start: f64, increment: f64, maximum: f64 },
+    Swing { left: usize, right: usize },
+}
+
+impl StopConfig {
+    pub fn build(&self, klines: &[KlineSummary]) -> Result<Box<dyn Observer>> {
+        Ok(match *self {
+            StopConfig::Atr { length, multiplier } => Box::new(ATRStopLoss::new(klines, length, multiplier)?),
+            StopConfig::Chandelier { length, atr_length, multiplier } => {
+                Box::new(ChandelierExit::new(klines, length, atr_length, multiplier)?)
+            }
+            StopConfig::ParabolicSar { start, increment, maximum } => {
+                Box::new(ParabolicSar::new(klines, start, increment, maximum)?)
+            }
+            StopConfig::Swing { left, right } => Box::new(SwingStop::new(klines, left, right)?),
+        })
+    }
+}
+
+impl Default for StopConfig {
+    // Stop de la stratégie Choppiness/Donchian historique
+    fn default() -> Self {
+        StopConfig::Atr {
+            length: 14,
+            multiplier: 1.5,
+        }
+    }
+}
+
+impl FromStr for StopConfig {
+    type Err = String;
+
+    // Écriture des fichiers de configuration : atr(14, 1.5), chandelier(22, 22, 3),
+    // parabolic_sar(0.02, 0.02, 0.2), swing(3, 3)
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        let invalid = || format!("Invalid stop: {}", value);
+        let (name, arguments) = value.trim().strip_suffix(')').and_then(|value| value.split_once('(')).ok_or_else(invalid)?;
+        let numbers = arguments
+            .split(',')
+            .map(|argument| argument.trim().parse::<f64>())
+            .collect::<std::result::Result<Vec<f64>, _>>()
+            .map_err(|_| invalid())?;
+        let count = |value: f64| if value.fract() == 0.0 && value >= 0.0 { Ok(value as usize) } else { Err(invalid()) };
+
+        match (name.trim(), numbers.as_slice()) {
+            ("atr", [length, multiplier]) => Ok(StopConfig::Atr { length: count(*length)?, multiplier: *multiplier }),
+            ("chandelier", [length, atr_length, multiplier]) => Ok(StopConfig::Chandelier {
+                length: count(*length)?,
+                atr_length: count(*atr_length)?,
+                multiplier: *multiplier,
+            }),
+            ("parabolic_sar", [start, increment, maximum]) => {
+                Ok(StopConfig::ParabolicSar { start: *start, increment: *increment, maximum: *maximum })
+            }
+            ("swing", [left, right]) => Ok(StopConfig::Swing { left: count(*left)?, right: count(*right)? }),
+            _ => Err(invalid()),
+        }
+    }
+}
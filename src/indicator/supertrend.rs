@@ -0,0 +1,143 @@
+use std::any::Any;
+
+use binance::model::KlineSummary;
+
+use crate::data::{Candles, RingBuffer};
+use crate::strategy::interface::Observer;
+use crate::{Error, Result};
+
+use super::atr::AtrCalculator;
+use super::batch::Series;
+use super::kernels;
+use super::{unseen_klines, PriceSource};
+
+// SuperTrend : bandes hl2 ± `factor` ATR qui ne se desserrent jamais tant que la tendance tient.
+// `direction` vaut 1 en tendance haussière (ligne sous le prix) et -1 en tendance baissière.
+#[derive(Debug)]
+pub struct SuperTrend {
+    pub values: RingBuffer<f64>,
+    pub direction: RingBuffer<i8>,
+    atr: AtrCalculator,
+    factor: f64,
+    previous: Option<State>,
+    last_open_time: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct State {
+    upper_band: f64,
+    lower_band: f64,
+    close: f64,
+    direction: i8,
+}
+
+impl State {
+    fn next(previous: Option<State>, close: f64, hl2: f64, atr: f64, factor: f64) -> State {
+        let mut upper_band = hl2 + factor * atr;
+        let mut lower_band = hl2 - factor * atr;
+
+        let direction = match previous {
+            Some(previous) => {
+                if !(lower_band > previous.lower_band || previous.close < previous.lower_band) {
+                    lower_band = previous.lower_band;
+                }
+                if !(upper_band < previous.upper_band || previous.close > previous.upper_band) {
+                    upper_band = previous.upper_band;
+                }
+
+                if previous.direction < 0 {
+                    if close > upper_band { 1 } else { -1 }
+                } else if close < lower_band {
+                    -1
+                } else {
+                    1
+                }
+            }
+            // Première valeur : tendance baissière par défaut, comme TradingView
+            None => -1,
+        };
+
+        State {
+            upper_band,
+            lower_band,
+            close,
+            direction,
+        }
+    }
+
+    fn value(&self) -> f64 {
+        if self.direction > 0 { self.lower_band } else { self.upper_band }
+    }
+}
+
+impl SuperTrend {
+    pub fn new(klines: &[KlineSummary], atr_length: usize, factor: f64) -> Result<Self> {
+        if !factor.is_finite() || factor <= 0.0 {
+            return Err(Error::invalid_parameter("factor", "must be a positive number"));
+        }
+
+        let mut indicator = Self {
+            values: RingBuffer::new(),
+            direction: RingBuffer::new(),
+            atr: AtrCalculator::new(atr_length)?,
+            factor,
+            previous: None,
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    // Calcul par lot : sorties "value" et "direction" (0 pendant l'initialisation)
+    pub fn compute(candles: &Candles, atr_length: usize, factor: f64) -> Result<Series> {
+        Self::new(&[], atr_length, factor)?;
+
+        let atr = kernels::atr(&candles.high, &candles.low, &candles.close, atr_length);
+        let hl2 = PriceSource::HL2.column(candles);
+        let mut values = vec![f64::NAN; candles.len()];
+        let mut directions = vec![0.0; candles.len()];
+        let mut previous = None;
+
+        for i in (0..candles.len()).filter(|&i| !atr[i].is_nan()) {
+            let state = State::next(previous, candles.close[i], hl2[i], atr[i], factor);
+            values[i] = state.value();
+            directions[i] = f64::from(state.direction);
+            previous = Some(state);
+        }
+        Ok(Series::new(vec![("value", values), ("direction", directions)]))
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            let atr = self.atr.next(kline);
+            let close = PriceSource::Close.extract(kline);
+            self.last_open_time = Some(kline.open_time);
+
+            if atr.is_nan() {
+                self.values.push(f64::NAN);
+                self.direction.push(0);
+                continue;
+            }
+
+            let state = State::next(self.previous, close, PriceSource::HL2.extract(kline), atr, self.factor);
+            self.values.push(state.value());
+            self.direction.push(state.direction);
+            self.previous = Some(state);
+        }
+    }
+}
+
+impl Observer for SuperTrend {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.values.set_capacity(capacity);
+        self.direction.set_capacity(capacity);
+    }
+}
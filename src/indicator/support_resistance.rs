@@ -0,0 +1,159 @@
+use std::any::Any;
+
+use binance::model::KlineSummary;
+
+use crate::data::RingBuffer;
+use crate::strategy::interface::Observer;
+use crate::{Error, Result};
+
+use super::swing::{Swing, SwingDetector};
+use super::unseen_klines;
+
+// Zone de prix regroupant des swings proches
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Zone {
+    pub lower: f64,
+    pub upper: f64,
+    pub touches: usize,
+    pub last_touch: i64, // Heure d'ouverture du dernier swing de la zone
+}
+
+impl Zone {
+    fn from_swing(swing: &Swing) -> Self {
+        Self {
+            lower: swing.price,
+            upper: swing.price,
+            touches: 1,
+            last_touch: swing.open_time,
+        }
+    }
+
+    pub fn center(&self) -> f64 {
+        (self.lower + self.upper) / 2.0
+    }
+
+    pub fn contains(&self, price: f64) -> bool {
+        (self.lower..=self.upper).contains(&price)
+    }
+
+    fn absorb(&mut self, swing: &Swing) {
+        self.lower = self.lower.min(swing.price);
+        self.upper = self.upper.max(swing.price);
+        self.touches += 1;
+        self.last_touch = self.last_touch.max(swing.open_time);
+    }
+}
+
+// Regroupement des swings confirmés en zones de support/résistance : un swing rejoint la zone
+// dont le centre est à moins de `tolerance` pour cent, sinon il ouvre une nouvelle zone.
+// Avec une rétention, les zones sans swing dans les `capacity` dernières bougies sont oubliées.
+#[derive(Debug)]
+pub struct SupportResistance {
+    zones: Vec<Zone>, // Triées par centre croissant
+    detector: SwingDetector,
+    tolerance: f64,
+    min_touches: usize,
+    retention: Option<usize>,
+    open_times: RingBuffer<i64>, // Heures d'ouverture de la fenêtre de rétention
+    last_open_time: Option<i64>,
+}
+
+impl SupportResistance {
+    pub fn new(klines: &[KlineSummary], left: usize, right: usize, tolerance: f64, min_touches: usize) -> Result<Self> {
+        if !tolerance.is_finite() || tolerance < 0.0 {
+            return Err(Error::invalid_parameter("tolerance", "must be a non-negative percentage"));
+        }
+        if min_touches == 0 {
+            return Err(Error::invalid_parameter("min_touches", "must be greater than 0"));
+        }
+
+        let mut indicator = Self {
+            zones: Vec::new(),
+            detector: SwingDetector::new(left, right)?,
+            tolerance: tolerance / 100.0,
+            min_touches,
+            retention: None,
+            open_times: RingBuffer::new(),
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            for swing in self.detector.next(kline) {
+                self.insert(&swing);
+            }
+            if self.retention.is_some() {
+                self.open_times.push(kline.open_time);
+                self.drop_stale_zones();
+            }
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+
+    fn drop_stale_zones(&mut self) {
+        if let (Some(capacity), Some(&oldest)) = (self.retention, self.open_times.first()) {
+            if self.open_times.len() >= capacity {
+                self.zones.retain(|zone| zone.last_touch >= oldest);
+            }
+        }
+    }
+
+    fn insert(&mut self, swing: &Swing) {
+        let tolerance = self.tolerance;
+        let nearest = self
+            .zones
+            .iter_mut()
+            .map(|zone| ((zone.center() - swing.price).abs(), zone))
+            .filter(|(distance, zone)| *distance <= zone.center().abs() * tolerance)
+            .min_by(|(a, _), (b, _)| a.total_cmp(b));
+
+        match nearest {
+            Some((_, zone)) => {
+                zone.absorb(swing);
+                // Le centre a bougé : la liste reste presque triée, le tri est linéaire
+                self.zones.sort_by(|a, b| a.center().total_cmp(&b.center()));
+            }
+            None => {
+                let position = self.zones.partition_point(|zone| zone.center() < swing.price);
+                self.zones.insert(position, Zone::from_swing(swing));
+            }
+        }
+    }
+
+    // Zones touchées au moins `min_touches` fois, triées par prix croissant
+    pub fn zones(&self) -> impl DoubleEndedIterator<Item = &Zone> + '_ {
+        self.zones.iter().filter(|zone| zone.touches >= self.min_touches)
+    }
+
+    // Zone significative la plus proche sous le prix
+    pub fn nearest_support(&self, price: f64) -> Option<Zone> {
+        self.zones().rev().find(|zone| zone.upper < price).copied()
+    }
+
+    // Zone significative la plus proche au-dessus du prix
+    pub fn nearest_resistance(&self, price: f64) -> Option<Zone> {
+        self.zones().find(|zone| zone.lower > price).copied()
+    }
+}
+
+impl Observer for SupportResistance {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.retention = capacity;
+        self.open_times.set_capacity(capacity);
+        if capacity.is_none() {
+            self.open_times.clear();
+        }
+        self.drop_stale_zones();
+    }
+}
@@ -0,0 +1,348 @@
+use std::any::Any;
+
+use binance::model::KlineSummary;
+
+use crate::data::{Candles, RingBuffer};
+use crate::strategy::interface::Observer;
+use crate::{Error, Result};
+
+use super::batch::Series;
+use super::kernels;
+use super::stop::{Side, StopIndicator};
+use super::window::RollingWindow;
+use super::{unseen_klines, PriceSource};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwingKind {
+    High,
+    Low,
+}
+
+// Point de retournement confirmé
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Swing {
+    pub kind: SwingKind,
+    pub open_time: i64,
+    pub price: f64,
+    pub confirmed_at: i64, // Heure d'ouverture de la bougie qui confirme le pivot
+}
+
+// Détection des pivots : un plus haut (plus bas) strictement supérieur (inférieur) aux `left` bougies
+// précédentes et aux `right` bougies suivantes. Un pivot n'est connu que `right` bougies plus tard.
+#[derive(Debug)]
+pub struct SwingDetector {
+    left: usize,
+    right: usize,
+    highs: RollingWindow,
+    lows: RollingWindow,
+    open_times: Vec<i64>,
+}
+
+impl SwingDetector {
+    pub fn new(left: usize, right: usize) -> Result<Self> {
+        if left == 0 {
+            return Err(Error::invalid_parameter("left", "must be greater than 0"));
+        }
+        if right == 0 {
+            return Err(Error::invalid_parameter("right", "must be greater than 0"));
+        }
+
+        let size = left + right + 1;
+        Ok(Self {
+            left,
+            right,
+            highs: RollingWindow::new(size),
+            lows: RollingWindow::new(size),
+            open_times: Vec::with_capacity(size),
+        })
+    }
+
+    pub fn left(&self) -> usize {
+        self.left
+    }
+
+    pub fn right(&self) -> usize {
+        self.right
+    }
+
+    // Pivots confirmés par cette bougie (au plus un haut et un bas)
+    pub fn next(&mut self, kline: &KlineSummary) -> Vec<Swing> {
+        self.highs.push(PriceSource::High.extract(kline));
+        self.lows.push(PriceSource::Low.extract(kline));
+        self.open_times.push(kline.open_time);
+        if self.open_times.len() > self.highs.capacity() {
+            self.open_times.remove(0);
+        }
+        if !self.highs.is_full() {
+            return Vec::new();
+        }
+
+        let mut swings = Vec::new();
+        if let Some(price) = Self::pivot(&self.highs, self.right, |candidate, other| candidate > other) {
+            swings.push(Swing {
+                kind: SwingKind::High,
+                open_time: self.open_times[self.left],
+                price,
+                confirmed_at: kline.open_time,
+            });
+        }
+        if let Some(price) = Self::pivot(&self.lows, self.right, |candidate, other| candidate < other) {
+            swings.push(Swing {
+                kind: SwingKind::Low,
+                open_time: self.open_times[self.left],
+                price,
+                confirmed_at: kline.open_time,
+            });
+        }
+        swings
+    }
+
+    pub fn reset(&mut self) {
+        self.highs.clear();
+        self.lows.clear();
+        self.open_times.clear();
+    }
+
+    fn pivot(window: &RollingWindow, right: usize, beats: impl Fn(f64, f64) -> bool) -> Option<f64> {
+        let candidate = window.ago(right)?;
+        window
+            .iter()
+            .rev()
+            .enumerate()
+            .all(|(ago, value)| ago == right || beats(candidate, *value))
+            .then_some(candidate)
+    }
+}
+
+// Fractales : pivots de `SwingDetector`, publiés avec `right` bougies de retard
+#[derive(Debug)]
+pub struct Fractals {
+    pub swings: RingBuffer<Swing>,
+    detector: SwingDetector,
+    last_open_time: Option<i64>,
+}
+
+impl Fractals {
+    pub fn new(klines: &[KlineSummary], left: usize, right: usize) -> Result<Self> {
+        let mut indicator = Self {
+            swings: RingBuffer::new(),
+            detector: SwingDetector::new(left, right)?,
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    // Calcul par lot : prix du pivot haut (bas) confirmé par chaque bougie, NaN sinon
+    pub fn compute(candles: &Candles, left: usize, right: usize) -> Result<Series> {
+        SwingDetector::new(left, right)?;
+        Ok(Series::new(vec![
+            ("high", kernels::pivot_highs(&candles.high, left, right)),
+            ("low", kernels::pivot_lows(&candles.low, left, right)),
+        ]))
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            self.swings.extend(self.detector.next(kline));
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+
+    pub fn last(&self, kind: SwingKind) -> Option<&Swing> {
+        self.swings.iter().rev().find(|swing| swing.kind == kind)
+    }
+
+    // Prix du pivot confirmé par la dernière bougie traitée
+    pub fn confirmed(&self, kind: SwingKind) -> Option<f64> {
+        self.last(kind).filter(|swing| Some(swing.confirmed_at) == self.last_open_time).map(|swing| swing.price)
+    }
+}
+
+impl Observer for Fractals {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.swings.set_capacity(capacity);
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Extreme {
+    open_time: i64,
+    price: f64,
+}
+
+// ZigZag : un sommet (creux) est confirmé quand le prix s'en éloigne de `deviation` pour cent.
+// Les swings alternent toujours entre haut et bas.
+#[derive(Debug)]
+pub struct ZigZag {
+    pub swings: RingBuffer<Swing>,
+    deviation: f64,
+    direction: Option<SwingKind>, // Extrême en cours de recherche
+    high: Option<Extreme>,
+    low: Option<Extreme>,
+    last_open_time: Option<i64>,
+}
+
+impl ZigZag {
+    pub fn new(klines: &[KlineSummary], deviation: f64) -> Result<Self> {
+        if !deviation.is_finite() || deviation <= 0.0 {
+            return Err(Error::invalid_parameter("deviation", "must be a positive percentage"));
+        }
+
+        let mut indicator = Self {
+            swings: RingBuffer::new(),
+            deviation: deviation / 100.0,
+            direction: None,
+            high: None,
+            low: None,
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            self.next(kline);
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+
+    fn next(&mut self, kline: &KlineSummary) {
+        let high = Extreme { open_time: kline.open_time, price: PriceSource::High.extract(kline) };
+        let low = Extreme { open_time: kline.open_time, price: PriceSource::Low.extract(kline) };
+
+        if self.direction != Some(SwingKind::Low) && self.high.is_none_or(|extreme| high.price > extreme.price) {
+            self.high = Some(high);
+        }
+        if self.direction != Some(SwingKind::High) && self.low.is_none_or(|extreme| low.price < extreme.price) {
+            self.low = Some(low);
+        }
+
+        match (self.direction, self.high, self.low) {
+            (Some(SwingKind::High) | None, Some(extreme), _) if low.price <= extreme.price * (1.0 - self.deviation) => {
+                self.confirm(SwingKind::High, extreme, kline.open_time);
+                self.low = Some(low);
+            }
+            (Some(SwingKind::Low) | None, _, Some(extreme)) if high.price >= extreme.price * (1.0 + self.deviation) => {
+                self.confirm(SwingKind::Low, extreme, kline.open_time);
+                self.high = Some(high);
+            }
+            _ => {}
+        }
+    }
+
+    fn confirm(&mut self, kind: SwingKind, extreme: Extreme, confirmed_at: i64) {
+        self.swings.push(Swing { kind, open_time: extreme.open_time, price: extreme.price, confirmed_at });
+        // Après un sommet on cherche un creux, et inversement
+        self.direction = Some(match kind {
+            SwingKind::High => SwingKind::Low,
+            SwingKind::Low => SwingKind::High,
+        });
+    }
+
+    // Extrême en cours, pas encore confirmé (il peut encore être repeint)
+    pub fn pending(&self) -> Option<(SwingKind, f64)> {
+        match self.direction? {
+            SwingKind::High => self.high.map(|extreme| (SwingKind::High, extreme.price)),
+            SwingKind::Low => self.low.map(|extreme| (SwingKind::Low, extreme.price)),
+        }
+    }
+}
+
+impl Observer for ZigZag {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.swings.set_capacity(capacity);
+    }
+}
+
+// Stop sous le dernier plus bas de swing confirmé (long) ou au-dessus du dernier plus haut (short)
+#[derive(Debug)]
+pub struct SwingStop {
+    pub long_stop: RingBuffer<f64>,
+    pub short_stop: RingBuffer<f64>,
+    pub swings: RingBuffer<Swing>,
+    detector: SwingDetector,
+    last_open_time: Option<i64>,
+}
+
+impl SwingStop {
+    pub fn new(klines: &[KlineSummary], left: usize, right: usize) -> Result<Self> {
+        let mut indicator = Self {
+            long_stop: RingBuffer::new(),
+            short_stop: RingBuffer::new(),
+            swings: RingBuffer::new(),
+            detector: SwingDetector::new(left, right)?,
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    // Calcul par lot : dernier pivot bas (haut) confirmé, reporté jusqu'au suivant
+    pub fn compute(candles: &Candles, left: usize, right: usize) -> Result<Series> {
+        SwingDetector::new(left, right)?;
+        Ok(Series::new(vec![
+            ("long", kernels::forward_fill(&kernels::pivot_lows(&candles.low, left, right))),
+            ("short", kernels::forward_fill(&kernels::pivot_highs(&candles.high, left, right))),
+        ]))
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            self.swings.extend(self.detector.next(kline));
+            self.long_stop.push(self.last_swing(SwingKind::Low).unwrap_or(f64::NAN));
+            self.short_stop.push(self.last_swing(SwingKind::High).unwrap_or(f64::NAN));
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+
+    fn last_swing(&self, kind: SwingKind) -> Option<f64> {
+        self.swings.iter().rev().find(|swing| swing.kind == kind).map(|swing| swing.price)
+    }
+}
+
+impl StopIndicator for SwingStop {
+    fn stop(&self, side: Side) -> Option<f64> {
+        match side {
+            Side::Long => self.last_swing(SwingKind::Low),
+            Side::Short => self.last_swing(SwingKind::High),
+        }
+    }
+}
+
+impl Observer for SwingStop {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.long_stop.set_capacity(capacity);
+        self.short_stop.set_capacity(capacity);
+        self.swings.set_capacity(capacity);
+    }
+
+    fn as_stop_indicator(&self) -> Option<&dyn StopIndicator> {
+        Some(self)
+    }
+}
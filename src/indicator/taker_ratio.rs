@@ -0,0 +1,92 @@
+use std::any::Any;
+
+use binance::model::KlineSummary;
+
+use crate::data::{Candles, RingBuffer};
+use crate::strategy::interface::Observer;
+use crate::{Error, Result};
+
+use super::batch::Series;
+use super::kernels;
+use super::window::RollingWindow;
+use super::{unseen_klines, PriceSource};
+
+// Ratio entre volume acheteur (taker buy) et volume vendeur sur `length` bougies
+#[derive(Debug)]
+pub struct TakerBuySellRatio {
+    pub ratio: RingBuffer<f64>,     // Volume acheteur / volume vendeur
+    pub buy_share: RingBuffer<f64>, // Part du volume acheteur dans le volume total, entre 0 et 1
+    buys: RollingWindow,
+    volumes: RollingWindow,
+    last_open_time: Option<i64>,
+}
+
+impl TakerBuySellRatio {
+    pub fn new(klines: &[KlineSummary], length: usize) -> Result<Self> {
+        if length == 0 {
+            return Err(Error::invalid_parameter("length", "must be greater than 0"));
+        }
+
+        let mut indicator = Self {
+            ratio: RingBuffer::new(),
+            buy_share: RingBuffer::new(),
+            buys: RollingWindow::new(length),
+            volumes: RollingWindow::new(length),
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    pub fn compute(candles: &Candles, length: usize) -> Result<Series> {
+        Self::new(&[], length)?;
+
+        let buys = kernels::rolling_sum(&candles.taker_buy_volume, length);
+        let volumes = kernels::rolling_sum(&candles.volume, length);
+        let ratio = kernels::zip_with(&buys, &volumes, |buy, volume| {
+            let sell = volume - buy;
+            if sell > 0.0 { buy / sell } else { f64::NAN }
+        });
+        let buy_share = kernels::zip_with(&buys, &volumes, |buy, volume| if volume > 0.0 { buy / volume } else { f64::NAN });
+        Ok(Series::new(vec![("ratio", ratio), ("buy_share", buy_share)]))
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            let buy = kline.taker_buy_base_asset_volume.parse::<f64>().unwrap_or(f64::NAN);
+            self.buys.push(buy);
+            self.volumes.push(PriceSource::Volume.extract(kline));
+
+            let (ratio, buy_share) = if self.volumes.is_full() {
+                let buy = self.buys.sum();
+                let volume = self.volumes.sum();
+                let sell = volume - buy;
+                (
+                    if sell > 0.0 { buy / sell } else { f64::NAN },
+                    if volume > 0.0 { buy / volume } else { f64::NAN },
+                )
+            } else {
+                (f64::NAN, f64::NAN)
+            };
+
+            self.ratio.push(ratio);
+            self.buy_share.push(buy_share);
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+}
+
+impl Observer for TakerBuySellRatio {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.ratio.set_capacity(capacity);
+        self.buy_share.set_capacity(capacity);
+    }
+}
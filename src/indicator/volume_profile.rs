@@ -0,0 +1,168 @@
+use std::any::Any;
+use std::collections::VecDeque;
+
+use binance::model::KlineSummary;
+
+use crate::data::{Candles, RingBuffer};
+use crate::strategy::interface::Observer;
+use crate::{Error, Result};
+
+use super::batch::Series;
+use super::{unseen_klines, PriceSource};
+
+// Profil de volume glissant sur `length` bougies : le volume de chaque bougie est réparti
+// uniformément entre son plus bas et son plus haut sur `bins` niveaux de prix.
+#[derive(Debug)]
+pub struct VolumeProfile {
+    pub poc: RingBuffer<f64>,              // Point of Control : centre du niveau le plus traité
+    pub value_area_high: RingBuffer<f64>,
+    pub value_area_low: RingBuffer<f64>,
+    window: VecDeque<(f64, f64, f64)>, // (low, high, volume)
+    length: usize,
+    bins: usize,
+    value_area: f64, // Part du volume dans la value area (0.7 par convention)
+    last_open_time: Option<i64>,
+}
+
+impl VolumeProfile {
+    pub fn new(klines: &[KlineSummary], length: usize, bins: usize, value_area: f64) -> Result<Self> {
+        if length == 0 {
+            return Err(Error::invalid_parameter("length", "must be greater than 0"));
+        }
+        if bins == 0 {
+            return Err(Error::invalid_parameter("bins", "must be greater than 0"));
+        }
+        if !(value_area > 0.0 && value_area <= 1.0) {
+            return Err(Error::invalid_parameter("value_area", "must be in ]0, 1]"));
+        }
+
+        let mut indicator = Self {
+            poc: RingBuffer::new(),
+            value_area_high: RingBuffer::new(),
+            value_area_low: RingBuffer::new(),
+            window: VecDeque::with_capacity(length + 1),
+            length,
+            bins,
+            value_area,
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    // Calcul par lot : profil des `length` dernières bougies valides à chaque bougie
+    pub fn compute(candles: &Candles, length: usize, bins: usize, value_area: f64) -> Result<Series> {
+        Self::new(&[], length, bins, value_area)?;
+
+        let mut bars = Vec::with_capacity(candles.len());
+        let mut outputs = [(); 3].map(|_| Vec::with_capacity(candles.len()));
+        for i in 0..candles.len() {
+            let bar = (candles.low[i], candles.high[i], candles.volume[i]);
+            if !bar.0.is_nan() && !bar.1.is_nan() && !bar.2.is_nan() {
+                bars.push(bar);
+            }
+
+            let (poc, value_area_low, value_area_high) = match bars.len().checked_sub(length) {
+                Some(start) => profile(&bars[start..], bins, value_area),
+                None => (f64::NAN, f64::NAN, f64::NAN),
+            };
+            outputs[0].push(poc);
+            outputs[1].push(value_area_high);
+            outputs[2].push(value_area_low);
+        }
+        let [poc, value_area_high, value_area_low] = outputs;
+        Ok(Series::new(vec![("poc", poc), ("value_area_high", value_area_high), ("value_area_low", value_area_low)]))
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            let low = PriceSource::Low.extract(kline);
+            let high = PriceSource::High.extract(kline);
+            let volume = PriceSource::Volume.extract(kline);
+            if !low.is_nan() && !high.is_nan() && !volume.is_nan() {
+                self.window.push_back((low, high, volume));
+                if self.window.len() > self.length {
+                    self.window.pop_front();
+                }
+            }
+
+            let (poc, value_area_low, value_area_high) = if self.window.len() == self.length {
+                profile(self.window.make_contiguous(), self.bins, self.value_area)
+            } else {
+                (f64::NAN, f64::NAN, f64::NAN)
+            };
+
+            self.poc.push(poc);
+            self.value_area_low.push(value_area_low);
+            self.value_area_high.push(value_area_high);
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+}
+
+// (POC, bas et haut de la value area) des bougies (low, high, volume)
+fn profile(window: &[(f64, f64, f64)], bins: usize, value_area: f64) -> (f64, f64, f64) {
+    let lowest = window.iter().map(|(low, _, _)| *low).fold(f64::MAX, f64::min);
+    let highest = window.iter().map(|(_, high, _)| *high).fold(f64::MIN, f64::max);
+    if highest <= lowest {
+        return (lowest, lowest, highest);
+    }
+
+    let bin_size = (highest - lowest) / bins as f64;
+    let bin_of = |price: f64| (((price - lowest) / bin_size) as usize).min(bins - 1);
+    let mut volumes = vec![0.0; bins];
+
+    for (low, high, volume) in window {
+        let first = bin_of(*low);
+        let last = bin_of(*high);
+        let share = volume / (last - first + 1) as f64;
+        for bin in volumes.iter_mut().take(last + 1).skip(first) {
+            *bin += share;
+        }
+    }
+
+    let mut poc = 0;
+    for (bin, volume) in volumes.iter().enumerate() {
+        if *volume > volumes[poc] {
+            poc = bin;
+        }
+    }
+
+    // Étendre la value area autour du POC vers le niveau voisin le plus traité
+    let total: f64 = volumes.iter().sum();
+    let (mut lower, mut upper) = (poc, poc);
+    let mut covered = volumes[poc];
+    while covered < total * value_area && (lower > 0 || upper < bins - 1) {
+        let below = if lower > 0 { volumes[lower - 1] } else { -1.0 };
+        let above = if upper < bins - 1 { volumes[upper + 1] } else { -1.0 };
+        if above >= below {
+            upper += 1;
+            covered += above;
+        } else {
+            lower -= 1;
+            covered += below;
+        }
+    }
+
+    (
+        lowest + (poc as f64 + 0.5) * bin_size,
+        lowest + lower as f64 * bin_size,
+        lowest + (upper + 1) as f64 * bin_size,
+    )
+}
+
+impl Observer for VolumeProfile {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.poc.set_capacity(capacity);
+        self.value_area_high.set_capacity(capacity);
+        self.value_area_low.set_capacity(capacity);
+    }
+}
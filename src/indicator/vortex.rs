@@ -0,0 +1,111 @@
+use std::any::Any;
+
+use binance::model::KlineSummary;
+
+use crate::data::{Candles, RingBuffer};
+use crate::strategy::interface::Observer;
+use crate::{Error, Result};
+
+use super::atr::true_range;
+use super::batch::Series;
+use super::kernels;
+use super::window::RollingWindow;
+use super::{unseen_klines, PriceSource};
+
+// Vortex Indicator : mouvements haussiers (VI+) et baissiers (VI-) rapportés au True Range
+#[derive(Debug)]
+pub struct Vortex {
+    pub plus: RingBuffer<f64>,
+    pub minus: RingBuffer<f64>,
+    plus_movements: RollingWindow,
+    minus_movements: RollingWindow,
+    true_ranges: RollingWindow,
+    previous: Option<(f64, f64, f64)>, // (high, low, close) de la bougie précédente
+    last_open_time: Option<i64>,
+}
+
+impl Vortex {
+    pub fn new(klines: &[KlineSummary], length: usize) -> Result<Self> {
+        if length == 0 {
+            return Err(Error::invalid_parameter("length", "must be greater than 0"));
+        }
+
+        let mut indicator = Self {
+            plus: RingBuffer::new(),
+            minus: RingBuffer::new(),
+            plus_movements: RollingWindow::new(length),
+            minus_movements: RollingWindow::new(length),
+            true_ranges: RollingWindow::new(length),
+            previous: None,
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    pub fn compute(candles: &Candles, length: usize) -> Result<Series> {
+        Self::new(&[], length)?;
+
+        let (high, low) = (&candles.high, &candles.low);
+        let movement = |from: &[f64], to: &[f64]| {
+            let mut movements = vec![f64::NAN; candles.len()];
+            for i in 1..candles.len() {
+                movements[i] = (to[i] - from[i - 1]).abs();
+            }
+            kernels::rolling_sum(&movements, length)
+        };
+        let plus_movements = movement(low, high);
+        let minus_movements = movement(high, low);
+        let ranges = kernels::rolling_sum(&kernels::true_range(high, low, &candles.close), length);
+
+        let ratio = |movements: &[f64]| {
+            kernels::zip_with(movements, &ranges, |movement, range| if range == 0.0 { f64::NAN } else { movement / range })
+        };
+        Ok(Series::new(vec![("plus", ratio(&plus_movements)), ("minus", ratio(&minus_movements))]))
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            let high = PriceSource::High.extract(kline);
+            let low = PriceSource::Low.extract(kline);
+            let close = PriceSource::Close.extract(kline);
+
+            let previous = self.previous.replace((high, low, close));
+            self.true_ranges.push(true_range(kline, previous.map(|(_, _, close)| close)));
+
+            let (plus, minus) = match previous {
+                Some((previous_high, previous_low, _)) => {
+                    self.plus_movements.push((high - previous_low).abs());
+                    self.minus_movements.push((low - previous_high).abs());
+
+                    let range = self.true_ranges.sum();
+                    if self.plus_movements.is_full() && range != 0.0 {
+                        (self.plus_movements.sum() / range, self.minus_movements.sum() / range)
+                    } else {
+                        (f64::NAN, f64::NAN)
+                    }
+                }
+                None => (f64::NAN, f64::NAN),
+            };
+
+            self.plus.push(plus);
+            self.minus.push(minus);
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+}
+
+impl Observer for Vortex {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.plus.set_capacity(capacity);
+        self.minus.set_capacity(capacity);
+    }
+}
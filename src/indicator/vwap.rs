@@ -0,0 +1,150 @@
+use std::any::Any;
+
+use binance::model::KlineSummary;
+
+use crate::data::{Candles, Interval, RingBuffer};
+use crate::strategy::interface::Observer;
+use crate::{Error, Result};
+
+use super::batch::Series;
+use super::{unseen_klines, PriceSource};
+
+// Point de départ du cumul de la VWAP
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VwapAnchor {
+    // Remise à zéro à chaque nouvelle période (Days(1) pour une VWAP de session quotidienne)
+    Session(Interval),
+    // Cumul à partir de la bougie ouverte à cette heure (NaN avant)
+    Anchored(i64),
+}
+
+// VWAP avec bandes à ± `multiplier` écarts-types pondérés par le volume
+#[derive(Debug)]
+pub struct Vwap {
+    pub values: RingBuffer<f64>,
+    pub upper_band: RingBuffer<f64>,
+    pub lower_band: RingBuffer<f64>,
+    pub deviation: RingBuffer<f64>, // Écart-type pondéré, pour construire d'autres multiples
+    anchor: VwapAnchor,
+    multiplier: f64,
+    source: PriceSource,
+    session_start: Option<i64>,
+    sum_volume: f64,
+    sum_price_volume: f64,
+    sum_squared_price_volume: f64,
+    last_open_time: Option<i64>,
+}
+
+impl Vwap {
+    pub fn new(klines: &[KlineSummary], anchor: VwapAnchor, multiplier: f64, source: PriceSource) -> Result<Self> {
+        if !multiplier.is_finite() || multiplier < 0.0 {
+            return Err(Error::invalid_parameter("multiplier", "must be a positive number"));
+        }
+
+        let mut indicator = Self {
+            values: RingBuffer::new(),
+            upper_band: RingBuffer::new(),
+            lower_band: RingBuffer::new(),
+            deviation: RingBuffer::new(),
+            anchor,
+            multiplier,
+            source,
+            session_start: None,
+            sum_volume: 0.0,
+            sum_price_volume: 0.0,
+            sum_squared_price_volume: 0.0,
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    // Calcul par lot : sommes cumulées remises à zéro à chaque début de session
+    pub fn compute(candles: &Candles, anchor: VwapAnchor, multiplier: f64, source: PriceSource) -> Result<Series> {
+        let mut indicator = Self::new(&[], anchor, multiplier, source)?;
+        let prices = source.column(candles);
+
+        for (i, open_time) in candles.open_time.iter().enumerate() {
+            let session_start = match anchor {
+                VwapAnchor::Session(interval) => interval.bucket_start(*open_time),
+                VwapAnchor::Anchored(anchor) if *open_time >= anchor => anchor,
+                VwapAnchor::Anchored(_) => {
+                    indicator.push(f64::NAN, f64::NAN);
+                    continue;
+                }
+            };
+            indicator.accumulate(session_start, prices[i], candles.volume[i]);
+        }
+        Ok(Series::new(vec![
+            ("value", indicator.values.to_vec()),
+            ("upper", indicator.upper_band.to_vec()),
+            ("lower", indicator.lower_band.to_vec()),
+            ("deviation", indicator.deviation.to_vec()),
+        ]))
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            self.last_open_time = Some(kline.open_time);
+
+            let session_start = match self.anchor {
+                VwapAnchor::Session(interval) => interval.bucket_start(kline.open_time),
+                VwapAnchor::Anchored(anchor) if kline.open_time >= anchor => anchor,
+                VwapAnchor::Anchored(_) => {
+                    self.push(f64::NAN, f64::NAN);
+                    continue;
+                }
+            };
+
+            self.accumulate(session_start, self.source.extract(kline), PriceSource::Volume.extract(kline));
+        }
+    }
+
+    fn accumulate(&mut self, session_start: i64, price: f64, volume: f64) {
+        if self.session_start != Some(session_start) {
+            self.session_start = Some(session_start);
+            self.sum_volume = 0.0;
+            self.sum_price_volume = 0.0;
+            self.sum_squared_price_volume = 0.0;
+        }
+
+        if !price.is_nan() && !volume.is_nan() {
+            self.sum_volume += volume;
+            self.sum_price_volume += price * volume;
+            self.sum_squared_price_volume += price * price * volume;
+        }
+
+        if self.sum_volume == 0.0 {
+            self.push(f64::NAN, f64::NAN);
+            return;
+        }
+
+        let vwap = self.sum_price_volume / self.sum_volume;
+        let variance = (self.sum_squared_price_volume / self.sum_volume - vwap * vwap).max(0.0);
+        self.push(vwap, variance.sqrt());
+    }
+
+    fn push(&mut self, vwap: f64, deviation: f64) {
+        self.values.push(vwap);
+        self.deviation.push(deviation);
+        self.upper_band.push(vwap + self.multiplier * deviation);
+        self.lower_band.push(vwap - self.multiplier * deviation);
+    }
+}
+
+impl Observer for Vwap {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.values.set_capacity(capacity);
+        self.upper_band.set_capacity(capacity);
+        self.lower_band.set_capacity(capacity);
+        self.deviation.set_capacity(capacity);
+    }
+}
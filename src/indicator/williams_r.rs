@@ -0,0 +1,88 @@
+use std::any::Any;
+
+use binance::model::KlineSummary;
+
+use crate::data::{Candles, RingBuffer};
+use crate::strategy::interface::Observer;
+use crate::{Error, Result};
+
+use super::batch::Series;
+use super::kernels;
+use super::window::RollingWindow;
+use super::{unseen_klines, PriceSource};
+
+// Williams %R : position de la clôture sous le plus haut de la période, entre -100 et 0
+#[derive(Debug)]
+pub struct WilliamsR {
+    pub values: RingBuffer<f64>,
+    highs: RollingWindow,
+    lows: RollingWindow,
+    last_open_time: Option<i64>,
+}
+
+impl WilliamsR {
+    pub fn new(klines: &[KlineSummary], length: usize) -> Result<Self> {
+        if length == 0 {
+            return Err(Error::invalid_parameter("length", "must be greater than 0"));
+        }
+
+        let mut indicator = Self {
+            values: RingBuffer::new(),
+            highs: RollingWindow::new(length),
+            lows: RollingWindow::new(length),
+            last_open_time: None,
+        };
+        indicator.add(klines);
+        Ok(indicator)
+    }
+
+    pub fn compute(candles: &Candles, length: usize) -> Result<Series> {
+        Self::new(&[], length)?;
+
+        let highest = kernels::rolling_max(&candles.high, length);
+        let lowest = kernels::rolling_min(&candles.low, length);
+        let values = (0..candles.len())
+            .map(|i| {
+                let range = highest[i] - lowest[i];
+                if range == 0.0 || range.is_nan() {
+                    f64::NAN
+                } else {
+                    100.0 * (candles.close[i] - highest[i]) / range
+                }
+            })
+            .collect();
+        Ok(Series::single(values))
+    }
+
+    pub fn add(&mut self, klines: &[KlineSummary]) {
+        for kline in unseen_klines(klines, self.last_open_time) {
+            self.highs.push(PriceSource::High.extract(kline));
+            self.lows.push(PriceSource::Low.extract(kline));
+
+            let highest = self.highs.max();
+            let lowest = self.lows.min();
+            let value = if !self.highs.is_full() || highest - lowest == 0.0 {
+                f64::NAN
+            } else {
+                100.0 * (PriceSource::Close.extract(kline) - highest) / (highest - lowest)
+            };
+
+            self.values.push(value);
+            self.last_open_time = Some(kline.open_time);
+        }
+    }
+}
+
+impl Observer for WilliamsR {
+    fn on_new_kline(&mut self, _kline: &KlineSummary, all_klines: &[KlineSummary]) {
+        self.add(all_klines);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_retention(&mut self, capacity: Option<usize>) {
+        self.values.set_capacity(capacity);
+    }
+}
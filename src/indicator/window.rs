@@ -0,0 +1,112 @@
+use std::collections::VecDeque;
+
+// Fenêtre glissante des `capacity` dernières valeurs
+#[derive(Debug, Clone)]
+pub struct RollingWindow {
+    capacity: usize,
+    values: VecDeque<f64>,
+}
+
+impl RollingWindow {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            values: VecDeque::with_capacity(capacity + 1),
+        }
+    }
+
+    // Ajouter une valeur et retourner celle qui sort de la fenêtre
+    pub fn push(&mut self, value: f64) -> Option<f64> {
+        self.values.push_back(value);
+        if self.values.len() > self.capacity {
+            return self.values.pop_front();
+        }
+        None
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.values.len() == self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    // Valeur la plus ancienne de la fenêtre
+    pub fn first(&self) -> Option<f64> {
+        self.values.front().copied()
+    }
+
+    pub fn last(&self) -> Option<f64> {
+        self.values.back().copied()
+    }
+
+    // Valeur d'il y a `n` éléments (0 = la plus récente)
+    pub fn ago(&self, n: usize) -> Option<f64> {
+        self.values.len().checked_sub(n + 1).and_then(|index| self.values.get(index).copied())
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &f64> + ExactSizeIterator {
+        self.values.iter()
+    }
+
+    pub fn max(&self) -> f64 {
+        self.values.iter().copied().fold(f64::NAN, f64::max)
+    }
+
+    pub fn min(&self) -> f64 {
+        self.values.iter().copied().fold(f64::NAN, f64::min)
+    }
+
+    pub fn sum(&self) -> f64 {
+        self.values.iter().sum()
+    }
+
+    pub fn mean(&self) -> f64 {
+        if self.values.is_empty() {
+            return f64::NAN;
+        }
+        self.sum() / self.values.len() as f64
+    }
+
+    // Écart-type de population (comme `ta.stdev` sur TradingView)
+    pub fn std_dev(&self) -> f64 {
+        let mean = self.mean();
+        let variance = self.values.iter().map(|value| (value - mean).powi(2)).sum::<f64>() / self.values.len() as f64;
+        variance.sqrt()
+    }
+
+    // Position (0 = la plus récente) de la valeur maximale, la plus récente en cas d'égalité
+    pub fn position_of_max(&self) -> Option<usize> {
+        let mut best: Option<(usize, f64)> = None;
+        for (n, value) in self.values.iter().rev().enumerate() {
+            if best.is_none_or(|(_, max)| *value > max) {
+                best = Some((n, *value));
+            }
+        }
+        best.map(|(n, _)| n)
+    }
+
+    // Position (0 = la plus récente) de la valeur minimale, la plus récente en cas d'égalité
+    pub fn position_of_min(&self) -> Option<usize> {
+        let mut best: Option<(usize, f64)> = None;
+        for (n, value) in self.values.iter().rev().enumerate() {
+            if best.is_none_or(|(_, min)| *value < min) {
+                best = Some((n, *value));
+            }
+        }
+        best.map(|(n, _)| n)
+    }
+
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+}
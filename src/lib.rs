@@ -1,2 +1,6 @@
+pub mod data;
+pub mod error;
 pub mod indicator;
 pub mod strategy;
+
+pub use error::{Error, Result};
@@ -1,21 +1,39 @@
+use crate::data::Validator;
+use crate::{Error, Result};
+
 use super::TradingStrategy;
 
 pub struct Backtester {
     strategy: Box<dyn TradingStrategy>,
+    validator: Option<Validator>, // Contrôle qualité appliqué aux données avant le backtest
 }
 
 impl Backtester {
     pub fn new(strategy: Box<dyn TradingStrategy>) -> Self {
-        Self { strategy }
+        Self { strategy, validator: None }
     }
 
-    pub fn run(&mut self, klines: &[binance::model::KlineSummary]) {
-        println!("Running backtester...");
-        let mut kline_manager = self.strategy.prepare(klines);
+    pub fn with_validator(mut self, validator: Validator) -> Self {
+        self.validator = Some(validator);
+        self
+    }
 
-        for i in 101..klines.len() {
-            let current_kline = &klines[i-1];
-            self.strategy.execute(current_kline.clone(), &mut kline_manager);
+    // Initialiser la stratégie sur ses `warmup` premières bougies, puis exécuter toutes les suivantes
+    pub fn run(&mut self, klines: &[binance::model::KlineSummary]) -> Result<()> {
+        let klines = match &self.validator {
+            Some(validator) => validator.repair(klines)?,
+            None => klines.to_vec(),
+        };
+        let warmup = self.strategy.warmup();
+        if klines.len() < warmup {
+            return Err(Error::InsufficientData { required: warmup, available: klines.len() });
         }
+        let mut kline_manager = self.strategy.prepare(&klines)?;
+
+        for kline in &klines[warmup..] {
+            self.strategy.execute(kline.clone(), &mut kline_manager)?;
+        }
+
+        Ok(())
     }
 }
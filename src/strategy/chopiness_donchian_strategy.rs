@@ -1,153 +1,170 @@
-use crate::indicator::{ATRStopLoss, ChoppinessIndex, DonchianChannel};
+use crate::data::RetentionPolicy;
+use crate::error::parse_field;
+use crate::indicator::{ChoppinessIndex, DonchianChannel, IndicatorSpec, Side, StopConfig};
+use crate::{Error, Result};
 use chrono::DateTime;
-use super::{KlineManager, TradingStrategy};
+use super::execution::{Executor, Mode};
+use super::intent::{Intent, MarketState, SizeHint};
+use super::position::{Position, PositionRules};
+use super::series_graph::SeriesSpec;
+use super::trade::Trade;
+use super::{KlineManager, SignalStrategy, TradingStrategy};
 
-pub enum Mode {
-    Backtest,
-    Live,
-}
+// Nombre de bougies nécessaires pour initialiser les indicateurs
+const WARMUP_KLINES: usize = 100;
 
 pub struct ChoppinessDonchianAtrStrategy {
-    mode: Mode,
-    symbol: String,
-    capital: f64,
-    quantity: f64,
-    on_trade: bool,
-    take_profit: f64,
-    stop_loss: f64,
+    executor: Executor,
+    position_rules: PositionRules, // Pyramidage et prises de profit
+    atr_length: usize, // ATR de l'écart entre deux unités
+    stop: StopConfig, // Indicateur utilisé pour placer le stop loss
+    donchian_length: usize,
+    donchian_offset: usize,
+    chop_length: usize,
+    chop_threshold: f64, // Entrée seulement si le Choppiness Index est inférieur ou égal
 }
 
 impl ChoppinessDonchianAtrStrategy {
     pub fn new(mode: Mode, symbol: &str) -> Self {
         Self {
-            mode,
-            symbol: symbol.to_string(),
-            capital: 0.01,
-            quantity: 0.0,
-            on_trade: false,
-            take_profit: 0.0,
-            stop_loss: 0.0,
+            executor: Executor::new(mode, symbol, 0.01),
+            position_rules: PositionRules::exit_at(3.0),
+            atr_length: 14,
+            stop: StopConfig::default(),
+            donchian_length: 20,
+            donchian_offset: 20,
+            chop_length: 100,
+            chop_threshold: 50.0,
         }
     }
 
-    fn place_order_buy(&mut self, price: f64, stop_loss: f64) {
-        self.on_trade = true;
-        self.quantity = self.capital / price;
-
-        match self.mode {
-            Mode::Backtest => {
-                // Simuler un achat pour le backtesting
-                self.stop_loss = stop_loss;
-                let risk_amount = price - stop_loss;
-                println!("risk_amount: {}", risk_amount);
-                self.take_profit = price + ( risk_amount * 3.0 );
-
-                self.capital -= self.quantity * price;
-                println!("Mocking order: Buy {} of price {}, placing stop loss at {} and take profit at {}", self.symbol, price, self.stop_loss, self.take_profit);
-            }
-            Mode::Live => {
-                // Appel réel à l'API Binance pour exécuter un achat
-                println!("Placing real order on Binance: Buy {} at price {}", self.symbol, price);
-                // Ici, vous devriez intégrer la logique d'achat réelle via l'API Binance
-                // let market: Market = Binance::new(Some(api_key), Some(secret_key));
-                // market.buy_limit(symbol, quantity, price).unwrap();
-            }
-        }
+    // Remplacer le stop ATR par défaut (Chandelier Exit, Parabolic SAR, swing...)
+    pub fn with_stop(mut self, stop: StopConfig) -> Self {
+        self.stop = stop;
+        self
     }
 
-    fn place_order_sell(&mut self, price: f64) {
-        self.on_trade = false;
-
-        match self.mode {
-            Mode::Backtest => {
-                // Simuler un achat pour le backtesting
-                println!("Mocking order: Sell {} of price {}", self.symbol, price);
-                if price >= self.take_profit {
-                    println!("Stop loss triggered: Sell {} of price {}", self.symbol, price);
-                    self.capital += self.quantity * price;
-                }
-                if price <= self.stop_loss {
-                    println!("Take profit triggered: Sell {} of price {}", self.symbol, price);
-                    self.capital += self.quantity * price;
-                }
-
-            // Calculer le taux d'évolution du capital en pourcentage
-            let evolution_percentage = ((self.capital - 0.01) / 0.01) * 100.0;
-            println!("Capital after sell: {}", self.capital);
-            println!("Capital evolution: {:.2}%", evolution_percentage);
-            }
-            Mode::Live => {
-                // Appel réel à l'API Binance pour exécuter un achat
-                println!("Placing real order on Binance: Buy {} at price {}", self.symbol, price);
-                // Ici, vous devriez intégrer la logique d'achat réelle via l'API Binance
-                // let market: Market = Binance::new(Some(api_key), Some(secret_key));
-                // market.buy_limit(symbol, quantity, price).unwrap();
-            }
-        }
+    pub fn with_donchian(mut self, length: usize, offset: usize) -> Self {
+        self.donchian_length = length;
+        self.donchian_offset = offset;
+        self
+    }
+
+    pub fn with_choppiness(mut self, length: usize, threshold: f64) -> Self {
+        self.chop_length = length;
+        self.chop_threshold = threshold;
+        self
+    }
+
+    // Unités supplémentaires sur la poursuite de la cassure et prises de profit partielles
+    pub fn with_position_rules(mut self, rules: PositionRules) -> Self {
+        self.position_rules = rules;
+        self
+    }
+
+    pub fn with_atr_length(mut self, atr_length: usize) -> Self {
+        self.atr_length = atr_length;
+        self
+    }
+
+    // Capital disponible (hors positions ouvertes)
+    pub fn capital(&self) -> f64 {
+        self.executor.capital()
+    }
+
+    pub fn position(&self) -> Option<&Position> {
+        self.executor.position()
+    }
+
+    // Sorties enregistrées au prix moyen d'entrée des quantités fermées
+    pub fn trades(&self) -> &[Trade] {
+        self.executor.trades()
+    }
+
+    pub fn executor(&self) -> &Executor {
+        &self.executor
+    }
+}
+
+impl SignalStrategy for ChoppinessDonchianAtrStrategy {
+    fn signals(&self, market: &MarketState) -> Result<Vec<Intent>> {
+        let manager = market.manager;
+        let (prev_kline, last_kline) = match manager.klines.as_slice() {
+            [.., prev_kline, last_kline] => (prev_kline, last_kline),
+            klines => return Err(Error::InsufficientData { required: 2, available: klines.len() }),
+        };
+        let prev_close = parse_field("close", &prev_kline.close)?;
+        let close = market.close;
+        // Valeurs lues par heure d'ouverture de bougie, NaN pendant l'initialisation (aucune entrée)
+        let obj_donchian_channel = manager.get_donchian_channel().ok_or(Error::MissingIndicator("DonchianChannel"))?;
+        let prev_donchian_channel = manager.value_at(&obj_donchian_channel.upper_band, prev_kline.open_time).unwrap_or(f64::NAN);
+        let donchian_channel = manager.value_at(&obj_donchian_channel.upper_band, last_kline.open_time).unwrap_or(f64::NAN);
+        let obj_choppiness_index = manager.get_choppiness_index().ok_or(Error::MissingIndicator("ChoppinessIndex"))?;
+        let choppiness_index = manager.value_at(&obj_choppiness_index.values, last_kline.open_time).unwrap_or(f64::NAN);
+        let obj_stop = manager.get_stop_indicator().ok_or(Error::MissingIndicator("StopIndicator"))?;
+        // Pas de stop disponible (initialisation, SAR en tendance baissière) : pas d'entrée
+        let Some(stop_loss) = obj_stop.stop(Side::Long) else {
+            return Ok(Vec::new());
+        };
+
+        let units = match market.position {
+            None if prev_close < prev_donchian_channel
+                && close > donchian_channel
+                && choppiness_index <= self.chop_threshold => 0,
+            // Pyramidage : nouvelle unité, avec son propre stop, tous les `add_step` ATR
+            Some(position) if position.can_add(close, market.series("atr", "value"), &self.position_rules) => position.units().len(),
+            _ => return Ok(Vec::new()),
+        };
+        // Capital réparti entre les unités restant à prendre
+        let remaining = self.position_rules.max_units().saturating_sub(units).max(1);
+
+        Ok(vec![Intent::Enter { side: Side::Long, stop: Some(stop_loss), size: SizeHint::Capital(1.0 / remaining as f64) }])
     }
 }
 
 impl TradingStrategy for ChoppinessDonchianAtrStrategy {
-    fn prepare(&self, klines: &[binance::model::KlineSummary]) -> KlineManager {
+    fn warmup(&self) -> usize {
+        WARMUP_KLINES
+    }
+
+    fn prepare(&self, klines: &[binance::model::KlineSummary]) -> Result<KlineManager> {
         println!("Preparing ChoppinessDonchianAtrStrategy...");
 
-        let initial_klines: Vec<binance::model::KlineSummary> = klines[0..100].to_vec();
-        let donchian_channel = Box::new(DonchianChannel::new(&initial_klines, 20, 20));
-        let choppiness_index = Box::new(ChoppinessIndex::new(&initial_klines, 100));
-        let atr_stop_loss = Box::new(ATRStopLoss::new(&initial_klines, 14, 1.5));
+        if klines.len() < WARMUP_KLINES {
+            return Err(Error::InsufficientData { required: WARMUP_KLINES, available: klines.len() });
+        }
 
-        KlineManager::new(initial_klines, vec![donchian_channel, choppiness_index, atr_stop_loss])
+        let initial_klines: Vec<binance::model::KlineSummary> = klines[0..WARMUP_KLINES].to_vec();
+        let donchian_channel = Box::new(DonchianChannel::new(&initial_klines, self.donchian_length, self.donchian_offset)?);
+        let choppiness_index = Box::new(ChoppinessIndex::new(&initial_klines, self.chop_length)?);
+        let stop = self.stop.build(&initial_klines)?;
+
+        let mut manager = KlineManager::new(initial_klines, vec![donchian_channel, choppiness_index, stop]);
+        // ATR de l'écart entre deux unités
+        let atr = IndicatorSpec::Atr { length: self.atr_length };
+        manager.add_series(vec![SeriesSpec { name: "atr".to_string(), node: atr.node()?, inputs: Vec::new() }])?;
+        Ok(match self.executor.mode() {
+            Mode::Backtest => manager,
+            // Un bot en continu ne garde que l'historique utile aux indicateurs
+            Mode::Live => manager.with_retention(RetentionPolicy::MaxLookback { margin: WARMUP_KLINES }),
+        })
     }
 
-    fn execute(&mut self, kline: binance::model::KlineSummary, manager: &mut KlineManager) {
+    fn execute(&mut self, kline: binance::model::KlineSummary, manager: &mut KlineManager) -> Result<()> {
         //println!("Running ChoppinessDonchianAtrStrategy...");
         manager.add_kline(kline.clone());
 
-        let last_kline = manager.klines[manager.klines.len() - 1].clone();
-        let prev_kline = manager.klines[manager.klines.len() - 2].clone();
-        let prev_close = prev_kline.close.parse::<f64>().unwrap_or(0.0);        
-        let close = last_kline.close.parse::<f64>().unwrap_or(0.0);
-        let obj_donchian_channel = manager.get_donchian_channel().unwrap();
-        let donchian_channel = obj_donchian_channel.upper_band[obj_donchian_channel.upper_band.len() - 1].clone();
-        let prev_donchian_channel = obj_donchian_channel.upper_band[obj_donchian_channel.upper_band.len() - 2].clone();
-        let obj_choppiness_index = manager.get_choppiness_index().unwrap();
-        let choppiness_index = obj_choppiness_index.values[obj_choppiness_index.values.len() - 1].clone();
-        let obj_atr_stop_loss = manager.get_atr_stop_loss().unwrap();
-        let atr_stop_loss = obj_atr_stop_loss.stop_losses[obj_atr_stop_loss.stop_losses.len() - 1].clone();
+        let market = MarketState::new(&kline, manager, self.executor.position())?;
+        let close = market.close;
+        let intents = self.signals(&market)?;
 
-        //println!("closed time: {}", convert_timestamp_to_datetime(kline.close_time));
-        //println!("Prev Close: {}", prev_close);
-        //println!("Close: {}", close);
-        //println!("Donchian Channel: {}", donchian_channel);
-        //println!("Prev Donchian Channel: {}", prev_donchian_channel);
-        //println!("Choppiness Index: {}", choppiness_index);
-
-        if self.on_trade == false 
-            && prev_close < prev_donchian_channel
-            && close > donchian_channel 
-            && choppiness_index <= 50.0 {
-            println!("Placing buy order...");
-            self.place_order_buy(close, atr_stop_loss);
-            println!("last kline: {:?}", kline);
-            println!("closed time: {}", convert_timestamp_to_datetime(kline.close_time));
-            // Récupérer le DonchianChannel et afficher `upper_band`
-            if let Some(donchian_channel) = manager.get_donchian_channel() {
-                println!("Upper Band: {:?}", donchian_channel.upper_band[donchian_channel.upper_band.len() - 1]);
-            } else {
-                println!("DonchianChannel non trouvé parmi les observateurs.");
-            }
-            if let Some(choppiness_index) = manager.get_choppiness_index() {
-                println!("Choppiness Index Values: {:?}", choppiness_index.values[choppiness_index.values.len() - 1]);
-            }
-            if let Some(atr_stop_loss) = manager.get_atr_stop_loss() {
-                println!("ATR Stop Loss: {:?}", atr_stop_loss.stop_losses[atr_stop_loss.stop_losses.len() - 1]);
-            }
+        for intent in &intents {
+            println!("Intent: {}", intent);
+            println!("closed time: {}", convert_timestamp_to_datetime(kline.close_time)?);
         }
+        // Ordres des intentions, puis stops par unité et prises de profit
+        self.executor.apply(kline.open_time, close, &intents, &self.position_rules)?;
 
-        if self.on_trade == true && (close > self.take_profit || close < self.stop_loss) {
-            self.place_order_sell(close);
-        }
-        
         //println!("last kline: {:?}", kline);
         //println!("closed time: {}", convert_timestamp_to_datetime(kline.close_time));
         //// Récupérer le DonchianChannel et afficher `upper_band`
@@ -162,13 +179,14 @@ impl TradingStrategy for ChoppinessDonchianAtrStrategy {
         //if let Some(atr_stop_loss) = manager.get_atr_stop_loss() {
         //    println!("ATR Stop Loss: {:?}", atr_stop_loss.stop_losses[atr_stop_loss.stop_losses.len() - 1]);
         //}
+
+        Ok(())
     }
 }
 
+fn convert_timestamp_to_datetime(timestamp: i64) -> Result<String> {
+    // Crée un NaiveDateTime à partir du timestamp (millisecondes depuis l'époque UNIX)
+    let naive = DateTime::from_timestamp_millis(timestamp).ok_or(Error::InvalidTimestamp(timestamp))?;
 
-fn convert_timestamp_to_datetime(timestamp: i64) -> String {
-    // Crée un NaiveDateTime à partir du timestamp (secondes depuis l'époque UNIX)
-    let naive = DateTime::from_timestamp_millis(timestamp).unwrap();
-
-    naive.format("%Y-%m-%d %H:%M:%S").to_string()
+    Ok(naive.format("%Y-%m-%d %H:%M:%S").to_string())
 }
@@ -0,0 +1,222 @@
+use crate::data::RingBuffer;
+use crate::indicator::Side;
+use crate::{Error, Result};
+
+use super::intent::{Intent, SizeHint};
+use super::position::{Position, PositionRules};
+use super::trade::Trade;
+
+// Nombre de transactions et d'ordres conservés par défaut en réel, où l'exécution ne s'arrête pas
+const LIVE_LOG_CAPACITY: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Backtest,
+    Live,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderSide {
+    Buy,
+    Sell,
+}
+
+// Ordre au marché, au prix de clôture de la bougie
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Order {
+    pub time: i64,
+    pub side: OrderSide,
+    pub quantity: f64,
+    pub price: f64,
+}
+
+// Couche d'exécution : convertit les intentions des stratégies en ordres (simulés en backtest,
+// envoyés à Binance en réel), tient le capital, la position ouverte et le journal des transactions.
+// Le journal est complet en backtest et limité aux `LIVE_LOG_CAPACITY` dernières entrées en réel.
+#[derive(Debug, Clone)]
+pub struct Executor {
+    mode: Mode,
+    symbol: String,
+    capital: f64, // Liquidités : diminuées des achats, augmentées des ventes
+    position: Option<Position>,
+    trades: RingBuffer<Trade>,
+    orders: RingBuffer<Order>,
+}
+
+impl Executor {
+    pub fn new(mode: Mode, symbol: &str, capital: f64) -> Self {
+        let capacity = match mode {
+            Mode::Backtest => None,
+            Mode::Live => Some(LIVE_LOG_CAPACITY),
+        };
+        Self {
+            mode,
+            symbol: symbol.to_string(),
+            capital,
+            position: None,
+            trades: RingBuffer::bounded(capacity),
+            orders: RingBuffer::bounded(capacity),
+        }
+    }
+
+    // Ne conserver que les `capacity` dernières transactions et derniers ordres (None : sans limite)
+    pub fn with_log_capacity(mut self, capacity: Option<usize>) -> Self {
+        self.trades.set_capacity(capacity);
+        self.orders.set_capacity(capacity);
+        self
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    pub fn capital(&self) -> f64 {
+        self.capital
+    }
+
+    // Capital en comptant la position ouverte à son prix moyen d'entrée
+    pub fn equity(&self) -> f64 {
+        self.position.as_ref().map_or(self.capital, |position| match position.side() {
+            Side::Long => self.capital + self.invested(),
+            Side::Short => self.capital - self.invested(),
+        })
+    }
+
+    // Montant engagé dans la position ouverte, à son prix moyen d'entrée
+    fn invested(&self) -> f64 {
+        self.position.as_ref().map_or(0.0, |position| position.average_price() * position.quantity())
+    }
+
+    pub fn position(&self) -> Option<&Position> {
+        self.position.as_ref()
+    }
+
+    // Sorties enregistrées au prix moyen d'entrée des quantités fermées
+    pub fn trades(&self) -> &[Trade] {
+        &self.trades
+    }
+
+    pub fn orders(&self) -> &[Order] {
+        &self.orders
+    }
+
+    // Exécuter les intentions dans l'ordre au prix `price`, puis les stops et prises de profit de la
+    // position. Une entrée contraire à la position ouverte est ignorée : la stratégie sort d'abord.
+    // Un stop invalide (ou absent alors que des prises de profit sont fixées) est une erreur.
+    pub fn apply(&mut self, time: i64, price: f64, intents: &[Intent], rules: &PositionRules) -> Result<Vec<Order>> {
+        let mut orders = Vec::new();
+
+        for intent in intents {
+            match *intent {
+                Intent::Enter { side, stop, size } => {
+                    if self.position.as_ref().is_some_and(|position| position.side() != side) {
+                        continue;
+                    }
+                    // Les prises de profit sont exprimées en R : une entrée sans stop ne peut pas les placer
+                    if stop.is_none() && !rules.take_profits().is_empty() {
+                        return Err(Error::invalid_parameter("stop", "entries need a stop when take profits are set"));
+                    }
+                    let quantity = self.quantity(size, price)?;
+                    // Tout le capital est déjà engagé
+                    if quantity <= 0.0 {
+                        continue;
+                    }
+                    match self.position.as_mut() {
+                        Some(position) => position.add_unit(time, price, quantity, stop)?,
+                        None => self.position = Some(Position::open(side, time, price, quantity, stop)?),
+                    }
+                    orders.push(self.send(time, entry_order(side), quantity, price));
+                }
+                Intent::Exit { side } => {
+                    let trade = self
+                        .position
+                        .as_mut()
+                        .filter(|position| position.side() == side)
+                        .and_then(|position| position.close(time, price));
+                    if let Some(trade) = trade {
+                        orders.push(self.record(trade));
+                    }
+                }
+                Intent::ModifyStop { side, stop } => {
+                    if !stop.is_finite() {
+                        return Err(Error::invalid_parameter("stop", format!("cannot move the stop to {}", stop)));
+                    }
+                    if let Some(position) = self.position.as_mut().filter(|position| position.side() == side) {
+                        position.move_stops(stop);
+                    }
+                }
+            }
+            self.position = self.position.take().filter(|position| !position.is_closed());
+        }
+
+        if let Some(mut position) = self.position.take() {
+            for trade in position.update(time, price, rules) {
+                orders.push(self.record(trade));
+            }
+            self.position = Some(position).filter(|position| !position.is_closed());
+        }
+
+        Ok(orders)
+    }
+
+    // Quantité d'une entrée. Une taille au risque ne dépasse jamais le capital encore disponible
+    // (nulle quand tout est engagé) : la position entière reste couverte par le capital.
+    fn quantity(&self, size: SizeHint, price: f64) -> Result<f64> {
+        let quantity = match size {
+            SizeHint::Quantity(quantity) => quantity,
+            SizeHint::Capital(fraction) => self.capital * fraction / price,
+            SizeHint::Risk { fraction, distance } => self.equity() * fraction / distance,
+        };
+        if !quantity.is_finite() || quantity <= 0.0 {
+            return Err(Error::invalid_parameter("size", format!("{:?} gives an invalid quantity {}", size, quantity)));
+        }
+        match size {
+            SizeHint::Risk { .. } => Ok(quantity.min((self.equity() - self.invested()).max(0.0) / price)),
+            _ => Ok(quantity),
+        }
+    }
+
+    fn record(&mut self, trade: Trade) -> Order {
+        let order = self.send(trade.exit_time, exit_order(trade.side), trade.quantity, trade.exit_price);
+        self.trades.push(trade);
+        order
+    }
+
+    fn send(&mut self, time: i64, side: OrderSide, quantity: f64, price: f64) -> Order {
+        match self.mode {
+            Mode::Backtest => {
+                // Simuler l'ordre pour le backtesting
+                println!("Mocking order: {:?} {} {} at price {}", side, quantity, self.symbol, price);
+            }
+            Mode::Live => {
+                // Appel réel à l'API Binance pour exécuter l'ordre
+                println!("Placing real order on Binance: {:?} {} {} at price {}", side, quantity, self.symbol, price);
+                // Ici, vous devriez intégrer la logique d'ordre réelle via l'API Binance
+                // let market: Market = Binance::new(Some(api_key), Some(secret_key));
+                // market.buy_limit(symbol, quantity, price).unwrap();
+            }
+        }
+
+        match side {
+            OrderSide::Buy => self.capital -= quantity * price,
+            OrderSide::Sell => self.capital += quantity * price,
+        }
+        let order = Order { time, side, quantity, price };
+        self.orders.push(order);
+        order
+    }
+}
+
+fn entry_order(side: Side) -> OrderSide {
+    match side {
+        Side::Long => OrderSide::Buy,
+        Side::Short => OrderSide::Sell,
+    }
+}
+
+fn exit_order(side: Side) -> OrderSide {
+    match side {
+        Side::Long => OrderSide::Sell,
+        Side::Short => OrderSide::Buy,
+    }
+}
@@ -0,0 +1,76 @@
+use std::fmt;
+
+use binance::model::KlineSummary;
+
+use crate::error::parse_field;
+use crate::indicator::Side;
+use crate::Result;
+
+use super::position::Position;
+use super::KlineManager;
+
+// Taille d'une entrée, convertie en quantité par la couche d'exécution
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SizeHint {
+    Quantity(f64),
+    Capital(f64), // Fraction du capital disponible engagée
+    Risk { fraction: f64, distance: f64 }, // Fraction du capital perdue sur un mouvement de `distance`
+}
+
+// Intention de la stratégie pour la bougie clôturée, exécutée au prix de clôture
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Intent {
+    // Ouvrir une position, ou ajouter une unité à la position ouverte dans le même sens
+    Enter { side: Side, stop: Option<f64>, size: SizeHint },
+    // Fermer toute la position ouverte dans ce sens
+    Exit { side: Side },
+    // Placer le stop de toutes les unités
+    ModifyStop { side: Side, stop: f64 },
+}
+
+impl Intent {
+    pub fn side(&self) -> Side {
+        match *self {
+            Intent::Enter { side, .. } | Intent::Exit { side } | Intent::ModifyStop { side, .. } => side,
+        }
+    }
+}
+
+impl fmt::Display for Intent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Intent::Enter { side, stop: Some(stop), size } => write!(f, "enter {:?} ({:?}), stop at {}", side, size, stop),
+            Intent::Enter { side, stop: None, size } => write!(f, "enter {:?} ({:?})", side, size),
+            Intent::Exit { side } => write!(f, "exit {:?}", side),
+            Intent::ModifyStop { side, stop } => write!(f, "move {:?} stop to {}", side, stop),
+        }
+    }
+}
+
+// État du marché vu par une stratégie : bougie clôturée, indicateurs et position ouverte
+pub struct MarketState<'a> {
+    pub kline: &'a KlineSummary,
+    pub close: f64,
+    pub manager: &'a KlineManager,
+    pub position: Option<&'a Position>,
+}
+
+impl<'a> MarketState<'a> {
+    pub fn new(kline: &'a KlineSummary, manager: &'a KlineManager, position: Option<&'a Position>) -> Result<Self> {
+        Ok(Self { kline, close: parse_field("close", &kline.close)?, manager, position })
+    }
+
+    pub fn open_time(&self) -> i64 {
+        self.kline.open_time
+    }
+
+    // Valeur d'une sortie d'un indicateur de série pour cette bougie (NaN pendant l'initialisation)
+    pub fn series(&self, name: &str, output: &str) -> f64 {
+        self.manager.series_at(name, output, self.kline.open_time).unwrap_or(f64::NAN)
+    }
+
+    // Position ouverte dans ce sens
+    pub fn position_on(&self, side: Side) -> Option<&'a Position> {
+        self.position.filter(|position| position.side() == side)
+    }
+}
@@ -1,13 +1,44 @@
 use std::any::Any;
 
+use crate::indicator::StopIndicator;
+use crate::Result;
+
+use super::intent::{Intent, MarketState};
 use super::KlineManager;
 
 pub trait TradingStrategy {
-    fn prepare(&self, klines: &[binance::model::KlineSummary]) -> KlineManager;
-    fn execute(&mut self, klines: binance::model::KlineSummary, manager: &mut KlineManager);
+    // Nombre de bougies passées à `prepare` pour initialiser les indicateurs : l'exécution
+    // commence à la bougie suivante
+    fn warmup(&self) -> usize;
+    fn prepare(&self, klines: &[binance::model::KlineSummary]) -> Result<KlineManager>;
+    fn execute(&mut self, klines: binance::model::KlineSummary, manager: &mut KlineManager) -> Result<()>;
+}
+
+// Signaux d'une stratégie : intentions calculées à partir de l'état du marché seul, sans ordre
+// ni capital. La couche d'exécution (`Executor`) les transforme en ordres.
+pub trait SignalStrategy {
+    fn signals(&self, market: &MarketState) -> Result<Vec<Intent>>;
 }
 
 pub trait Observer {
     fn on_new_kline(&mut self, kline: &binance::model::KlineSummary, all_klines: &[binance::model::KlineSummary]);
     fn as_any(&self) -> &dyn Any;
+
+    // Accès mutable, pour les observateurs alimentés aussi hors des bougies (séries de référence)
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+        None
+    }
+
+    // Accès à l'observateur en tant qu'indicateur de stop, pour les stops interchangeables
+    fn as_stop_indicator(&self) -> Option<&dyn StopIndicator> {
+        None
+    }
+
+    // Nombre de bougies récentes dont l'observateur a besoin dans `all_klines`
+    fn lookback(&self) -> usize {
+        1
+    }
+
+    // Ne conserver que les `capacity` dernières valeurs des séries (None : tout l'historique)
+    fn set_retention(&mut self, _capacity: Option<usize>) {}
 }
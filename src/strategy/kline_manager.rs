@@ -1,30 +1,99 @@
-use crate::indicator::{ATRStopLoss, ChoppinessIndex, DonchianChannel};
+use crate::indicator::{self, ATRStopLoss, ChoppinessIndex, DonchianChannel, StopIndicator};
+
+use crate::data::{RetentionPolicy, RingBuffer};
+use crate::Result;
 
 use super::interface::Observer;
+use super::series_graph::{SeriesGraph, SeriesSpec};
 
 pub struct KlineManager {
-    pub klines: Vec<binance::model::KlineSummary>,
+    pub klines: RingBuffer<binance::model::KlineSummary>,
     observers: Vec<Box<dyn Observer>>, // Liste d'observateurs dynamiques
+    series: SeriesGraph,               // Indicateurs de séries, calculés dans l'ordre de leurs dépendances
+    retention: RetentionPolicy,        // Taille des historiques de bougies et de séries
 }
 
 impl KlineManager {
     pub fn new(initial_klines: Vec<binance::model::KlineSummary>, observers: Vec<Box<dyn Observer>>) -> Self {
         let mut manager = Self {
-            klines: initial_klines.clone(),
+            klines: RingBuffer::from(initial_klines.clone()),
             observers,
+            series: SeriesGraph::new(),
+            retention: RetentionPolicy::Unbounded,
         };
 
         // Calculer les valeurs initiales pour les observateurs avec les klines fournies
-        for observer in manager.observers.iter_mut() {
-            observer.on_new_kline(&initial_klines[0], &manager.klines); // Passer les klines au complet
+        if let Some(first_kline) = initial_klines.first() {
+            for observer in manager.observers.iter_mut() {
+                observer.on_new_kline(first_kline, &manager.klines); // Passer les klines au complet
+            }
         }
 
         manager
     }
 
+    // Borner la mémoire pour un bot qui tourne en continu (tout l'historique est conservé par défaut)
+    pub fn with_retention(mut self, retention: RetentionPolicy) -> Self {
+        self.set_retention(retention);
+        self
+    }
+
+    pub fn set_retention(&mut self, retention: RetentionPolicy) {
+        self.retention = retention;
+
+        let lookback = self
+            .observers
+            .iter()
+            .map(|observer| observer.lookback())
+            .chain([self.series.lookback(), 2])
+            .max()
+            .unwrap_or(2);
+        let capacity = match retention {
+            RetentionPolicy::Unbounded => None,
+            RetentionPolicy::Capacity(capacity) => Some(capacity),
+            RetentionPolicy::MaxLookback { margin } => Some(lookback + margin),
+        };
+
+        // Les bougies ne descendent jamais sous l'historique demandé par les indicateurs
+        self.klines.set_capacity(capacity.map(|capacity| capacity.max(lookback)));
+        for observer in self.observers.iter_mut() {
+            observer.set_retention(capacity);
+        }
+        self.series.set_retention(capacity);
+    }
+
+    pub fn retention(&self) -> RetentionPolicy {
+        self.retention
+    }
+
     pub fn add_kline(&mut self, kline: binance::model::KlineSummary) {
         self.klines.push(kline.clone());
         self.notify_observers(&kline);
+        self.series.update(&self.klines);
+    }
+
+    // Ajouter des indicateurs de séries (dans n'importe quel ordre) et calculer leur historique
+    // sur les bougies conservées
+    pub fn add_series(&mut self, specs: Vec<SeriesSpec>) -> Result<()> {
+        self.series.add(specs, &self.klines)?;
+        self.set_retention(self.retention);
+        Ok(())
+    }
+
+    // Valeur d'une série d'indicateur pour la bougie ouverte à `open_time`
+    // (None si la bougie n'est plus conservée ou pendant l'initialisation)
+    pub fn value_at(&self, series: &[f64], open_time: i64) -> Option<f64> {
+        indicator::value_at(&self.klines, series, open_time)
+    }
+
+    // Valeur d'une sortie d'un indicateur de série pour la bougie ouverte à `open_time`
+    pub fn series_at(&self, name: &str, output: &str, open_time: i64) -> Option<f64> {
+        self.value_at(self.get_series(name, output)?, open_time)
+    }
+
+    // Série d'une sortie d'un indicateur de série ("value" pour la sortie principale)
+    pub fn get_series(&self, name: &str, output: &str) -> Option<&[f64]> {
+        self.series.get(name, output)
     }
 
     fn notify_observers(&mut self, kline: &binance::model::KlineSummary) {
@@ -33,6 +102,25 @@ impl KlineManager {
         }
     }
 
+    // Récupérer le premier observateur du type demandé
+    pub fn get_indicator<T: Observer + 'static>(&self) -> Option<&T> {
+        self.observers
+            .iter()
+            .find_map(|observer| observer.as_any().downcast_ref::<T>())
+    }
+
+    // Récupérer le premier observateur du type demandé, s'il accepte un accès mutable
+    pub fn get_indicator_mut<T: Observer + 'static>(&mut self) -> Option<&mut T> {
+        self.observers
+            .iter_mut()
+            .find_map(|observer| observer.as_any_mut().and_then(|any| any.downcast_mut::<T>()))
+    }
+
+    // Récupérer le premier observateur qui fournit un stop, quel que soit son type
+    pub fn get_stop_indicator(&self) -> Option<&dyn StopIndicator> {
+        self.observers.iter().find_map(|observer| observer.as_stop_indicator())
+    }
+
     // Récupérer une référence à un `DonchianChannel` si c'est l'un des observateurs
     pub fn get_donchian_channel(&self) -> Option<&DonchianChannel> {
         for observer in &self.observers {
@@ -1,12 +1,43 @@
 pub mod interface;
-pub use interface::TradingStrategy;
+pub use interface::{SignalStrategy, TradingStrategy};
 
 pub mod chopiness_donchian_strategy;
 pub use chopiness_donchian_strategy::ChoppinessDonchianAtrStrategy;
-pub use chopiness_donchian_strategy::Mode;
 
 pub mod backtester;
 pub use backtester::Backtester;
 
 pub mod kline_manager;
 pub use kline_manager::KlineManager;
+
+pub mod multi_timeframe_manager;
+pub use multi_timeframe_manager::MultiTimeframeManager;
+
+pub mod trade;
+pub use trade::Trade;
+
+pub mod position;
+pub use position::{Position, PositionRules, PositionUnit, TakeProfit};
+
+pub mod intent;
+pub use intent::{Intent, MarketState, SizeHint};
+
+pub mod execution;
+pub use execution::{Executor, Mode, Order, OrderSide};
+
+pub mod rules;
+pub use rules::RuleSet;
+
+pub mod rule_strategy;
+pub use rule_strategy::RuleStrategy;
+
+pub mod turtle_strategy;
+pub use turtle_strategy::{TurtleStrategy, TurtleSystem};
+
+pub mod reference_strategies;
+
+pub mod registry;
+pub use registry::{ParameterKind, ParameterSpec, Parameters, StrategyConfig, StrategyEntry, StrategyRegistry};
+
+pub mod series_graph;
+pub use series_graph::{SeriesGraph, SeriesSpec};
@@ -0,0 +1,145 @@
+use std::collections::VecDeque;
+
+use binance::model::KlineSummary;
+
+use crate::data::{Interval, Resampler};
+use crate::Result;
+
+use super::interface::Observer;
+use super::KlineManager;
+
+// Source des bougies d'une unité de temps supérieure
+enum TimeframeFeed {
+    // Bougies récupérées séparément, en attente de leur clôture
+    Fetched(VecDeque<KlineSummary>),
+    // Agrégation à partir de l'intervalle de base
+    Resampled(Resampler),
+}
+
+struct Timeframe {
+    interval: Interval,
+    manager: KlineManager,
+    feed: TimeframeFeed,
+}
+
+// Gestionnaire de plusieurs unités de temps synchronisées pour un même symbole.
+// Les bougies des unités de temps supérieures ne deviennent visibles qu'une fois clôturées
+// par rapport à la dernière bougie de base reçue.
+pub struct MultiTimeframeManager {
+    base_interval: Interval,
+    base: KlineManager,
+    timeframes: Vec<Timeframe>,
+}
+
+impl MultiTimeframeManager {
+    pub fn new(base_interval: Interval, base: KlineManager) -> Self {
+        Self {
+            base_interval,
+            base,
+            timeframes: Vec::new(),
+        }
+    }
+
+    // Ajouter une unité de temps dont les bougies ont été récupérées séparément.
+    // `build_observers` reçoit les bougies déjà clôturées pour initialiser les indicateurs.
+    pub fn add_fetched_timeframe<F>(&mut self, interval: Interval, klines: Vec<KlineSummary>, build_observers: F) -> Result<()>
+    where
+        F: FnOnce(&[KlineSummary]) -> Result<Vec<Box<dyn Observer>>>,
+    {
+        let now = self.current_time();
+        let (visible, pending): (Vec<KlineSummary>, Vec<KlineSummary>) = klines
+            .into_iter()
+            .partition(|kline| now.is_some_and(|now| kline.close_time <= now));
+
+        let observers = build_observers(&visible)?;
+        self.push_timeframe(Timeframe {
+            interval,
+            manager: KlineManager::new(visible, observers),
+            feed: TimeframeFeed::Fetched(pending.into()),
+        });
+
+        Ok(())
+    }
+
+    // Ajouter une unité de temps construite par agrégation des bougies de base. Les périodes
+    // incomplètes (début de l'historique ou trou dans les données) ne sont pas publiées.
+    // `build_observers` reçoit les bougies déjà clôturées pour initialiser les indicateurs.
+    pub fn add_resampled_timeframe<F>(&mut self, interval: Interval, build_observers: F) -> Result<()>
+    where
+        F: FnOnce(&[KlineSummary]) -> Result<Vec<Box<dyn Observer>>>,
+    {
+        let mut resampler = Resampler::new(interval);
+        let closed: Vec<KlineSummary> = self.base.klines.iter().flat_map(|kline| resampler.push(kline)).collect();
+
+        let observers = build_observers(&closed)?;
+        self.push_timeframe(Timeframe {
+            interval,
+            manager: KlineManager::new(closed, observers),
+            feed: TimeframeFeed::Resampled(resampler),
+        });
+
+        Ok(())
+    }
+
+    // Ajouter une nouvelle bougie de base et publier les bougies supérieures qui viennent de clôturer
+    pub fn add_kline(&mut self, kline: KlineSummary) {
+        self.base.add_kline(kline.clone());
+
+        for timeframe in self.timeframes.iter_mut() {
+            match &mut timeframe.feed {
+                TimeframeFeed::Fetched(pending) => {
+                    while pending.front().is_some_and(|next| next.close_time <= kline.close_time) {
+                        if let Some(closed) = pending.pop_front() {
+                            timeframe.manager.add_kline(closed);
+                        }
+                    }
+                }
+                TimeframeFeed::Resampled(resampler) => {
+                    if let Some(closed) = resampler.push(&kline) {
+                        timeframe.manager.add_kline(closed);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn base_interval(&self) -> Interval {
+        self.base_interval
+    }
+
+    pub fn base(&self) -> &KlineManager {
+        &self.base
+    }
+
+    pub fn base_mut(&mut self) -> &mut KlineManager {
+        &mut self.base
+    }
+
+    // Bougies clôturées et indicateurs d'une unité de temps (l'intervalle de base inclus)
+    pub fn timeframe(&self, interval: Interval) -> Option<&KlineManager> {
+        if interval == self.base_interval {
+            return Some(&self.base);
+        }
+
+        self.timeframes
+            .iter()
+            .find(|timeframe| timeframe.interval == interval)
+            .map(|timeframe| &timeframe.manager)
+    }
+
+    pub fn intervals(&self) -> Vec<Interval> {
+        let mut intervals = vec![self.base_interval];
+        intervals.extend(self.timeframes.iter().map(|timeframe| timeframe.interval));
+        intervals
+    }
+
+    fn current_time(&self) -> Option<i64> {
+        self.base.klines.last().map(|kline| kline.close_time)
+    }
+
+    fn push_timeframe(&mut self, timeframe: Timeframe) {
+        // Une seule source par unité de temps : la dernière ajoutée remplace la précédente
+        self.timeframes.retain(|existing| existing.interval != timeframe.interval);
+        self.timeframes.push(timeframe);
+    }
+}
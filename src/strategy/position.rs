@@ -0,0 +1,273 @@
+use std::str::FromStr;
+
+use crate::indicator::Side;
+use crate::{Error, Result};
+
+use super::trade::Trade;
+
+// Unité de position : quantité encore ouverte et stop propre à l'unité
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PositionUnit {
+    pub entry_time: i64,
+    pub entry_price: f64,
+    pub quantity: f64,
+    pub stop: Option<f64>,
+}
+
+// Prise de profit partielle : `fraction` de la quantité ouverte est fermée quand le prix
+// dépasse l'entrée de `r_multiple` R (R = distance entre la première entrée et son stop)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TakeProfit {
+    pub r_multiple: f64,
+    pub fraction: f64,
+}
+
+impl TakeProfit {
+    pub fn new(r_multiple: f64, fraction: f64) -> Result<Self> {
+        if !r_multiple.is_finite() || r_multiple <= 0.0 {
+            return Err(Error::invalid_parameter("take_profits", "R multiple must be a positive number"));
+        }
+        if fraction.is_nan() || fraction <= 0.0 || fraction > 1.0 {
+            return Err(Error::invalid_parameter("take_profits", "fraction must be in (0, 1]"));
+        }
+        Ok(Self { r_multiple, fraction })
+    }
+}
+
+// Cible `r:fraction`, par exemple `1.5:0.5`
+impl FromStr for TakeProfit {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self> {
+        let invalid = || Error::invalid_parameter("take_profits", format!("expected `r_multiple:fraction`, got {:?}", text));
+        let (r_multiple, fraction) = text.split_once(':').ok_or_else(invalid)?;
+        let r_multiple = r_multiple.trim().parse().map_err(|_| invalid())?;
+        let fraction = fraction.trim().parse().map_err(|_| invalid())?;
+        Self::new(r_multiple, fraction)
+    }
+}
+
+// Gestion de la position : nombre d'unités, écart entre deux ajouts (en ATR) et prises de profit
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionRules {
+    max_units: usize,
+    add_step: f64,
+    take_profits: Vec<TakeProfit>,
+}
+
+impl Default for PositionRules {
+    // Une seule unité, sans prise de profit
+    fn default() -> Self {
+        Self { max_units: 1, add_step: 0.5, take_profits: Vec::new() }
+    }
+}
+
+impl PositionRules {
+    pub fn new(max_units: usize, add_step: f64, take_profits: Vec<TakeProfit>) -> Result<Self> {
+        if max_units == 0 {
+            return Err(Error::invalid_parameter("max_units", "must be greater than 0"));
+        }
+        if !add_step.is_finite() || add_step <= 0.0 {
+            return Err(Error::invalid_parameter("add_step", "must be a positive number"));
+        }
+        if take_profits.windows(2).any(|pair| pair[0].r_multiple >= pair[1].r_multiple) {
+            return Err(Error::invalid_parameter("take_profits", "R multiples must be increasing"));
+        }
+        Ok(Self { max_units, add_step, take_profits })
+    }
+
+    // Une seule unité, fermée entièrement à `r_multiple` R
+    pub fn exit_at(r_multiple: f64) -> Self {
+        Self { take_profits: vec![TakeProfit { r_multiple, fraction: 1.0 }], ..Self::default() }
+    }
+
+    // Cibles séparées par des virgules : `1:0.5, 2:0.5`
+    pub fn parse_take_profits(text: &str) -> Result<Vec<TakeProfit>> {
+        text.split(',').map(str::trim).filter(|target| !target.is_empty()).map(str::parse).collect()
+    }
+
+    pub fn max_units(&self) -> usize {
+        self.max_units
+    }
+
+    pub fn add_step(&self) -> f64 {
+        self.add_step
+    }
+
+    pub fn take_profits(&self) -> &[TakeProfit] {
+        &self.take_profits
+    }
+}
+
+// Position ouverte en une ou plusieurs unités. Les sorties (stops, prises de profit) sont
+// enregistrées au prix moyen d'entrée des quantités fermées.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Position {
+    side: Side,
+    units: Vec<PositionUnit>,
+    entry_price: f64,  // Première entrée, référence des cibles en R
+    risk: Option<f64>, // R, indéfini sans stop
+    targets_hit: usize,
+}
+
+impl Position {
+    // Le stop, s'il est donné, doit être un nombre sous le prix d'entrée d'un long et au-dessus de
+    // celui d'un short
+    pub fn open(side: Side, time: i64, price: f64, quantity: f64, stop: Option<f64>) -> Result<Self> {
+        let risk = stop.map(|stop| check_stop(side, price, stop)).transpose()?;
+        Ok(Self {
+            side,
+            units: vec![PositionUnit { entry_time: time, entry_price: price, quantity, stop }],
+            entry_price: price,
+            risk,
+            targets_hit: 0,
+        })
+    }
+
+    pub fn side(&self) -> Side {
+        self.side
+    }
+
+    pub fn units(&self) -> &[PositionUnit] {
+        &self.units
+    }
+
+    pub fn risk(&self) -> Option<f64> {
+        self.risk
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.units.is_empty()
+    }
+
+    pub fn quantity(&self) -> f64 {
+        self.units.iter().map(|unit| unit.quantity).sum()
+    }
+
+    // Prix moyen d'entrée des unités ouvertes, pondéré par leur quantité
+    pub fn average_price(&self) -> f64 {
+        average_price(&self.units)
+    }
+
+    // Prix de la cible à `r_multiple` R (aucune sans stop initial)
+    pub fn target_price(&self, r_multiple: f64) -> Option<f64> {
+        self.risk.map(|risk| match self.side {
+            Side::Long => self.entry_price + r_multiple * risk,
+            Side::Short => self.entry_price - r_multiple * risk,
+        })
+    }
+
+    // Nouvelle unité due : place disponible et progression de `add_step` ATR depuis la dernière entrée
+    pub fn can_add(&self, price: f64, atr: f64, rules: &PositionRules) -> bool {
+        let Some(last) = self.units.last() else {
+            return false;
+        };
+        let progress = match self.side {
+            Side::Long => price - last.entry_price,
+            Side::Short => last.entry_price - price,
+        };
+        self.units.len() < rules.max_units && atr > 0.0 && progress >= rules.add_step * atr
+    }
+
+    pub fn add_unit(&mut self, time: i64, price: f64, quantity: f64, stop: Option<f64>) -> Result<()> {
+        if let Some(stop) = stop {
+            check_stop(self.side, price, stop)?;
+        }
+        self.units.push(PositionUnit { entry_time: time, entry_price: price, quantity, stop });
+        Ok(())
+    }
+
+    // Placer le stop de toutes les unités
+    pub fn move_stops(&mut self, stop: f64) {
+        for unit in &mut self.units {
+            unit.stop = Some(stop);
+        }
+    }
+
+    // Sorties sur la bougie clôturée à `price` : unités dont le stop est franchi, puis prises de
+    // profit atteintes (les dernières unités entrées sont réduites en premier)
+    pub fn update(&mut self, time: i64, price: f64, rules: &PositionRules) -> Vec<Trade> {
+        let mut trades = Vec::new();
+
+        let side = self.side;
+        let (stopped, open): (Vec<PositionUnit>, Vec<PositionUnit>) = self.units.iter().partition(|unit| match side {
+            Side::Long => unit.stop.is_some_and(|stop| price < stop),
+            Side::Short => unit.stop.is_some_and(|stop| price > stop),
+        });
+        self.units = open;
+        trades.extend(self.exit(&stopped, time, price));
+
+        while let Some(target) = rules.take_profits.get(self.targets_hit) {
+            let reached = self.target_price(target.r_multiple).is_some_and(|target_price| match self.side {
+                Side::Long => price > target_price,
+                Side::Short => price < target_price,
+            });
+            if self.is_closed() || !reached {
+                break;
+            }
+            self.targets_hit += 1;
+            let portions = self.reduce(self.quantity() * target.fraction, target.fraction >= 1.0);
+            trades.extend(self.exit(&portions, time, price));
+        }
+
+        trades
+    }
+
+    // Fermer toutes les unités
+    pub fn close(&mut self, time: i64, price: f64) -> Option<Trade> {
+        let units = std::mem::take(&mut self.units);
+        self.exit(&units, time, price)
+    }
+
+    // Retirer `quantity` en partant de la dernière unité entrée
+    fn reduce(&mut self, mut quantity: f64, all: bool) -> Vec<PositionUnit> {
+        if all {
+            return std::mem::take(&mut self.units);
+        }
+
+        let mut portions = Vec::new();
+        while quantity > 0.0 {
+            let Some(unit) = self.units.last_mut() else {
+                break;
+            };
+            if quantity >= unit.quantity {
+                quantity -= unit.quantity;
+                portions.extend(self.units.pop());
+            } else {
+                portions.push(PositionUnit { quantity, ..*unit });
+                unit.quantity -= quantity;
+                quantity = 0.0;
+            }
+        }
+        portions
+    }
+
+    fn exit(&self, portions: &[PositionUnit], time: i64, price: f64) -> Option<Trade> {
+        let entry_time = portions.iter().map(|unit| unit.entry_time).min()?;
+        Some(Trade {
+            side: self.side,
+            entry_time,
+            entry_price: average_price(portions),
+            exit_time: time,
+            exit_price: price,
+            quantity: portions.iter().map(|unit| unit.quantity).sum(),
+        })
+    }
+}
+
+// Distance entre l'entrée et le stop (R)
+fn check_stop(side: Side, price: f64, stop: f64) -> Result<f64> {
+    let risk = match side {
+        Side::Long => price - stop,
+        Side::Short => stop - price,
+    };
+    if !stop.is_finite() || risk <= 0.0 {
+        return Err(Error::invalid_parameter("stop", format!("{} is not a valid {:?} stop for an entry at {}", stop, side, price)));
+    }
+    Ok(risk)
+}
+
+fn average_price(units: &[PositionUnit]) -> f64 {
+    let quantity: f64 = units.iter().map(|unit| unit.quantity).sum();
+    units.iter().map(|unit| unit.entry_price * unit.quantity).sum::<f64>() / quantity
+}
@@ -0,0 +1,68 @@
+use crate::{Error, Result};
+
+use super::RuleStrategy;
+
+// Stratégies de référence (positions longues uniquement), écrites dans le langage de règles :
+// elles servent de points de comparaison pour `ChoppinessDonchianAtrStrategy`
+
+// Moyennes mobiles du registre d'indicateurs
+pub const MOVING_AVERAGES: &[&str] = &["sma", "ema", "rma", "wma", "hma", "dema", "tema", "kama", "vwma"];
+
+// Achat quand la moyenne rapide croise la moyenne lente par le haut, vente au croisement inverse
+pub fn moving_average_crossover(average: &str, fast: usize, slow: usize) -> Result<RuleStrategy> {
+    if !MOVING_AVERAGES.contains(&average) {
+        return Err(Error::invalid_parameter(
+            "average",
+            format!("unknown moving average '{}' (available: {})", average, MOVING_AVERAGES.join(", ")),
+        ));
+    }
+    if fast >= slow {
+        return Err(Error::invalid_parameter("fast", "must be smaller than the slow period"));
+    }
+
+    RuleStrategy::parse(&format!(
+        "name: ma_crossover
+entry_long: crosses_above({average}({fast}), {average}({slow}))
+exit_long: crosses_below({average}({fast}), {average}({slow}))
+"
+    ))
+}
+
+// Achat sous la bande inférieure de Bollinger, vente au retour sur la moyenne
+pub fn bollinger_mean_reversion(length: usize, multiplier: f64) -> Result<RuleStrategy> {
+    RuleStrategy::parse(&format!(
+        "name: bollinger_mean_reversion
+entry_long: close < bollinger({length}, {multiplier}).lower
+exit_long: close > bollinger({length}, {multiplier}).basis
+"
+    ))
+}
+
+// Achat quand le RSI sort de la zone de survente, vente en zone de surachat
+pub fn rsi_reversal(period: usize, oversold: f64, overbought: f64) -> Result<RuleStrategy> {
+    if !(0.0..=100.0).contains(&oversold) || !(0.0..=100.0).contains(&overbought) || oversold >= overbought {
+        return Err(Error::invalid_parameter("oversold", "thresholds must satisfy 0 <= oversold < overbought <= 100"));
+    }
+
+    RuleStrategy::parse(&format!(
+        "name: rsi_reversal
+entry_long: crosses_above(rsi({period}), {oversold})
+exit_long: rsi({period}) >= {overbought}
+"
+    ))
+}
+
+// Compression : bandes de Bollinger à l'intérieur du canal de Keltner sur la bougie précédente.
+// Achat à la sortie de la compression par le haut du canal, vente sous la moyenne du canal.
+pub fn keltner_squeeze_breakout(length: usize, bollinger_multiplier: f64, keltner_multiplier: f64) -> Result<RuleStrategy> {
+    let bollinger = format!("bollinger({length}, {bollinger_multiplier})");
+    let keltner = format!("keltner({length}, {length}, {keltner_multiplier})");
+
+    RuleStrategy::parse(&format!(
+        "name: keltner_squeeze
+entry_long: {bollinger}.upper[1] < {keltner}.upper[1] and {bollinger}.lower[1] > {keltner}.lower[1]
+    and close > {keltner}.upper
+exit_long: close < {keltner}.basis
+"
+    ))
+}
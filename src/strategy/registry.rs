@@ -0,0 +1,430 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+
+use crate::indicator::StopConfig;
+use crate::{Error, Result};
+
+use super::position::PositionRules;
+use super::reference_strategies;
+use super::{ChoppinessDonchianAtrStrategy, Mode, RuleStrategy, TradingStrategy, TurtleStrategy, TurtleSystem};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParameterKind {
+    Integer, // Entier positif ou nul (longueurs, décalages)
+    Number,
+    Text,
+}
+
+// Paramètre d'une stratégie. Sans valeur par défaut, le paramètre est obligatoire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParameterSpec {
+    pub name: &'static str,
+    pub kind: ParameterKind,
+    pub default: Option<&'static str>,
+    pub description: &'static str,
+}
+
+impl ParameterSpec {
+    pub const fn new(name: &'static str, kind: ParameterKind, default: Option<&'static str>, description: &'static str) -> Self {
+        Self { name, kind, default, description }
+    }
+
+    fn check(&self, value: &str) -> Result<()> {
+        let valid = match self.kind {
+            ParameterKind::Integer => value.parse::<usize>().is_ok(),
+            ParameterKind::Number => value.parse::<f64>().is_ok_and(f64::is_finite),
+            ParameterKind::Text => true,
+        };
+        if !valid {
+            let expected = match self.kind {
+                ParameterKind::Integer => "a non-negative integer",
+                ParameterKind::Number => "a number",
+                ParameterKind::Text => "a text",
+            };
+            return Err(Error::invalid_parameter(self.name, format!("expected {}, got {:?}", expected, value)));
+        }
+        Ok(())
+    }
+}
+
+// Valeurs des paramètres, sous forme de texte (ligne de commande, fichier de configuration)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Parameters {
+    values: BTreeMap<String, String>,
+}
+
+impl Parameters {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, name: &str, value: &str) -> Self {
+        self.set(name, value);
+        self
+    }
+
+    pub fn set(&mut self, name: &str, value: &str) {
+        self.values.insert(name.to_string(), value.to_string());
+    }
+
+    // Affectation `nom=valeur` de la ligne de commande
+    pub fn set_assignment(&mut self, assignment: &str) -> Result<()> {
+        let (name, value) = assignment
+            .split_once('=')
+            .ok_or_else(|| Error::invalid_parameter("parameters", format!("expected `name=value`, got {:?}", assignment)))?;
+        self.set(name.trim(), value.trim());
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(String::as_str)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.values.keys().map(String::as_str)
+    }
+
+    pub fn text(&self, name: &'static str) -> Result<&str> {
+        self.get(name).ok_or_else(|| Error::invalid_parameter(name, "missing value"))
+    }
+
+    pub fn integer(&self, name: &'static str) -> Result<usize> {
+        let value = self.text(name)?;
+        value.parse().map_err(|_| Error::invalid_parameter(name, format!("expected a non-negative integer, got {:?}", value)))
+    }
+
+    pub fn number(&self, name: &'static str) -> Result<f64> {
+        let value = self.text(name)?;
+        value.parse().map_err(|_| Error::invalid_parameter(name, format!("expected a number, got {:?}", value)))
+    }
+}
+
+// Stratégie disponible par son nom
+#[derive(Debug, Clone, Copy)]
+pub struct StrategyEntry {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub parameters: &'static [ParameterSpec],
+    pub build: fn(&Parameters) -> Result<Box<dyn TradingStrategy>>,
+}
+
+// Registre des stratégies : création par nom avec des paramètres validés contre leur schéma
+#[derive(Debug, Clone, Default)]
+pub struct StrategyRegistry {
+    entries: Vec<StrategyEntry>,
+}
+
+impl StrategyRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Registre des stratégies fournies par la crate
+    pub fn builtin() -> Self {
+        let mut registry = Self::new();
+        for entry in BUILTIN_STRATEGIES {
+            // Les noms des stratégies intégrées sont uniques
+            let _ = registry.register(*entry);
+        }
+        registry
+    }
+
+    pub fn register(&mut self, entry: StrategyEntry) -> Result<()> {
+        if self.get(entry.name).is_some() {
+            return Err(Error::invalid_parameter("strategy", format!("strategy '{}' is already registered", entry.name)));
+        }
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&StrategyEntry> {
+        self.entries.iter().find(|entry| entry.name == name)
+    }
+
+    pub fn entries(&self) -> &[StrategyEntry] {
+        &self.entries
+    }
+
+    // Créer une stratégie : paramètres inconnus refusés, valeurs vérifiées, défauts complétés
+    pub fn create(&self, name: &str, parameters: &Parameters) -> Result<Box<dyn TradingStrategy>> {
+        let entry = self.get(name).ok_or_else(|| {
+            let names: Vec<&str> = self.entries.iter().map(|entry| entry.name).collect();
+            Error::invalid_parameter("strategy", format!("unknown strategy '{}' (available: {})", name, names.join(", ")))
+        })?;
+
+        if let Some(unknown) = parameters.names().find(|name| !entry.parameters.iter().any(|spec| spec.name == *name)) {
+            return Err(Error::invalid_parameter(
+                "parameters",
+                format!("{} has no parameter '{}'", entry.name, unknown),
+            ));
+        }
+
+        let mut resolved = Parameters::new();
+        for spec in entry.parameters {
+            let value = parameters
+                .get(spec.name)
+                .or(spec.default)
+                .ok_or_else(|| Error::invalid_parameter(spec.name, format!("required by {}", entry.name)))?;
+            spec.check(value)?;
+            resolved.set(spec.name, value);
+        }
+        (entry.build)(&resolved)
+    }
+
+    // Liste des stratégies et de leurs paramètres, pour la ligne de commande
+    pub fn describe(&self) -> String {
+        let mut text = String::new();
+        for entry in &self.entries {
+            let _ = writeln!(text, "{}\n    {}", entry.name, entry.description);
+            for spec in entry.parameters {
+                let default = spec.default.map_or("required".to_string(), |default| format!("default: {}", default));
+                let _ = writeln!(text, "    {:<18} {:<8} {} ({})", spec.name, format!("{:?}", spec.kind), spec.description, default);
+            }
+        }
+        text
+    }
+}
+
+// Fichier de configuration d'une stratégie, sous-ensemble de TOML :
+//
+//     strategy = "choppiness_donchian_atr"
+//     chop_threshold = 45
+//     stop = "chandelier(22, 22, 3)"
+//
+// `strategy` désigne la stratégie, les autres clés sont ses paramètres. Les commentaires `#`
+// sont ignorés ; les sections `[...]` et les clés en double sont refusées.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StrategyConfig {
+    pub strategy: String,
+    pub parameters: Parameters,
+}
+
+impl StrategyConfig {
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut strategy = None;
+        let mut parameters = Parameters::new();
+
+        for line in text.lines() {
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            if line.starts_with('[') {
+                return Err(Error::invalid_parameter("config", format!("sections are not supported, got {:?}", line)));
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| Error::invalid_parameter("config", format!("expected `key = value`, got {:?}", line)))?;
+            let value = value.trim();
+            let value = value.strip_prefix('"').and_then(|value| value.strip_suffix('"')).unwrap_or(value);
+
+            match key.trim() {
+                "strategy" if strategy.is_some() => {
+                    return Err(Error::invalid_parameter("config", "duplicate key \"strategy\""));
+                }
+                "strategy" => strategy = Some(value.to_string()),
+                key if parameters.get(key).is_some() => {
+                    return Err(Error::invalid_parameter("config", format!("duplicate key {:?}", key)));
+                }
+                key => parameters.set(key, value),
+            }
+        }
+
+        let strategy = strategy.ok_or_else(|| Error::invalid_parameter("strategy", "missing in config"))?;
+        Ok(Self { strategy, parameters })
+    }
+
+    pub fn create(&self, registry: &StrategyRegistry) -> Result<Box<dyn TradingStrategy>> {
+        registry.create(&self.strategy, &self.parameters)
+    }
+}
+
+// Retire un commentaire `#` de fin de ligne, sauf à l'intérieur d'une valeur entre guillemets
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    for (index, character) in line.char_indices() {
+        match character {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..index],
+            _ => {}
+        }
+    }
+    line
+}
+
+const SYMBOL: ParameterSpec = ParameterSpec::new("symbol", ParameterKind::Text, Some("ETHBTC"), "traded symbol");
+const MODE: ParameterSpec = ParameterSpec::new("mode", ParameterKind::Text, Some("backtest"), "backtest or live");
+
+fn mode(parameters: &Parameters) -> Result<Mode> {
+    match parameters.text("mode")? {
+        "backtest" => Ok(Mode::Backtest),
+        "live" => Ok(Mode::Live),
+        mode => Err(Error::invalid_parameter("mode", format!("expected backtest or live, got {:?}", mode))),
+    }
+}
+
+const TURTLE: &[ParameterSpec] = &[
+    SYMBOL,
+    MODE,
+    ParameterSpec::new("capital", ParameterKind::Number, Some("1"), "initial capital used for unit sizing"),
+    ParameterSpec::new("atr_length", ParameterKind::Integer, Some("20"), "ATR length (N)"),
+    ParameterSpec::new("risk", ParameterKind::Number, Some("0.01"), "fraction of capital risked per N move and unit"),
+    ParameterSpec::new("max_units", ParameterKind::Integer, Some("4"), "maximum number of units"),
+    ParameterSpec::new("add_step", ParameterKind::Number, Some("0.5"), "move in N before adding a unit"),
+    ParameterSpec::new("stop_multiple", ParameterKind::Number, Some("2"), "stop distance in N from the last unit"),
+];
+
+fn turtle(system: TurtleSystem, parameters: &Parameters) -> Result<Box<dyn TradingStrategy>> {
+    Ok(Box::new(
+        TurtleStrategy::new(system, mode(parameters)?, parameters.number("capital")?)?
+            .with_symbol(parameters.text("symbol")?)
+            .with_atr_length(parameters.integer("atr_length")?)
+            .with_units(
+                parameters.number("risk")?,
+                parameters.integer("max_units")?,
+                parameters.number("add_step")?,
+                parameters.number("stop_multiple")?,
+            )?,
+    ))
+}
+
+pub const BUILTIN_STRATEGIES: &[StrategyEntry] = &[
+    StrategyEntry {
+        name: "choppiness_donchian_atr",
+        description: "Long on a close above the Donchian upper band while the Choppiness Index is low, exit at the stop or 3R",
+        parameters: &[
+            SYMBOL,
+            MODE,
+            ParameterSpec::new("donchian_length", ParameterKind::Integer, Some("20"), "Donchian channel length"),
+            ParameterSpec::new("donchian_offset", ParameterKind::Integer, Some("20"), "Donchian channel offset"),
+            ParameterSpec::new("chop_length", ParameterKind::Integer, Some("100"), "Choppiness Index length"),
+            ParameterSpec::new("chop_threshold", ParameterKind::Number, Some("50"), "maximum Choppiness Index to enter"),
+            ParameterSpec::new(
+                "stop",
+                ParameterKind::Text,
+                Some("atr(14, 1.5)"),
+                "atr(length, multiplier), chandelier(length, atr_length, multiplier), parabolic_sar(start, increment, maximum) or swing(left, right)",
+            ),
+            ParameterSpec::new("max_units", ParameterKind::Integer, Some("1"), "maximum number of units"),
+            ParameterSpec::new("add_step", ParameterKind::Number, Some("0.5"), "move in ATR before adding a unit"),
+            ParameterSpec::new("atr_length", ParameterKind::Integer, Some("14"), "ATR length for adding units"),
+            ParameterSpec::new(
+                "take_profits",
+                ParameterKind::Text,
+                Some("3:1"),
+                "partial exits as r_multiple:fraction of the open quantity, comma separated",
+            ),
+        ],
+        build: |parameters| {
+            let stop: StopConfig = parameters.text("stop")?.parse().map_err(|reason: String| Error::invalid_parameter("stop", reason))?;
+            let rules = PositionRules::new(
+                parameters.integer("max_units")?,
+                parameters.number("add_step")?,
+                PositionRules::parse_take_profits(parameters.text("take_profits")?)?,
+            )?;
+
+            Ok(Box::new(
+                ChoppinessDonchianAtrStrategy::new(mode(parameters)?, parameters.text("symbol")?)
+                    .with_donchian(parameters.integer("donchian_length")?, parameters.integer("donchian_offset")?)
+                    .with_choppiness(parameters.integer("chop_length")?, parameters.number("chop_threshold")?)
+                    .with_stop(stop)
+                    .with_position_rules(rules)
+                    .with_atr_length(parameters.integer("atr_length")?),
+            ))
+        },
+    },
+    StrategyEntry {
+        name: "rules",
+        description: "Strategy defined by a rule file (entry_long, exit_long, stop_long and their short counterparts)",
+        parameters: &[SYMBOL, ParameterSpec::new("file", ParameterKind::Text, None, "path of the rule file")],
+        build: |parameters| {
+            let path = parameters.text("file")?;
+            let text = fs::read_to_string(path)
+                .map_err(|error| Error::invalid_parameter("file", format!("cannot read {}: {}", path, error)))?;
+            Ok(Box::new(RuleStrategy::parse(&text)?.with_symbol(parameters.text("symbol")?)))
+        },
+    },
+    StrategyEntry {
+        name: "turtle_system_1",
+        description: "Turtle System 1: 20-bar Donchian breakout, 10-bar exit, ATR-sized units added every 0.5 N",
+        parameters: TURTLE,
+        build: |parameters| turtle(TurtleSystem::System1, parameters),
+    },
+    StrategyEntry {
+        name: "turtle_system_2",
+        description: "Turtle System 2: 55-bar Donchian breakout, 20-bar exit, ATR-sized units added every 0.5 N",
+        parameters: TURTLE,
+        build: |parameters| turtle(TurtleSystem::System2, parameters),
+    },
+    StrategyEntry {
+        name: "ma_crossover",
+        description: "Long when the fast moving average crosses above the slow one, exit on the opposite cross",
+        parameters: &[
+            SYMBOL,
+            ParameterSpec::new("average", ParameterKind::Text, Some("ema"), "moving average (sma, ema, rma, wma, hma, dema, tema, kama, vwma)"),
+            ParameterSpec::new("fast", ParameterKind::Integer, Some("20"), "fast period"),
+            ParameterSpec::new("slow", ParameterKind::Integer, Some("50"), "slow period"),
+        ],
+        build: |parameters| {
+            let strategy = reference_strategies::moving_average_crossover(
+                parameters.text("average")?,
+                parameters.integer("fast")?,
+                parameters.integer("slow")?,
+            )?;
+            Ok(Box::new(strategy.with_symbol(parameters.text("symbol")?)))
+        },
+    },
+    StrategyEntry {
+        name: "bollinger_mean_reversion",
+        description: "Long on a close below the lower Bollinger band, exit above the basis",
+        parameters: &[
+            SYMBOL,
+            ParameterSpec::new("length", ParameterKind::Integer, Some("20"), "Bollinger length"),
+            ParameterSpec::new("multiplier", ParameterKind::Number, Some("2"), "standard deviation multiplier"),
+        ],
+        build: |parameters| {
+            let strategy = reference_strategies::bollinger_mean_reversion(
+                parameters.integer("length")?,
+                parameters.number("multiplier")?,
+            )?;
+            Ok(Box::new(strategy.with_symbol(parameters.text("symbol")?)))
+        },
+    },
+    StrategyEntry {
+        name: "rsi_reversal",
+        description: "Long when the RSI leaves the oversold zone, exit in the overbought zone",
+        parameters: &[
+            SYMBOL,
+            ParameterSpec::new("period", ParameterKind::Integer, Some("14"), "RSI period"),
+            ParameterSpec::new("oversold", ParameterKind::Number, Some("30"), "oversold threshold"),
+            ParameterSpec::new("overbought", ParameterKind::Number, Some("70"), "overbought threshold"),
+        ],
+        build: |parameters| {
+            let strategy = reference_strategies::rsi_reversal(
+                parameters.integer("period")?,
+                parameters.number("oversold")?,
+                parameters.number("overbought")?,
+            )?;
+            Ok(Box::new(strategy.with_symbol(parameters.text("symbol")?)))
+        },
+    },
+    StrategyEntry {
+        name: "keltner_squeeze",
+        description: "Long on a close above the Keltner channel after Bollinger bands squeezed inside it, exit below the basis",
+        parameters: &[
+            SYMBOL,
+            ParameterSpec::new("length", ParameterKind::Integer, Some("20"), "Bollinger and Keltner length"),
+            ParameterSpec::new("bollinger_multiplier", ParameterKind::Number, Some("2"), "Bollinger standard deviation multiplier"),
+            ParameterSpec::new("keltner_multiplier", ParameterKind::Number, Some("1.5"), "Keltner ATR multiplier"),
+        ],
+        build: |parameters| {
+            let strategy = reference_strategies::keltner_squeeze_breakout(
+                parameters.integer("length")?,
+                parameters.number("bollinger_multiplier")?,
+                parameters.number("keltner_multiplier")?,
+            )?;
+            Ok(Box::new(strategy.with_symbol(parameters.text("symbol")?)))
+        },
+    },
+];
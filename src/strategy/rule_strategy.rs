@@ -0,0 +1,368 @@
+use binance::model::KlineSummary;
+
+use crate::indicator::registry::{self, Argument};
+use crate::indicator::{IndicatorSpec, PriceSource, Side};
+use crate::{Error, Result};
+
+use super::execution::{Executor, Mode};
+use super::intent::{Intent, MarketState, SizeHint};
+use super::position::{Position, PositionRules};
+use super::rules::{BinaryOp, Expr, RuleSet, UnaryOp};
+use super::series_graph::SeriesSpec;
+use super::trade::Trade;
+use super::{KlineManager, SignalStrategy, TradingStrategy};
+
+// Nombre de bougies passées aux indicateurs avant la première évaluation des règles
+const WARMUP_KLINES: usize = 100;
+const DEFAULT_SYMBOL: &str = "ETHBTC";
+
+// Expression numérique compilée
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Constant(f64),
+    Price(PriceSource),
+    Series { name: String, output: &'static str },
+    Offset(Box<Value>, usize),
+    Negate(Box<Value>),
+    Arithmetic(BinaryOp, Box<Value>, Box<Value>),
+}
+
+// Expression booléenne compilée. Une comparaison avec une valeur indéfinie (NaN pendant
+// l'initialisation des indicateurs) est fausse.
+#[derive(Debug, Clone, PartialEq)]
+enum Condition {
+    Constant(bool),
+    Compare(BinaryOp, Value, Value),
+    CrossesAbove(Value, Value),
+    CrossesBelow(Value, Value),
+    Offset(Box<Condition>, usize),
+    Not(Box<Condition>),
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+}
+
+enum Compiled {
+    Value(Value),
+    Condition(Condition),
+}
+
+// Résolution des expressions contre le registre d'indicateurs. Chaque indicateur distinct
+// devient une série du `KlineManager`, nommée d'après ses paramètres.
+#[derive(Default)]
+struct Compiler {
+    indicators: Vec<IndicatorSpec>,
+}
+
+impl Compiler {
+    fn value(&mut self, expr: &Expr) -> std::result::Result<Value, String> {
+        match self.compile(expr)? {
+            Compiled::Value(value) => Ok(value),
+            Compiled::Condition(_) => Err("expected a number, found a condition".to_string()),
+        }
+    }
+
+    fn condition(&mut self, expr: &Expr) -> std::result::Result<Condition, String> {
+        match self.compile(expr)? {
+            Compiled::Condition(condition) => Ok(condition),
+            Compiled::Value(_) => Err("expected a condition, found a number".to_string()),
+        }
+    }
+
+    fn compile(&mut self, expr: &Expr) -> std::result::Result<Compiled, String> {
+        Ok(match expr {
+            Expr::Number(value) => Compiled::Value(Value::Constant(*value)),
+            Expr::Bool(value) => Compiled::Condition(Condition::Constant(*value)),
+            Expr::Identifier(name) => Compiled::Value(Value::Price(source(name)?)),
+            Expr::Call { name, arguments } if name == "crosses_above" || name == "crosses_below" => {
+                let [a, b] = arguments.as_slice() else {
+                    return Err(format!("{} expects 2 arguments, got {}", name, arguments.len()));
+                };
+                let (a, b) = (self.value(a)?, self.value(b)?);
+                Compiled::Condition(if name == "crosses_above" {
+                    Condition::CrossesAbove(a, b)
+                } else {
+                    Condition::CrossesBelow(a, b)
+                })
+            }
+            Expr::Call { name, .. } => {
+                let spec = self.indicator(expr)?;
+                let entry = registry::find_indicator(name).ok_or_else(|| format!("unknown indicator '{}'", name))?;
+                // Sans `.sortie`, la première sortie de l'indicateur
+                Compiled::Value(Value::Series { name: spec.key(), output: entry.outputs[0] })
+            }
+            Expr::Output { target, output } => {
+                let Expr::Call { name, .. } = target.as_ref() else {
+                    return Err(format!("only indicators have outputs, found .{}", output));
+                };
+                let spec = self.indicator(target)?;
+                let entry = registry::find_indicator(name).ok_or_else(|| format!("unknown indicator '{}'", name))?;
+                let output = entry.outputs.iter().find(|candidate| **candidate == output).ok_or_else(|| {
+                    format!("{} has no output '{}' (outputs: {})", name, output, entry.outputs.join(", "))
+                })?;
+                Compiled::Value(Value::Series { name: spec.key(), output })
+            }
+            Expr::Offset { target, bars } => match self.compile(target)? {
+                Compiled::Value(value) => Compiled::Value(Value::Offset(Box::new(value), *bars)),
+                Compiled::Condition(condition) => Compiled::Condition(Condition::Offset(Box::new(condition), *bars)),
+            },
+            Expr::Unary { op: UnaryOp::Negate, operand } => Compiled::Value(match self.value(operand)? {
+                Value::Constant(value) => Value::Constant(-value),
+                value => Value::Negate(Box::new(value)),
+            }),
+            Expr::Unary { op: UnaryOp::Not, operand } => Compiled::Condition(Condition::Not(Box::new(self.condition(operand)?))),
+            Expr::Binary { op, left, right } => match op {
+                BinaryOp::And | BinaryOp::Or => {
+                    let (left, right) = (Box::new(self.condition(left)?), Box::new(self.condition(right)?));
+                    Compiled::Condition(if *op == BinaryOp::And {
+                        Condition::And(left, right)
+                    } else {
+                        Condition::Or(left, right)
+                    })
+                }
+                BinaryOp::Add | BinaryOp::Subtract | BinaryOp::Multiply | BinaryOp::Divide => {
+                    Compiled::Value(Value::Arithmetic(*op, Box::new(self.value(left)?), Box::new(self.value(right)?)))
+                }
+                _ => Compiled::Condition(Condition::Compare(*op, self.value(left)?, self.value(right)?)),
+            },
+        })
+    }
+
+    // Indicateur d'un appel : les arguments sont des nombres ou des sources de prix
+    fn indicator(&mut self, expr: &Expr) -> std::result::Result<IndicatorSpec, String> {
+        let Expr::Call { name, arguments } = expr else {
+            return Err(format!("expected an indicator, found {:?}", expr));
+        };
+
+        let arguments = arguments
+            .iter()
+            .map(|argument| match argument {
+                Expr::Number(value) => Ok(Argument::Number(*value)),
+                Expr::Unary { op: UnaryOp::Negate, operand } => match operand.as_ref() {
+                    Expr::Number(value) => Ok(Argument::Number(-value)),
+                    _ => Err(format!("{}: arguments must be numbers or price sources", name)),
+                },
+                Expr::Identifier(name) => Ok(Argument::Source(source(name)?)),
+                _ => Err(format!("{}: arguments must be numbers or price sources", name)),
+            })
+            .collect::<std::result::Result<Vec<_>, String>>()?;
+
+        let spec = registry::resolve(name, &arguments).map_err(|error| error.to_string())?;
+        // Paramètres validés dès la compilation par les constructeurs des indicateurs
+        spec.node().map_err(|error| error.to_string())?;
+        if !self.indicators.contains(&spec) {
+            self.indicators.push(spec);
+        }
+        Ok(spec)
+    }
+}
+
+fn source(name: &str) -> std::result::Result<PriceSource, String> {
+    name.parse().map_err(|_| format!("unknown identifier '{}'", name))
+}
+
+// Lecture des bougies et des séries d'indicateurs, `ago` bougies avant la dernière
+struct Bars<'a> {
+    manager: &'a KlineManager,
+}
+
+impl Bars<'_> {
+    fn value(&self, value: &Value, ago: usize) -> f64 {
+        match value {
+            Value::Constant(value) => *value,
+            Value::Price(source) => self.manager.klines.ago(ago).map_or(f64::NAN, |kline| source.extract(kline)),
+            Value::Series { name, output } => self
+                .manager
+                .get_series(name, output)
+                .and_then(|series| series.len().checked_sub(ago + 1).map(|index| series[index]))
+                .unwrap_or(f64::NAN),
+            Value::Offset(value, bars) => self.value(value, ago + bars),
+            Value::Negate(value) => -self.value(value, ago),
+            Value::Arithmetic(op, left, right) => {
+                let (left, right) = (self.value(left, ago), self.value(right, ago));
+                match op {
+                    BinaryOp::Add => left + right,
+                    BinaryOp::Subtract => left - right,
+                    BinaryOp::Multiply => left * right,
+                    _ => left / right,
+                }
+            }
+        }
+    }
+
+    fn condition(&self, condition: &Condition, ago: usize) -> bool {
+        match condition {
+            Condition::Constant(value) => *value,
+            Condition::Compare(op, left, right) => {
+                let (left, right) = (self.value(left, ago), self.value(right, ago));
+                match op {
+                    BinaryOp::Less => left < right,
+                    BinaryOp::LessEqual => left <= right,
+                    BinaryOp::Greater => left > right,
+                    BinaryOp::GreaterEqual => left >= right,
+                    BinaryOp::Equal => left == right,
+                    // NaN != x est vrai : une valeur indéfinie ne valide aucune comparaison
+                    _ => !left.is_nan() && !right.is_nan() && left != right,
+                }
+            }
+            // Comme `ta.crossover` : a passe au-dessus de b sur la dernière bougie
+            Condition::CrossesAbove(a, b) => {
+                self.value(a, ago) > self.value(b, ago) && self.value(a, ago + 1) <= self.value(b, ago + 1)
+            }
+            Condition::CrossesBelow(a, b) => {
+                self.value(a, ago) < self.value(b, ago) && self.value(a, ago + 1) >= self.value(b, ago + 1)
+            }
+            Condition::Offset(condition, bars) => self.condition(condition, ago + bars),
+            Condition::Not(condition) => !self.condition(condition, ago),
+            Condition::And(left, right) => self.condition(left, ago) && self.condition(right, ago),
+            Condition::Or(left, right) => self.condition(left, ago) || self.condition(right, ago),
+        }
+    }
+}
+
+// Règles compilées pour un sens de position
+#[derive(Debug, Clone)]
+struct SideRules {
+    entry: Option<Condition>,
+    exit: Option<Condition>,
+    stop: Option<Value>,
+}
+
+// Stratégie définie par des règles (voir `rules`) : à chaque bougie, sortie si la règle de sortie
+// est vraie, sinon entrée avec tout le capital à la clôture si une règle d'entrée est vraie (long en
+// priorité). Le stop est fixé à l'entrée et contrôlé par la couche d'exécution dès la bougie
+// d'entrée. Une règle de stop indéfinie empêche l'entrée.
+#[derive(Debug, Clone)]
+pub struct RuleStrategy {
+    name: String,
+    long: SideRules,
+    short: SideRules,
+    indicators: Vec<IndicatorSpec>,
+    executor: Executor,
+}
+
+impl RuleStrategy {
+    pub fn new(rules: &RuleSet) -> Result<Self> {
+        let mut compiler = Compiler::default();
+        let mut condition = |rule: &str, expr: &Option<Expr>| {
+            expr.as_ref()
+                .map(|expr| compiler.condition(expr).map_err(|reason| Error::invalid_rule(rule, reason)))
+                .transpose()
+        };
+        let entry_long = condition("entry_long", &rules.entry_long)?;
+        let exit_long = condition("exit_long", &rules.exit_long)?;
+        let entry_short = condition("entry_short", &rules.entry_short)?;
+        let exit_short = condition("exit_short", &rules.exit_short)?;
+
+        let mut value = |rule: &str, expr: &Option<Expr>| {
+            expr.as_ref()
+                .map(|expr| compiler.value(expr).map_err(|reason| Error::invalid_rule(rule, reason)))
+                .transpose()
+        };
+        let stop_long = value("stop_long", &rules.stop_long)?;
+        let stop_short = value("stop_short", &rules.stop_short)?;
+
+        Ok(Self {
+            name: rules.name.clone().unwrap_or_else(|| "rules".to_string()),
+            long: SideRules { entry: entry_long, exit: exit_long, stop: stop_long },
+            short: SideRules { entry: entry_short, exit: exit_short, stop: stop_short },
+            indicators: compiler.indicators,
+            executor: Executor::new(Mode::Backtest, DEFAULT_SYMBOL, 1.0),
+        })
+    }
+
+    // Compiler un texte de règles
+    pub fn parse(text: &str) -> Result<Self> {
+        Self::new(&RuleSet::parse(text)?)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // Indicateurs utilisés par les règles, chacun une seule fois
+    pub fn indicators(&self) -> &[IndicatorSpec] {
+        &self.indicators
+    }
+
+    pub fn with_symbol(mut self, symbol: &str) -> Self {
+        self.executor = Executor::new(self.executor.mode(), symbol, self.executor.capital());
+        self
+    }
+
+    pub fn position(&self) -> Option<&Position> {
+        self.executor.position()
+    }
+
+    pub fn trades(&self) -> &[Trade] {
+        self.executor.trades()
+    }
+
+    pub fn executor(&self) -> &Executor {
+        &self.executor
+    }
+
+    fn rules(&self, side: Side) -> &SideRules {
+        match side {
+            Side::Long => &self.long,
+            Side::Short => &self.short,
+        }
+    }
+}
+
+impl TradingStrategy for RuleStrategy {
+    fn warmup(&self) -> usize {
+        WARMUP_KLINES
+    }
+
+    fn prepare(&self, klines: &[KlineSummary]) -> Result<KlineManager> {
+        if klines.len() < WARMUP_KLINES {
+            return Err(Error::InsufficientData { required: WARMUP_KLINES, available: klines.len() });
+        }
+
+        let mut manager = KlineManager::new(klines[..WARMUP_KLINES].to_vec(), Vec::new());
+        let specs = self
+            .indicators
+            .iter()
+            .map(|indicator| Ok(SeriesSpec { name: indicator.key(), node: indicator.node()?, inputs: Vec::new() }))
+            .collect::<Result<Vec<_>>>()?;
+        manager.add_series(specs)?;
+        Ok(manager)
+    }
+
+    fn execute(&mut self, kline: KlineSummary, manager: &mut KlineManager) -> Result<()> {
+        manager.add_kline(kline.clone());
+        let market = MarketState::new(&kline, manager, self.executor.position())?;
+        let (close, intents) = (market.close, self.signals(&market)?);
+        // Une unité par position, sans prise de profit
+        self.executor.apply(kline.open_time, close, &intents, &PositionRules::default())?;
+        Ok(())
+    }
+}
+
+impl SignalStrategy for RuleStrategy {
+    fn signals(&self, market: &MarketState) -> Result<Vec<Intent>> {
+        let bars = Bars { manager: market.manager };
+
+        if let Some(position) = market.position {
+            let side = position.side();
+            let exit = self.rules(side).exit.as_ref().is_some_and(|exit| bars.condition(exit, 0));
+            return Ok(if exit { vec![Intent::Exit { side }] } else { Vec::new() });
+        }
+
+        for side in [Side::Long, Side::Short] {
+            let rules = self.rules(side);
+            if !rules.entry.as_ref().is_some_and(|entry| bars.condition(entry, 0)) {
+                continue;
+            }
+
+            let stop = rules.stop.as_ref().map(|stop| bars.value(stop, 0));
+            if stop.is_some_and(f64::is_nan) {
+                continue;
+            }
+            // Tout le capital disponible est engagé, quel que soit le prix de l'actif
+            return Ok(vec![Intent::Enter { side, stop, size: SizeHint::Capital(1.0) }]);
+        }
+
+        Ok(Vec::new())
+    }
+}
@@ -0,0 +1,386 @@
+use std::str::FromStr;
+
+use crate::{Error, Result};
+
+// Langage de règles de stratégie. Une règle par ligne, `clé: expression` :
+//
+//     # Cassure du canal de Donchian hors des marchés sans tendance
+//     name: donchian_chop
+//     entry_long: crosses_above(close, donchian(20, 20).upper) and chop(100) <= 50
+//     stop_long: atr_stop(14, 1.5).long
+//     exit_long: crosses_below(close, donchian(20, 20).lower)
+//
+// Les lignes indentées prolongent la règle précédente, `#` commence un commentaire.
+// Expressions : nombres, sources de prix (open, high, low, close, volume, hl2, hlc3, ohlc4),
+// indicateurs du registre (`rsi(14)`, `bollinger(20, 2).upper`), valeur d'il y a n bougies
+// (`close[1]`), + - * /, comparaisons (< <= > >= == !=), and / or / not, true / false,
+// crosses_above(a, b) et crosses_below(a, b).
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+    Negate,
+    Not,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Equal,
+    NotEqual,
+    And,
+    Or,
+}
+
+// Expression d'une règle, avant résolution des indicateurs
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Number(f64),
+    Bool(bool),
+    Identifier(String),
+    Call { name: String, arguments: Vec<Expr> },
+    Output { target: Box<Expr>, output: String }, // donchian(20, 20).upper
+    Offset { target: Box<Expr>, bars: usize },    // close[1]
+    Unary { op: UnaryOp, operand: Box<Expr> },
+    Binary { op: BinaryOp, left: Box<Expr>, right: Box<Expr> },
+}
+
+impl Expr {
+    // Les erreurs indiquent la colonne (à partir de 1) dans `text`
+    pub fn parse(text: &str) -> std::result::Result<Expr, String> {
+        let tokens = tokenize(text)?;
+        let mut parser = Parser { tokens, position: 0, end: text.chars().count() + 1 };
+        let expr = parser.or()?;
+        match parser.peek() {
+            None => Ok(expr),
+            Some((token, column)) => Err(format!("unexpected {} at column {}", token, column)),
+        }
+    }
+}
+
+// Règles d'une stratégie. Sans règle de sortie, une position n'est fermée que par son stop.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RuleSet {
+    pub name: Option<String>,
+    pub entry_long: Option<Expr>,
+    pub exit_long: Option<Expr>,
+    pub stop_long: Option<Expr>, // Niveau de stop calculé à l'entrée
+    pub entry_short: Option<Expr>,
+    pub exit_short: Option<Expr>,
+    pub stop_short: Option<Expr>,
+}
+
+impl RuleSet {
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut rules: Vec<(String, String)> = Vec::new();
+
+        for line in text.lines() {
+            let content = line.split('#').next().unwrap_or("");
+            if content.trim().is_empty() {
+                continue;
+            }
+
+            if content.starts_with([' ', '\t']) {
+                let (_, expression) = rules
+                    .last_mut()
+                    .ok_or_else(|| Error::invalid_rule(content.trim(), "continuation line without a rule"))?;
+                expression.push(' ');
+                expression.push_str(content.trim());
+                continue;
+            }
+
+            let (key, expression) = content
+                .split_once(':')
+                .ok_or_else(|| Error::invalid_rule(content.trim(), "expected `key: expression`"))?;
+            let key = key.trim();
+            if rules.iter().any(|(other, _)| other == key) {
+                return Err(Error::invalid_rule(key, "defined twice"));
+            }
+            rules.push((key.to_string(), expression.trim().to_string()));
+        }
+
+        let mut rule_set = RuleSet::default();
+        for (key, expression) in rules {
+            if key == "name" {
+                rule_set.name = Some(expression);
+                continue;
+            }
+
+            let slot = match key.as_str() {
+                "entry_long" => &mut rule_set.entry_long,
+                "exit_long" => &mut rule_set.exit_long,
+                "stop_long" => &mut rule_set.stop_long,
+                "entry_short" => &mut rule_set.entry_short,
+                "exit_short" => &mut rule_set.exit_short,
+                "stop_short" => &mut rule_set.stop_short,
+                _ => return Err(Error::invalid_rule(&key, "unknown rule")),
+            };
+            *slot = Some(Expr::parse(&expression).map_err(|reason| Error::invalid_rule(&key, reason))?);
+        }
+
+        if rule_set.entry_long.is_none() && rule_set.entry_short.is_none() {
+            return Err(Error::invalid_rule("entry_long", "at least one entry rule is required"));
+        }
+        Ok(rule_set)
+    }
+}
+
+impl FromStr for RuleSet {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self> {
+        Self::parse(text)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Identifier(String),
+    Symbol(&'static str),
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Number(value) => write!(f, "number {}", value),
+            Token::Identifier(name) => write!(f, "'{}'", name),
+            Token::Symbol(symbol) => write!(f, "'{}'", symbol),
+        }
+    }
+}
+
+const SYMBOLS: [&str; 15] = ["<=", ">=", "==", "!=", "<", ">", "+", "-", "*", "/", "(", ")", "[", "]", ","];
+
+// Jetons de l'expression, avec leur colonne
+fn tokenize(text: &str) -> std::result::Result<Vec<(Token, usize)>, String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let column = i + 1;
+        let c = chars[i];
+
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let literal: String = chars[start..i].iter().collect();
+            let value = literal.parse().map_err(|_| format!("invalid number '{}' at column {}", literal, column))?;
+            tokens.push((Token::Number(value), column));
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push((Token::Identifier(chars[start..i].iter().collect()), column));
+        } else if c == '.' {
+            tokens.push((Token::Symbol("."), column));
+            i += 1;
+        } else {
+            let rest: String = chars[i..chars.len().min(i + 2)].iter().collect();
+            let symbol = SYMBOLS
+                .iter()
+                .find(|symbol| rest.starts_with(**symbol))
+                .ok_or_else(|| format!("unexpected character '{}' at column {}", c, column))?;
+            tokens.push((Token::Symbol(symbol), column));
+            i += symbol.len();
+        }
+    }
+
+    Ok(tokens)
+}
+
+// Analyse par descente récursive, de la priorité la plus faible à la plus forte :
+// or, and, not, comparaison, + -, * /, - unaire, .sortie et [décalage], terme
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    position: usize,
+    end: usize, // Colonne de fin de l'expression
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&(Token, usize)> {
+        self.tokens.get(self.position)
+    }
+
+    fn column(&self) -> usize {
+        self.peek().map_or(self.end, |(_, column)| *column)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).map(|(token, _)| token.clone());
+        self.position += 1;
+        token
+    }
+
+    fn accept_symbol(&mut self, symbol: &str) -> bool {
+        if matches!(self.peek(), Some((Token::Symbol(candidate), _)) if *candidate == symbol) {
+            self.position += 1;
+            return true;
+        }
+        false
+    }
+
+    fn accept_keyword(&mut self, keyword: &str) -> bool {
+        if matches!(self.peek(), Some((Token::Identifier(name), _)) if name == keyword) {
+            self.position += 1;
+            return true;
+        }
+        false
+    }
+
+    fn expect_symbol(&mut self, symbol: &str) -> std::result::Result<(), String> {
+        if self.accept_symbol(symbol) {
+            return Ok(());
+        }
+        Err(match self.peek() {
+            Some((token, column)) => format!("expected '{}', found {} at column {}", symbol, token, column),
+            None => format!("expected '{}' at end of expression", symbol),
+        })
+    }
+
+    fn binary(op: BinaryOp, left: Expr, right: Expr) -> Expr {
+        Expr::Binary { op, left: Box::new(left), right: Box::new(right) }
+    }
+
+    fn or(&mut self) -> std::result::Result<Expr, String> {
+        let mut expr = self.and()?;
+        while self.accept_keyword("or") {
+            expr = Self::binary(BinaryOp::Or, expr, self.and()?);
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> std::result::Result<Expr, String> {
+        let mut expr = self.not()?;
+        while self.accept_keyword("and") {
+            expr = Self::binary(BinaryOp::And, expr, self.not()?);
+        }
+        Ok(expr)
+    }
+
+    fn not(&mut self) -> std::result::Result<Expr, String> {
+        if self.accept_keyword("not") {
+            return Ok(Expr::Unary { op: UnaryOp::Not, operand: Box::new(self.not()?) });
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> std::result::Result<Expr, String> {
+        let left = self.sum()?;
+        let operators = [
+            ("<=", BinaryOp::LessEqual),
+            (">=", BinaryOp::GreaterEqual),
+            ("==", BinaryOp::Equal),
+            ("!=", BinaryOp::NotEqual),
+            ("<", BinaryOp::Less),
+            (">", BinaryOp::Greater),
+        ];
+        for (symbol, op) in operators {
+            if self.accept_symbol(symbol) {
+                return Ok(Self::binary(op, left, self.sum()?));
+            }
+        }
+        Ok(left)
+    }
+
+    fn sum(&mut self) -> std::result::Result<Expr, String> {
+        let mut expr = self.product()?;
+        loop {
+            if self.accept_symbol("+") {
+                expr = Self::binary(BinaryOp::Add, expr, self.product()?);
+            } else if self.accept_symbol("-") {
+                expr = Self::binary(BinaryOp::Subtract, expr, self.product()?);
+            } else {
+                return Ok(expr);
+            }
+        }
+    }
+
+    fn product(&mut self) -> std::result::Result<Expr, String> {
+        let mut expr = self.unary()?;
+        loop {
+            if self.accept_symbol("*") {
+                expr = Self::binary(BinaryOp::Multiply, expr, self.unary()?);
+            } else if self.accept_symbol("/") {
+                expr = Self::binary(BinaryOp::Divide, expr, self.unary()?);
+            } else {
+                return Ok(expr);
+            }
+        }
+    }
+
+    fn unary(&mut self) -> std::result::Result<Expr, String> {
+        if self.accept_symbol("-") {
+            return Ok(Expr::Unary { op: UnaryOp::Negate, operand: Box::new(self.unary()?) });
+        }
+        self.postfix()
+    }
+
+    fn postfix(&mut self) -> std::result::Result<Expr, String> {
+        let mut expr = self.term()?;
+        loop {
+            if self.accept_symbol(".") {
+                let column = self.column();
+                match self.next() {
+                    Some(Token::Identifier(output)) => expr = Expr::Output { target: Box::new(expr), output },
+                    _ => return Err(format!("expected an output name at column {}", column)),
+                }
+            } else if self.accept_symbol("[") {
+                let column = self.column();
+                let bars = match self.next() {
+                    Some(Token::Number(bars)) if bars.fract() == 0.0 => bars as usize,
+                    _ => return Err(format!("expected a number of bars at column {}", column)),
+                };
+                self.expect_symbol("]")?;
+                expr = Expr::Offset { target: Box::new(expr), bars };
+            } else {
+                return Ok(expr);
+            }
+        }
+    }
+
+    fn term(&mut self) -> std::result::Result<Expr, String> {
+        let column = self.column();
+        match self.next() {
+            Some(Token::Number(value)) => Ok(Expr::Number(value)),
+            Some(Token::Identifier(name)) => match name.as_str() {
+                "true" => Ok(Expr::Bool(true)),
+                "false" => Ok(Expr::Bool(false)),
+                "and" | "or" | "not" => Err(format!("unexpected '{}' at column {}", name, column)),
+                _ if self.accept_symbol("(") => {
+                    let mut arguments = Vec::new();
+                    if !self.accept_symbol(")") {
+                        loop {
+                            arguments.push(self.or()?);
+                            if self.accept_symbol(")") {
+                                break;
+                            }
+                            self.expect_symbol(",")?;
+                        }
+                    }
+                    Ok(Expr::Call { name, arguments })
+                }
+                _ => Ok(Expr::Identifier(name)),
+            },
+            Some(Token::Symbol("(")) => {
+                let expr = self.or()?;
+                self.expect_symbol(")")?;
+                Ok(expr)
+            }
+            Some(token) => Err(format!("unexpected {} at column {}", token, column)),
+            None => Err("unexpected end of expression".to_string()),
+        }
+    }
+}
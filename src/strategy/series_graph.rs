@@ -0,0 +1,222 @@
+use binance::model::KlineSummary;
+
+use crate::data::RingBuffer;
+use crate::indicator::{PriceSource, SeriesNode, Source};
+use crate::{Error, Result};
+
+// Indicateur de série à ajouter au graphe, sous un nom unique
+#[derive(Debug)]
+pub struct SeriesSpec {
+    pub name: String,
+    pub node: Box<dyn SeriesNode>,
+    pub inputs: Vec<Source>,
+}
+
+impl SeriesSpec {
+    pub fn new(name: &str, node: impl SeriesNode + 'static, inputs: Vec<Source>) -> Self {
+        Self {
+            name: name.to_string(),
+            node: Box::new(node),
+            inputs,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Input {
+    Price(PriceSource),
+    Series { entry: usize, output: usize },
+}
+
+#[derive(Debug)]
+struct Entry {
+    name: String,
+    node: Box<dyn SeriesNode>,
+    inputs: Vec<Input>,
+    outputs: Vec<RingBuffer<f64>>, // Une série par sortie, une valeur par bougie
+}
+
+// Graphe de dépendances des indicateurs de séries. Les entrées sont conservées dans l'ordre
+// topologique : chaque indicateur est calculé après ceux dont il lit les sorties.
+#[derive(Debug, Default)]
+pub struct SeriesGraph {
+    entries: Vec<Entry>,
+    buffer: Vec<f64>,
+    capacity: Option<usize>, // Nombre de valeurs conservées par série
+}
+
+impl SeriesGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Ajouter des indicateurs dans n'importe quel ordre et calculer leur historique sur `klines`.
+    // Échoue sans rien ajouter si un nom est dupliqué, une source inconnue ou si les dépendances forment un cycle.
+    pub fn add(&mut self, specs: Vec<SeriesSpec>, klines: &[KlineSummary]) -> Result<()> {
+        for (index, spec) in specs.iter().enumerate() {
+            if self.position(&spec.name).is_some() || specs[..index].iter().any(|other| other.name == spec.name) {
+                return Err(Error::invalid_parameter("name", format!("series '{}' already exists", spec.name)));
+            }
+            if spec.inputs.len() != spec.node.inputs() {
+                return Err(Error::invalid_parameter(
+                    "inputs",
+                    format!("series '{}' expects {} inputs, got {}", spec.name, spec.node.inputs(), spec.inputs.len()),
+                ));
+            }
+        }
+        for source in specs.iter().flat_map(|spec| &spec.inputs) {
+            if let Source::Series { name, output } = source {
+                let outputs = match self.position(name) {
+                    Some(entry) => Some(self.entries[entry].node.outputs()),
+                    None => specs.iter().find(|spec| spec.name == *name).map(|spec| spec.node.outputs()),
+                };
+                if outputs.is_some_and(|outputs| !outputs.contains(output)) {
+                    return Err(Error::invalid_parameter("inputs", format!("series '{}' has no output '{}'", name, output)));
+                }
+            }
+        }
+        for spec in self.sort(specs)? {
+            let inputs = spec.inputs.iter().map(|source| self.resolve(source)).collect::<Result<Vec<_>>>()?;
+            let outputs = (0..spec.node.outputs().len()).map(|_| RingBuffer::bounded(self.capacity)).collect();
+            self.entries.push(Entry { name: spec.name, node: spec.node, inputs, outputs });
+
+            let index = self.entries.len() - 1;
+            for end in 1..=klines.len() {
+                self.step(index, &klines[..end], klines.len() - end);
+            }
+        }
+
+        Ok(())
+    }
+
+    // Calculer tous les indicateurs pour la dernière bougie de `klines`
+    pub fn update(&mut self, klines: &[KlineSummary]) {
+        for index in 0..self.entries.len() {
+            self.step(index, klines, 0);
+        }
+    }
+
+    // Plus grand historique de bougies demandé par les indicateurs
+    pub fn lookback(&self) -> usize {
+        self.entries.iter().map(|entry| entry.node.lookback()).max().unwrap_or(1)
+    }
+
+    // Ne conserver que les `capacity` dernières valeurs de chaque série (None : tout l'historique)
+    pub fn set_retention(&mut self, capacity: Option<usize>) {
+        self.capacity = capacity;
+        for entry in self.entries.iter_mut() {
+            entry.node.set_retention(capacity);
+            for series in entry.outputs.iter_mut() {
+                series.set_capacity(capacity);
+            }
+        }
+    }
+
+    // Série d'une sortie d'un indicateur
+    pub fn get(&self, name: &str, output: &str) -> Option<&[f64]> {
+        let entry = &self.entries[self.position(name)?];
+        let output = entry.node.outputs().iter().position(|candidate| *candidate == output)?;
+        Some(&entry.outputs[output])
+    }
+
+    // Noms des indicateurs, dans l'ordre de calcul
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|entry| entry.name.as_str())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.entries.iter().position(|entry| entry.name == name)
+    }
+
+    fn resolve(&self, source: &Source) -> Result<Input> {
+        match source {
+            Source::Price(price) => Ok(Input::Price(*price)),
+            Source::Series { name, output } => {
+                let entry = self
+                    .position(name)
+                    .ok_or_else(|| Error::invalid_parameter("inputs", format!("unknown series '{}'", name)))?;
+                let output_index = self.entries[entry]
+                    .node
+                    .outputs()
+                    .iter()
+                    .position(|candidate| candidate == output)
+                    .ok_or_else(|| Error::invalid_parameter("inputs", format!("series '{}' has no output '{}'", name, output)))?;
+                Ok(Input::Series { entry, output: output_index })
+            }
+        }
+    }
+
+    // Tri topologique (Kahn) des nouveaux indicateurs : chacun vient après les séries qu'il lit.
+    // Échoue si une source est inconnue ou si les dépendances forment un cycle.
+    fn sort(&self, specs: Vec<SeriesSpec>) -> Result<Vec<SeriesSpec>> {
+        let names: Vec<String> = specs.iter().map(|spec| spec.name.clone()).collect();
+        let mut known: Vec<String> = self.entries.iter().map(|entry| entry.name.clone()).collect();
+        let mut pending = specs;
+        let mut sorted = Vec::with_capacity(pending.len());
+
+        while !pending.is_empty() {
+            let ready = pending.iter().position(|spec| {
+                spec.inputs.iter().all(|source| match source {
+                    Source::Price(_) => true,
+                    Source::Series { name, .. } => known.contains(name),
+                })
+            });
+
+            match ready {
+                Some(index) => {
+                    let spec = pending.remove(index);
+                    known.push(spec.name.clone());
+                    sorted.push(spec);
+                }
+                None => {
+                    let unknown = pending.iter().flat_map(|spec| &spec.inputs).find_map(|source| match source {
+                        Source::Series { name, .. } if !known.contains(name) && !names.contains(name) => Some(name),
+                        _ => None,
+                    });
+                    return Err(match unknown {
+                        Some(name) => Error::invalid_parameter("inputs", format!("unknown series '{}'", name)),
+                        None => Error::invalid_parameter(
+                            "inputs",
+                            format!("dependency cycle involving series '{}'", pending[0].name),
+                        ),
+                    });
+                }
+            }
+        }
+
+        Ok(sorted)
+    }
+
+    // Calculer un indicateur pour la dernière bougie de `klines`, qui date de `ago` bougies.
+    // Les dépendances sont à jour jusqu'à la bougie la plus récente (rattrapage d'un nouvel
+    // indicateur) : on lit leur valeur à la même bougie.
+    fn step(&mut self, index: usize, klines: &[KlineSummary], ago: usize) {
+        let (previous, rest) = self.entries.split_at_mut(index);
+        let entry = &mut rest[0];
+        let Some(kline) = klines.last() else {
+            return;
+        };
+
+        let inputs: Vec<f64> = entry
+            .inputs
+            .iter()
+            .map(|input| match *input {
+                Input::Price(price) => price.extract(kline),
+                Input::Series { entry, output } => {
+                    previous[entry].outputs[output].ago(ago).copied().unwrap_or(f64::NAN)
+                }
+            })
+            .collect();
+
+        self.buffer.clear();
+        self.buffer.resize(entry.outputs.len(), f64::NAN);
+        entry.node.next(&inputs, klines, &mut self.buffer);
+        for (series, value) in entry.outputs.iter_mut().zip(&self.buffer) {
+            series.push(*value);
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use crate::indicator::Side;
+
+// Position (ou unité de position) fermée, aux prix d'exécution
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Trade {
+    pub side: Side,
+    pub entry_time: i64,
+    pub entry_price: f64,
+    pub exit_time: i64,
+    pub exit_price: f64,
+    pub quantity: f64,
+}
+
+impl Trade {
+    // Rendement de la position, en fraction du prix d'entrée
+    pub fn return_rate(&self) -> f64 {
+        let change = (self.exit_price - self.entry_price) / self.entry_price;
+        match self.side {
+            Side::Long => change,
+            Side::Short => -change,
+        }
+    }
+
+    // Gain (ou perte) en devise de cotation
+    pub fn profit(&self) -> f64 {
+        self.return_rate() * self.entry_price * self.quantity
+    }
+}
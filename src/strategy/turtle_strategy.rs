@@ -0,0 +1,196 @@
+use binance::model::KlineSummary;
+
+use crate::indicator::{IndicatorSpec, Side};
+use crate::{Error, Result};
+
+use super::execution::{Executor, Mode};
+use super::intent::{Intent, MarketState, SizeHint};
+use super::position::{Position, PositionRules};
+use super::series_graph::SeriesSpec;
+use super::trade::Trade;
+use super::{KlineManager, SignalStrategy, TradingStrategy};
+
+// Nombre de bougies nécessaires pour initialiser les indicateurs
+const WARMUP_KLINES: usize = 100;
+const DEFAULT_SYMBOL: &str = "ETHBTC";
+
+// Systèmes des Turtles : cassure du canal de Donchian de `entry_length` bougies, sortie sur le
+// canal opposé de `exit_length` bougies (sans le filtre de la dernière cassure gagnante du système 1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TurtleSystem {
+    System1, // 20 / 10
+    System2, // 55 / 20
+}
+
+impl TurtleSystem {
+    pub fn lengths(&self) -> (usize, usize) {
+        match self {
+            TurtleSystem::System1 => (20, 10),
+            TurtleSystem::System2 => (55, 20),
+        }
+    }
+}
+
+// Stratégie des Turtles. N est l'ATR de `atr_length` bougies ; chaque unité risque `risk` du
+// capital sur un mouvement de N (quantité = capital × risk / N, dans la limite du capital encore
+// disponible). Une unité est ajoutée à chaque progression de `add_step` N depuis la dernière entrée
+// (jusqu'à `max_units`), et le stop de toutes les unités est placé à `stop_multiple` N de la
+// dernière entrée.
+#[derive(Debug, Clone)]
+pub struct TurtleStrategy {
+    entry_length: usize,
+    exit_length: usize,
+    atr_length: usize,
+    risk: f64,
+    position_rules: PositionRules,
+    stop_multiple: f64,
+    executor: Executor,
+}
+
+impl TurtleStrategy {
+    pub fn new(system: TurtleSystem, mode: Mode, capital: f64) -> Result<Self> {
+        let (entry_length, exit_length) = system.lengths();
+        Self::with_lengths(entry_length, exit_length, mode, capital)
+    }
+
+    pub fn with_lengths(entry_length: usize, exit_length: usize, mode: Mode, capital: f64) -> Result<Self> {
+        if !capital.is_finite() || capital <= 0.0 {
+            return Err(Error::invalid_parameter("capital", "must be a positive number"));
+        }
+
+        Ok(Self {
+            entry_length,
+            exit_length,
+            atr_length: 20,
+            risk: 0.01,
+            position_rules: PositionRules::new(4, 0.5, Vec::new())?,
+            stop_multiple: 2.0,
+            executor: Executor::new(mode, DEFAULT_SYMBOL, capital),
+        })
+    }
+
+    // Remplacer la gestion des unités par défaut (1 % de risque, 4 unités tous les 0,5 N, stop à 2 N)
+    pub fn with_units(mut self, risk: f64, max_units: usize, add_step: f64, stop_multiple: f64) -> Result<Self> {
+        if !risk.is_finite() || risk <= 0.0 || risk > 1.0 {
+            return Err(Error::invalid_parameter("risk", "must be in (0, 1]"));
+        }
+        if !stop_multiple.is_finite() || stop_multiple <= 0.0 {
+            return Err(Error::invalid_parameter("stop_multiple", "must be a positive number"));
+        }
+
+        self.risk = risk;
+        self.position_rules = PositionRules::new(max_units, add_step, Vec::new())?;
+        self.stop_multiple = stop_multiple;
+        Ok(self)
+    }
+
+    pub fn with_atr_length(mut self, atr_length: usize) -> Self {
+        self.atr_length = atr_length;
+        self
+    }
+
+    pub fn with_symbol(mut self, symbol: &str) -> Self {
+        self.executor = Executor::new(self.executor.mode(), symbol, self.executor.capital());
+        self
+    }
+
+    // Capital après les positions fermées (position ouverte comptée à son prix d'entrée)
+    pub fn capital(&self) -> f64 {
+        self.executor.equity()
+    }
+
+    pub fn position(&self) -> Option<&Position> {
+        self.executor.position()
+    }
+
+    // Sorties enregistrées au prix moyen d'entrée des unités fermées
+    pub fn trades(&self) -> &[Trade] {
+        self.executor.trades()
+    }
+
+    pub fn executor(&self) -> &Executor {
+        &self.executor
+    }
+
+    fn stop(&self, side: Side, price: f64, n: f64) -> f64 {
+        match side {
+            Side::Long => price - self.stop_multiple * n,
+            Side::Short => price + self.stop_multiple * n,
+        }
+    }
+}
+
+impl TradingStrategy for TurtleStrategy {
+    fn warmup(&self) -> usize {
+        WARMUP_KLINES
+    }
+
+    fn prepare(&self, klines: &[KlineSummary]) -> Result<KlineManager> {
+        if klines.len() < WARMUP_KLINES {
+            return Err(Error::InsufficientData { required: WARMUP_KLINES, available: klines.len() });
+        }
+
+        // Canaux des bougies précédentes : la bougie courante casse le canal
+        let series = [
+            ("entry", IndicatorSpec::DonchianChannel { length: self.entry_length, offset: 1 }),
+            ("exit", IndicatorSpec::DonchianChannel { length: self.exit_length, offset: 1 }),
+            ("n", IndicatorSpec::Atr { length: self.atr_length }),
+        ];
+        let specs = series
+            .iter()
+            .map(|(name, spec)| Ok(SeriesSpec { name: name.to_string(), node: spec.node()?, inputs: Vec::new() }))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut manager = KlineManager::new(klines[..WARMUP_KLINES].to_vec(), Vec::new());
+        manager.add_series(specs)?;
+        Ok(manager)
+    }
+
+    fn execute(&mut self, kline: KlineSummary, manager: &mut KlineManager) -> Result<()> {
+        manager.add_kline(kline.clone());
+        let market = MarketState::new(&kline, manager, self.executor.position())?;
+        let (close, intents) = (market.close, self.signals(&market)?);
+        self.executor.apply(kline.open_time, close, &intents, &self.position_rules)?;
+        Ok(())
+    }
+}
+
+impl SignalStrategy for TurtleStrategy {
+    fn signals(&self, market: &MarketState) -> Result<Vec<Intent>> {
+        let close = market.close;
+        let n = market.series("n", "value");
+        // Chaque unité risque `risk` du capital sur un mouvement de N
+        let size = SizeHint::Risk { fraction: self.risk, distance: n };
+
+        if let Some(position) = market.position {
+            let side = position.side();
+            let exit = match side {
+                Side::Long => close < market.series("exit", "lower"),
+                Side::Short => close > market.series("exit", "upper"),
+            };
+            if exit {
+                return Ok(vec![Intent::Exit { side }]);
+            }
+
+            // Pyramidage : nouvelle unité, stop de toutes les unités déplacé à la nouvelle entrée
+            if position.can_add(close, n, &self.position_rules) {
+                let stop = self.stop(side, close, n);
+                return Ok(vec![Intent::Enter { side, stop: Some(stop), size }, Intent::ModifyStop { side, stop }]);
+            }
+            return Ok(Vec::new());
+        }
+
+        // Pas d'entrée sans N (initialisation de l'ATR)
+        if n.is_nan() || n <= 0.0 {
+            return Ok(Vec::new());
+        }
+        let side = if close > market.series("entry", "upper") {
+            Side::Long
+        } else if close < market.series("entry", "lower") {
+            Side::Short
+        } else {
+            return Ok(Vec::new());
+        };
+        Ok(vec![Intent::Enter { side, stop: Some(self.stop(side, close, n)), size }])
+    }
+}
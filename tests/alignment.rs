@@ -0,0 +1,70 @@
+mod common;
+
+use common::{assert_close, load_klines};
+use root::indicator::{value_at, ATRStopLoss, ChoppinessIndex, DonchianChannel};
+use root::strategy::interface::Observer;
+use root::strategy::KlineManager;
+
+const FIXTURE: &str = "synthetic_1h.csv";
+
+fn first_defined(values: &[f64]) -> Option<usize> {
+    values.iter().position(|value| !value.is_nan())
+}
+
+#[test]
+fn original_indicators_have_one_value_per_kline() {
+    let klines = load_klines(FIXTURE);
+    let choppiness = ChoppinessIndex::new(&klines, 14).unwrap();
+    let donchian = DonchianChannel::new(&klines, 20, 5).unwrap();
+    let atr_stop = ATRStopLoss::new(&klines, 14, 1.5).unwrap();
+
+    assert_eq!(choppiness.values.len(), klines.len());
+    assert_eq!(donchian.upper_band.len(), klines.len());
+    assert_eq!(donchian.basis.len(), klines.len());
+    assert_eq!(atr_stop.stop_losses.len(), klines.len());
+
+    // NaN jusqu'à ce que la fenêtre soit remplie
+    assert_eq!(first_defined(&choppiness.values), Some(13));
+    assert_eq!(first_defined(&donchian.lower_band), Some(24));
+    // Comme `ta.atr`, le premier True Range vaut high - low : 14 True Ranges dès la bougie 13
+    assert_eq!(first_defined(&atr_stop.stop_losses), Some(13));
+}
+
+#[test]
+fn manager_keeps_series_aligned_with_klines() {
+    let klines = load_klines(FIXTURE);
+    let initial = klines[..100].to_vec();
+    let observers: Vec<Box<dyn Observer>> = vec![
+        Box::new(ChoppinessIndex::new(&initial, 14).unwrap()),
+        Box::new(DonchianChannel::new(&initial, 20, 20).unwrap()),
+        Box::new(ATRStopLoss::new(&initial, 14, 1.5).unwrap()),
+    ];
+    let mut manager = KlineManager::new(initial, observers);
+    for kline in &klines[100..] {
+        manager.add_kline(kline.clone());
+    }
+
+    let batch = DonchianChannel::new(&klines, 20, 20).unwrap();
+    let incremental = manager.get_donchian_channel().unwrap();
+    assert_eq!(incremental.upper_band.len(), manager.klines.len());
+    for (index, expected) in batch.upper_band.iter().enumerate() {
+        assert_close("upper_band", index, incremental.upper_band[index], *expected);
+    }
+    assert_eq!(manager.get_choppiness_index().unwrap().values.len(), klines.len());
+    assert_eq!(manager.get_atr_stop_loss().unwrap().stop_losses.len(), klines.len());
+}
+
+#[test]
+fn values_are_looked_up_by_open_time() {
+    let klines = load_klines(FIXTURE);
+    let choppiness = ChoppinessIndex::new(&klines, 14).unwrap();
+
+    assert_eq!(value_at(&klines, &choppiness.values, klines[150].open_time), Some(choppiness.values[150]));
+    assert_eq!(value_at(&klines, &choppiness.values, klines[5].open_time), None);
+    assert_eq!(value_at(&klines, &choppiness.values, klines[150].open_time + 1), None);
+
+    // Série plus courte que les bougies (historique borné) : alignée sur la dernière bougie
+    let tail = &choppiness.values[choppiness.values.len() - 10..];
+    assert_eq!(value_at(&klines, tail, klines[195].open_time), Some(choppiness.values[195]));
+    assert_eq!(value_at(&klines, tail, klines[185].open_time), None);
+}
@@ -0,0 +1,64 @@
+mod common;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use binance::model::KlineSummary;
+use common::load_klines;
+use root::strategy::{Backtester, KlineManager, TradingStrategy};
+use root::{Error, Result};
+
+const FIXTURE: &str = "synthetic_1h.csv";
+
+// Stratégie qui relève l'heure d'ouverture des bougies exécutées
+struct Recorder {
+    warmup: usize,
+    executed: Rc<RefCell<Vec<i64>>>,
+}
+
+impl TradingStrategy for Recorder {
+    fn warmup(&self) -> usize {
+        self.warmup
+    }
+
+    fn prepare(&self, klines: &[KlineSummary]) -> Result<KlineManager> {
+        Ok(KlineManager::new(klines[..self.warmup].to_vec(), Vec::new()))
+    }
+
+    fn execute(&mut self, kline: KlineSummary, manager: &mut KlineManager) -> Result<()> {
+        self.executed.borrow_mut().push(kline.open_time);
+        manager.add_kline(kline);
+        Ok(())
+    }
+}
+
+fn run(klines: &[KlineSummary], warmup: usize) -> (Result<()>, Vec<i64>) {
+    let executed = Rc::new(RefCell::new(Vec::new()));
+    let strategy = Recorder { warmup, executed: Rc::clone(&executed) };
+    let result = Backtester::new(Box::new(strategy)).run(klines);
+    let executed = executed.borrow().clone();
+    (result, executed)
+}
+
+#[test]
+fn every_kline_after_the_warmup_is_executed() {
+    let klines = load_klines(FIXTURE);
+    for warmup in [1, 30, 100] {
+        let (result, executed) = run(&klines, warmup);
+        assert!(result.is_ok());
+        let expected: Vec<i64> = klines[warmup..].iter().map(|kline| kline.open_time).collect();
+        assert_eq!(executed, expected, "warmup {}", warmup);
+    }
+
+    // Tout l'historique sert à l'initialisation : aucune bougie à exécuter
+    let (result, executed) = run(&klines[..30], 30);
+    assert!(result.is_ok() && executed.is_empty());
+}
+
+#[test]
+fn short_histories_are_rejected() {
+    let klines = load_klines(FIXTURE);
+    let (result, executed) = run(&klines[..29], 30);
+    assert!(matches!(result, Err(Error::InsufficientData { required: 30, available: 29 })));
+    assert!(executed.is_empty());
+}
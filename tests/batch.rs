@@ -0,0 +1,352 @@
+mod common;
+
+use common::golden::{Golden, GOLDEN_FIXTURE};
+use common::{assert_close, load_klines};
+use root::data::{Candles, Interval};
+use root::indicator::registry;
+use root::indicator::*;
+use root::strategy::{KlineManager, SeriesSpec};
+
+// Les calculs par lot doivent retrouver les mêmes fichiers de référence que les indicateurs
+// incrémentaux (voir tests/golden.rs)
+
+#[test]
+fn batch_moving_averages_match_golden_files() {
+    let average = |kind, period| IndicatorSpec::MovingAverage { kind, period, source: PriceSource::Close };
+
+    Golden::reference("sma_20").check_batch(average(MovingAverageKind::Sma, 20));
+    Golden::reference("ema_20").check_batch(average(MovingAverageKind::Ema, 20));
+    Golden::reference("rma_14").check_batch(average(MovingAverageKind::Rma, 14));
+    Golden::reference("wma_20").check_batch(average(MovingAverageKind::Wma, 20));
+    Golden::reference("hma_20").check_batch(average(MovingAverageKind::Hma, 20));
+    Golden::reference("dema_20").check_batch(average(MovingAverageKind::Dema, 20));
+    Golden::reference("tema_20").check_batch(average(MovingAverageKind::Tema, 20));
+    Golden::reference("vwma_20").check_batch(average(MovingAverageKind::Vwma, 20));
+    Golden::reference("kama_10_2_30").check_batch(average(MovingAverageKind::Kama, 10));
+}
+
+#[test]
+fn batch_momentum_oscillators_match_golden_files() {
+    let close = PriceSource::Close;
+
+    Golden::reference("rsi_14").check_batch(IndicatorSpec::Rsi { period: 14, source: close });
+    Golden::reference("stochastic_14_3_3").check_batch(IndicatorSpec::Stochastic { length: 14, smooth_k: 3, smooth_d: 3 });
+    Golden::reference("stochastic_rsi_14_14_3_3").check_batch(IndicatorSpec::StochasticRsi {
+        rsi_length: 14,
+        stoch_length: 14,
+        smooth_k: 3,
+        smooth_d: 3,
+        source: close,
+    });
+    Golden::reference("macd_12_26_9").check_batch(IndicatorSpec::Macd {
+        fast_length: 12,
+        slow_length: 26,
+        signal_length: 9,
+        source: close,
+    });
+    Golden::reference("cci_20").check_batch(IndicatorSpec::Cci { length: 20, source: PriceSource::HLC3 });
+    Golden::reference("williams_r_14").check_batch(IndicatorSpec::WilliamsR { length: 14 });
+    Golden::reference("rate_of_change_9").check_batch(IndicatorSpec::RateOfChange { length: 9, source: close });
+}
+
+#[test]
+fn batch_volatility_indicators_match_golden_files() {
+    let close = PriceSource::Close;
+
+    Golden::reference("atr_14").check_batch(IndicatorSpec::Atr { length: 14 });
+    Golden::reference("bollinger_bands_20_2").check_batch(IndicatorSpec::BollingerBands {
+        length: 20,
+        multiplier: 2.0,
+        source: close,
+    });
+    Golden::reference("keltner_channel_20_10_2").check_batch(IndicatorSpec::KeltnerChannel {
+        length: 20,
+        atr_length: 10,
+        multiplier: 2.0,
+        source: close,
+    });
+    Golden::reference("atr_bands_14_2").check_batch(IndicatorSpec::AtrBands {
+        atr_length: 14,
+        multiplier: 2.0,
+        source: PriceSource::HL2,
+    });
+    Golden::reference("standard_deviation_20").check_batch(IndicatorSpec::StandardDeviation { length: 20, source: close });
+    Golden::reference("z_score_20").check_batch(IndicatorSpec::ZScore { length: 20, source: close });
+    Golden::reference("choppiness_index_14").check_batch(IndicatorSpec::ChoppinessIndex { length: 14 });
+    Golden::reference("donchian_channel_20_20").check_batch(IndicatorSpec::DonchianChannel { length: 20, offset: 20 });
+    Golden::reference("atr_stop_loss_14_1.5").check_batch(IndicatorSpec::AtrStopLoss { length: 14, multiplier: 1.5 });
+}
+
+#[test]
+fn batch_trend_indicators_match_golden_files() {
+    Golden::reference("adx_14_14").check_batch(IndicatorSpec::Adx { di_length: 14, adx_smoothing: 14 });
+    Golden::reference("aroon_14").check_batch(IndicatorSpec::Aroon { length: 14 });
+    Golden::reference("vortex_14").check_batch(IndicatorSpec::Vortex { length: 14 });
+    Golden::reference("supertrend_10_3").check_batch(IndicatorSpec::SuperTrend { atr_length: 10, factor: 3.0 });
+    Golden::reference("linear_regression_20").check_batch(IndicatorSpec::LinearRegression {
+        length: 20,
+        source: PriceSource::Close,
+    });
+}
+
+#[test]
+fn batch_volume_indicators_match_golden_files() {
+    Golden::reference("obv").check_batch(IndicatorSpec::Obv);
+    Golden::reference("mfi_14").check_batch(IndicatorSpec::Mfi { length: 14 });
+    Golden::reference("accumulation_distribution").check_batch(IndicatorSpec::AccumulationDistribution);
+    Golden::reference("chaikin_money_flow_20").check_batch(IndicatorSpec::ChaikinMoneyFlow { length: 20 });
+}
+
+#[test]
+fn batch_composite_indicators_match_golden_files() {
+    Golden::reference("ichimoku_9_26_52_26").check_batch(IndicatorSpec::Ichimoku {
+        conversion_length: 9,
+        base_length: 26,
+        leading_b_length: 52,
+        displacement: 26,
+    });
+    let session = VwapAnchor::Session(Interval::Days(1));
+    Golden::reference("vwap_session_1d_2").check_batch(IndicatorSpec::Vwap { anchor: session, multiplier: 2.0, source: PriceSource::HLC3 });
+    let anchor = VwapAnchor::Anchored(load_klines(GOLDEN_FIXTURE)[100].open_time);
+    Golden::reference("vwap_anchored_100_2").check_batch(IndicatorSpec::Vwap { anchor, multiplier: 2.0, source: PriceSource::HLC3 });
+    Golden::reference("parabolic_sar_0.02_0.02_0.2").check_batch(IndicatorSpec::ParabolicSar {
+        start: 0.02,
+        increment: 0.02,
+        maximum: 0.2,
+    });
+    Golden::reference("chandelier_exit_22_22_3").check_batch(IndicatorSpec::ChandelierExit {
+        length: 22,
+        atr_length: 22,
+        multiplier: 3.0,
+    });
+    Golden::reference("fractals_2_2").check_batch(IndicatorSpec::Fractals { left: 2, right: 2 });
+    Golden::reference("swing_stop_3_3").check_batch(IndicatorSpec::SwingStop { left: 3, right: 3 });
+    Golden::reference("squeeze_20_2_1.5").check_batch(IndicatorSpec::Squeeze {
+        length: 20,
+        bollinger_multiplier: 2.0,
+        keltner_multiplier: 1.5,
+    });
+    Golden::reference("taker_buy_sell_ratio_14").check_batch(IndicatorSpec::TakerBuySellRatio { length: 14 });
+}
+
+// Indicateurs calculés par lot et en incrémental, avec des paramètres usuels
+fn composite_specs() -> Vec<IndicatorSpec> {
+    let close = PriceSource::Close;
+    vec![
+        IndicatorSpec::Ichimoku { conversion_length: 9, base_length: 26, leading_b_length: 52, displacement: 26 },
+        IndicatorSpec::ChandelierExit { length: 22, atr_length: 22, multiplier: 3.0 },
+        IndicatorSpec::ParabolicSar { start: 0.02, increment: 0.02, maximum: 0.2 },
+        IndicatorSpec::SwingStop { left: 3, right: 3 },
+        IndicatorSpec::Fractals { left: 2, right: 2 },
+        IndicatorSpec::Squeeze { length: 20, bollinger_multiplier: 2.0, keltner_multiplier: 1.5 },
+        IndicatorSpec::TakerBuySellRatio { length: 14 },
+        IndicatorSpec::Vwap { anchor: VwapAnchor::Session(Interval::Days(1)), multiplier: 2.0, source: PriceSource::HLC3 },
+        IndicatorSpec::VolumeProfile { length: 48, bins: 24, value_area: 0.7 },
+        IndicatorSpec::Autocorrelation { length: 20, lag: 1, source: close },
+        IndicatorSpec::Autocorrelation { length: 10, lag: 3, source: close },
+        IndicatorSpec::HurstExponent { length: 64, source: close },
+        IndicatorSpec::CandlestickPatterns { tolerance: PatternTolerance::default() },
+    ]
+}
+
+#[test]
+fn cached_series_match_incremental_indicators() {
+    let klines = load_klines(GOLDEN_FIXTURE);
+    let mut cache = IndicatorCache::new(Candles::from_klines(&klines));
+    let specs = composite_specs();
+
+    let mut manager = KlineManager::new(klines[..1].to_vec(), Vec::new());
+    let series = specs.iter().map(|spec| SeriesSpec { name: spec.key(), node: spec.node().unwrap(), inputs: Vec::new() });
+    manager.add_series(series.collect()).unwrap();
+    for kline in &klines[1..] {
+        manager.add_kline(kline.clone());
+    }
+
+    for spec in &specs {
+        let batch = cache.get(spec).unwrap();
+        assert_eq!(batch.outputs().collect::<Vec<_>>(), spec.node().unwrap().outputs(), "{}", spec.key());
+        for output in batch.outputs() {
+            let expected = batch.get(output).unwrap();
+            assert_eq!(expected.len(), klines.len(), "{}.{}", spec.key(), output);
+            // Les séries doivent contenir des valeurs définies, pas seulement des NaN concordants
+            assert!(expected.iter().any(|value| !value.is_nan()), "{}.{}", spec.key(), output);
+
+            let incremental = manager.get_series(&spec.key(), output).unwrap();
+            for (index, (actual, expected)) in incremental.iter().zip(expected).enumerate() {
+                assert_close(&format!("{}.{}", spec.key(), output), index, *actual, *expected);
+            }
+        }
+    }
+    assert_eq!(cache.misses(), specs.len());
+}
+
+#[test]
+fn batch_rejects_invalid_parameters() {
+    let candles = Candles::from_klines(&load_klines(GOLDEN_FIXTURE));
+
+    assert!(IndicatorSpec::Rsi { period: 0, source: PriceSource::Close }.compute(&candles).is_err());
+    assert!(IndicatorSpec::BollingerBands { length: 20, multiplier: -1.0, source: PriceSource::Close }
+        .compute(&candles)
+        .is_err());
+    assert!(IndicatorSpec::SuperTrend { atr_length: 10, factor: f64::NAN }.compute(&candles).is_err());
+}
+
+#[test]
+fn cache_reuses_unchanged_series() {
+    let mut cache = IndicatorCache::new(Candles::from_klines(&load_klines(GOLDEN_FIXTURE)));
+    let channel = IndicatorSpec::DonchianChannel { length: 20, offset: 20 };
+
+    // Balayage d'un seuil de choppiness : le canal de Donchian n'est calculé qu'une fois
+    for length in [50, 100, 150] {
+        let donchian = cache.get(&channel).unwrap();
+        let chop = cache.get(&IndicatorSpec::ChoppinessIndex { length }).unwrap();
+        assert_eq!(donchian.len(), cache.candles().len());
+        assert_eq!(chop.get("value").map(<[f64]>::len), Some(cache.candles().len()));
+    }
+
+    assert_eq!(cache.misses(), 4);
+    assert_eq!(cache.hits(), 2);
+    assert_eq!(cache.len(), 4);
+
+    let first = cache.get(&channel).unwrap();
+    let second = cache.get(&channel).unwrap();
+    assert!(std::sync::Arc::ptr_eq(&first, &second));
+
+    cache.clear();
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn series_nodes_match_batch_computation() {
+    let klines = load_klines(GOLDEN_FIXTURE);
+    let candles = Candles::from_klines(&klines);
+    let mut manager = KlineManager::new(klines[..1].to_vec(), Vec::new());
+
+    // Un appel par indicateur du registre, avec des paramètres usuels
+    let calls = [
+        ("sma", vec![20.0]),
+        ("vwma", vec![20.0]),
+        ("rsi", vec![14.0]),
+        ("macd", vec![12.0, 26.0, 9.0]),
+        ("stoch", vec![14.0, 3.0, 3.0]),
+        ("stoch_rsi", vec![14.0, 14.0, 3.0, 3.0]),
+        ("cci", vec![20.0]),
+        ("williams_r", vec![14.0]),
+        ("roc", vec![9.0]),
+        ("atr", vec![14.0]),
+        ("bollinger", vec![20.0, 2.0]),
+        ("keltner", vec![20.0, 10.0, 2.0]),
+        ("atr_bands", vec![14.0, 2.0]),
+        ("stdev", vec![20.0]),
+        ("zscore", vec![20.0]),
+        ("donchian", vec![20.0, 20.0]),
+        ("chop", vec![14.0]),
+        ("atr_stop", vec![14.0, 1.5]),
+        ("adx", vec![14.0, 14.0]),
+        ("aroon", vec![14.0]),
+        ("vortex", vec![14.0]),
+        ("supertrend", vec![10.0, 3.0]),
+        ("linreg", vec![20.0]),
+        ("obv", vec![]),
+        ("mfi", vec![14.0]),
+        ("ad", vec![]),
+        ("cmf", vec![20.0]),
+    ];
+
+    let mut specs = Vec::new();
+    for (name, arguments) in &calls {
+        let arguments: Vec<Argument> = arguments.iter().map(|value| Argument::Number(*value)).collect();
+        let spec = registry::resolve(name, &arguments).unwrap();
+        let node = spec.node().unwrap();
+        assert_eq!(node.outputs(), registry::find_indicator(name).unwrap().outputs, "{}", name);
+        specs.push((spec, SeriesSpec { name: spec.key(), node, inputs: Vec::new() }));
+    }
+    let (specs, series): (Vec<IndicatorSpec>, Vec<SeriesSpec>) = specs.into_iter().unzip();
+    manager.add_series(series).unwrap();
+    for kline in &klines[1..] {
+        manager.add_kline(kline.clone());
+    }
+
+    for spec in specs {
+        let batch = spec.compute(&candles).unwrap();
+        for output in batch.outputs() {
+            let incremental = manager.get_series(&spec.key(), output).unwrap();
+            for (index, (actual, expected)) in incremental.iter().zip(batch.get(output).unwrap()).enumerate() {
+                assert_close(&format!("{}.{}", spec.key(), output), index, *actual, *expected);
+            }
+        }
+    }
+}
+
+#[test]
+fn registry_rejects_invalid_calls() {
+    assert!(registry::resolve("unknown", &[]).is_err());
+    assert!(registry::resolve("rsi", &[]).is_err());
+    assert!(registry::resolve("rsi", &[Argument::Number(14.5)]).is_err());
+    assert!(registry::resolve("rsi", &[Argument::Number(14.0), Argument::Number(2.0)]).is_err());
+    assert!(registry::resolve("atr", &[Argument::Number(14.0), Argument::Source(PriceSource::Close)]).is_err());
+    assert_eq!(
+        registry::resolve("donchian", &[Argument::Number(20.0)]).unwrap(),
+        IndicatorSpec::DonchianChannel { length: 20, offset: 0 }
+    );
+    assert_eq!(
+        registry::resolve("rsi", &[Argument::Number(14.0), Argument::Source(PriceSource::HL2)]).unwrap(),
+        IndicatorSpec::Rsi { period: 14, source: PriceSource::HL2 }
+    );
+}
+
+#[test]
+fn batch_sweep_is_not_slower_than_incremental() {
+    // La fixture répétée sur 4 000 bougies consécutives
+    let fixture = load_klines(GOLDEN_FIXTURE);
+    let step = fixture[1].open_time - fixture[0].open_time;
+    let klines: Vec<_> = (0..4_000)
+        .map(|index| {
+            let mut kline = fixture[index % fixture.len()].clone();
+            kline.open_time = fixture[0].open_time + index as i64 * step;
+            kline.close_time = kline.open_time + step - 1;
+            kline
+        })
+        .collect();
+    let candles = Candles::from_klines(&klines);
+
+    // Noyaux à somme glissante : O(n) par lot, O(n·length) pour les fenêtres incrémentales
+    let close = PriceSource::Close;
+    let specs: Vec<IndicatorSpec> = [10, 50, 100, 200]
+        .into_iter()
+        .flat_map(|length| {
+            [
+                IndicatorSpec::MovingAverage { kind: MovingAverageKind::Wma, period: length, source: close },
+                IndicatorSpec::StandardDeviation { length, source: close },
+                IndicatorSpec::ZScore { length, source: close },
+                IndicatorSpec::LinearRegression { length, source: close },
+                IndicatorSpec::ChoppinessIndex { length },
+                IndicatorSpec::Aroon { length },
+            ]
+        })
+        .collect();
+
+    let started = std::time::Instant::now();
+    let mut manager = KlineManager::new(klines[..1].to_vec(), Vec::new());
+    let series = specs.iter().map(|spec| SeriesSpec { name: spec.key(), node: spec.node().unwrap(), inputs: Vec::new() });
+    manager.add_series(series.collect()).unwrap();
+    for kline in &klines[1..] {
+        manager.add_kline(kline.clone());
+    }
+    let incremental = started.elapsed();
+
+    let started = std::time::Instant::now();
+    let batch: Vec<Series> = specs.iter().map(|spec| spec.compute(&candles).unwrap()).collect();
+    let elapsed = started.elapsed();
+    assert!(elapsed <= incremental, "batch {:?}, incremental {:?}", elapsed, incremental);
+
+    // Les sommes glissantes ne dérivent pas sur une longue série
+    for (spec, series) in specs.iter().zip(&batch) {
+        for output in series.outputs() {
+            let values = manager.get_series(&spec.key(), output).unwrap();
+            for (index, (actual, expected)) in values.iter().zip(series.get(output).unwrap()).enumerate() {
+                assert_close(&format!("{}.{}", spec.key(), output), index, *expected, *actual);
+            }
+        }
+    }
+}
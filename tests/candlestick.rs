@@ -0,0 +1,141 @@
+mod common;
+
+use binance::model::KlineSummary;
+use common::candle;
+use root::indicator::{CandlePattern, CandlestickPatterns, PatternTolerance};
+
+fn detect(ohlc: &[(f64, f64, f64, f64)]) -> CandlestickPatterns {
+    let klines: Vec<KlineSummary> = ohlc
+        .iter()
+        .enumerate()
+        .map(|(index, &(open, high, low, close))| candle(index as i64, open, high, low, close))
+        .collect();
+    CandlestickPatterns::new(&klines, PatternTolerance::default()).unwrap()
+}
+
+#[test]
+fn single_candle_shapes() {
+    let patterns = detect(&[
+        (100.0, 105.0, 95.0, 100.2),  // doji
+        (100.0, 101.2, 90.0, 101.0),  // marteau
+        (100.0, 110.0, 98.8, 99.0),   // étoile filante
+        (100.0, 110.0, 90.0, 108.0),  // grand corps
+        (100.0, 100.5, 90.0, 101.0),  // clôture au-dessus du plus haut : bougie invalide
+    ]);
+
+    assert!(patterns.is_detected(0, CandlePattern::Doji));
+    assert!(patterns.is_detected(1, CandlePattern::Hammer));
+    assert!(!patterns.is_detected(1, CandlePattern::ShootingStar));
+    assert!(patterns.is_detected(2, CandlePattern::ShootingStar));
+    assert!(!patterns.is_detected(3, CandlePattern::Doji));
+    assert!(!patterns.is_detected(3, CandlePattern::Hammer));
+    assert!(patterns.patterns[4].is_empty());
+}
+
+#[test]
+fn two_candle_patterns() {
+    let patterns = detect(&[
+        (105.0, 106.0, 99.0, 100.0), // baissière
+        (99.5, 107.0, 98.0, 106.0),  // avalement haussier et bougie englobante
+        (104.0, 105.0, 100.0, 101.0), // bougie incluse, baissière
+        (101.5, 106.0, 101.0, 105.5), // pas d'avalement : ouverture au-dessus de la clôture précédente
+    ]);
+
+    assert!(patterns.is_detected(1, CandlePattern::BullishEngulfing));
+    assert!(patterns.is_detected(1, CandlePattern::OutsideBar));
+    assert!(patterns.is_detected(2, CandlePattern::InsideBar));
+    assert!(!patterns.is_detected(2, CandlePattern::BearishEngulfing));
+    assert!(!patterns.is_detected(3, CandlePattern::BullishEngulfing));
+}
+
+#[test]
+fn three_candle_patterns() {
+    let patterns = detect(&[
+        (110.0, 111.0, 99.0, 100.0), // grande baissière
+        (99.0, 100.0, 97.0, 99.5),   // petit corps
+        (100.0, 108.5, 99.5, 108.0), // haussière au-dessus du milieu du premier corps
+        (107.0, 116.0, 106.5, 115.5),
+        (114.0, 122.0, 113.5, 121.5), // troisième soldat
+    ]);
+
+    assert!(patterns.is_detected(2, CandlePattern::MorningStar));
+    assert!(!patterns.is_detected(2, CandlePattern::EveningStar));
+    assert!(patterns.is_detected(4, CandlePattern::ThreeWhiteSoldiers));
+    assert!(!patterns.is_detected(3, CandlePattern::ThreeWhiteSoldiers));
+}
+
+// Figure détectée (ou non) sur la dernière bougie de la séquence
+fn last_is(ohlc: &[(f64, f64, f64, f64)], pattern: CandlePattern) -> bool {
+    detect(ohlc).is_detected(ohlc.len() - 1, pattern)
+}
+
+#[test]
+fn shape_thresholds() {
+    // Doji : corps d'au plus 10 % du range
+    assert!(last_is(&[(100.0, 105.0, 95.0, 101.0)], CandlePattern::Doji));
+    assert!(!last_is(&[(100.0, 105.0, 95.0, 101.2)], CandlePattern::Doji));
+
+    // Marteau : mèche basse d'au moins deux corps, mèche haute d'au plus 15 % du range
+    assert!(last_is(&[(158.0, 200.0, 100.0, 185.0)], CandlePattern::Hammer));
+    assert!(!last_is(&[(156.0, 200.0, 100.0, 185.0)], CandlePattern::Hammer)); // mèche de 56 pour un corps de 29
+    assert!(!last_is(&[(158.0, 205.0, 100.0, 185.0)], CandlePattern::Hammer)); // mèche haute trop longue
+    assert!(!last_is(&[(140.0, 200.0, 100.0, 185.0)], CandlePattern::Hammer)); // corps de plus de 30 % du range
+
+    // Étoile filante : le symétrique du marteau
+    assert!(last_is(&[(142.0, 200.0, 100.0, 115.0)], CandlePattern::ShootingStar));
+    assert!(!last_is(&[(144.0, 200.0, 100.0, 115.0)], CandlePattern::ShootingStar));
+    assert!(!last_is(&[(142.0, 200.0, 95.0, 115.0)], CandlePattern::ShootingStar));
+    assert!(!last_is(&[(142.0, 200.0, 100.0, 115.0)], CandlePattern::Hammer));
+}
+
+#[test]
+fn engulfing_needs_a_larger_opposite_body() {
+    let bearish = (105.0, 106.0, 99.0, 100.0);
+    assert!(last_is(&[bearish, (99.5, 107.0, 98.0, 106.0)], CandlePattern::BullishEngulfing));
+    // Corps de même taille
+    assert!(!last_is(&[bearish, (100.0, 106.0, 99.0, 105.0)], CandlePattern::BullishEngulfing));
+    // Clôture sous l'ouverture précédente
+    assert!(!last_is(&[bearish, (99.5, 106.0, 98.0, 104.5)], CandlePattern::BullishEngulfing));
+    // Bougie précédente haussière
+    assert!(!last_is(&[(100.0, 106.0, 99.0, 105.0), (99.5, 107.0, 98.0, 106.0)], CandlePattern::BullishEngulfing));
+
+    let bullish = (100.0, 105.0, 99.0, 104.0);
+    assert!(last_is(&[bullish, (104.5, 106.0, 98.0, 99.0)], CandlePattern::BearishEngulfing));
+    assert!(!last_is(&[bullish, (104.5, 106.0, 98.0, 100.5)], CandlePattern::BearishEngulfing));
+    assert!(!last_is(&[bullish, (104.5, 106.0, 98.0, 99.0)], CandlePattern::BullishEngulfing));
+}
+
+#[test]
+fn stars_close_beyond_the_middle_of_the_first_body() {
+    // Étoile du matin : grande baissière (milieu du corps à 105), petit corps, haussière
+    let (first, second) = ((110.0, 111.0, 99.0, 100.0), (99.0, 100.0, 97.0, 99.5));
+    assert!(last_is(&[first, second, (100.0, 106.0, 99.5, 105.5)], CandlePattern::MorningStar));
+    assert!(!last_is(&[first, second, (100.0, 105.0, 99.5, 104.5)], CandlePattern::MorningStar));
+    // Deuxième bougie à grand corps
+    assert!(!last_is(&[first, (99.0, 100.0, 95.0, 95.5), (100.0, 106.0, 99.5, 105.5)], CandlePattern::MorningStar));
+
+    // Étoile du soir : grande haussière (milieu à 105), petit corps, baissière
+    let (first, second) = ((100.0, 111.0, 99.0, 110.0), (110.5, 112.0, 110.0, 111.0));
+    assert!(last_is(&[first, second, (110.0, 110.5, 103.0, 104.0)], CandlePattern::EveningStar));
+    assert!(!last_is(&[first, second, (110.0, 110.5, 105.5, 106.0)], CandlePattern::EveningStar));
+    assert!(!last_is(&[first, second, (110.0, 110.5, 103.0, 104.0)], CandlePattern::MorningStar));
+}
+
+#[test]
+fn incremental_matches_batch() {
+    let klines = common::load_klines("synthetic_1h.csv");
+    let batch = CandlestickPatterns::new(&klines, PatternTolerance::default()).unwrap();
+
+    let mut incremental = CandlestickPatterns::new(&klines[..50], PatternTolerance::default()).unwrap();
+    for end in 51..=klines.len() {
+        incremental.add(&klines[..end]);
+    }
+
+    assert_eq!(incremental.patterns, batch.patterns);
+}
+
+#[test]
+fn rejects_invalid_tolerance() {
+    let tolerance = PatternTolerance { doji_body: 1.5, ..PatternTolerance::default() };
+    assert!(CandlestickPatterns::new(&[], tolerance).is_err());
+}
@@ -0,0 +1,171 @@
+// Tests de référence (« golden files ») : les séries d'un indicateur calculées sur une fixture
+// de bougies sont comparées, bougie par bougie, à des valeurs attendues stockées en CSV.
+//
+// - `reference/` : valeurs recalculées avec les formules de TradingView (ou la formule de
+//   l'indicateur sans équivalent) par `reference.py`, jamais réécrites par les tests.
+// - `snapshot/` : sorties validées des indicateurs sans équivalent TradingView, régénérées avec
+//   `UPDATE_GOLDEN=1 cargo test` après une modification volontaire du calcul.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use binance::model::KlineSummary;
+use root::data::Candles;
+use root::indicator::IndicatorSpec;
+use root::strategy::interface::Observer;
+use root::strategy::KlineManager;
+
+use super::{fixture_path, load_klines};
+
+pub const GOLDEN_FIXTURE: &str = "synthetic_1h.csv";
+
+// Tolérance relative, avec un plancher absolu pour les valeurs proches de zéro
+const RELATIVE_TOLERANCE: f64 = 1e-9;
+const ABSOLUTE_TOLERANCE: f64 = 1e-12;
+
+pub type Outputs = Vec<(&'static str, Vec<f64>)>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GoldenSource {
+    Reference,
+    Snapshot,
+}
+
+pub struct Golden {
+    name: String,
+    source: GoldenSource,
+}
+
+impl Golden {
+    // Valeurs recalculées indépendamment (fichier produit par `reference.py`)
+    pub fn reference(name: &str) -> Self {
+        Self { name: name.to_string(), source: GoldenSource::Reference }
+    }
+
+    // Sortie validée de l'indicateur, régénérable avec UPDATE_GOLDEN=1
+    pub fn snapshot(name: &str) -> Self {
+        Self { name: name.to_string(), source: GoldenSource::Snapshot }
+    }
+
+    fn path(&self) -> PathBuf {
+        let directory = match self.source {
+            GoldenSource::Reference => "reference",
+            GoldenSource::Snapshot => "snapshot",
+        };
+        fixture_path("golden").join(directory).join(format!("{}.csv", self.name))
+    }
+
+    // Calculer l'indicateur d'un bloc sur toute la fixture, puis bougie par bougie via un
+    // `KlineManager` : les deux historiques doivent correspondre aux valeurs attendues.
+    pub fn check_observer<T, B, O>(&self, build: B, outputs: O)
+    where
+        T: Observer + 'static,
+        B: Fn(&[KlineSummary]) -> T,
+        O: Fn(&T) -> Outputs,
+    {
+        let klines = load_klines(GOLDEN_FIXTURE);
+
+        let batch = build(&klines);
+        self.check(&klines, &outputs(&batch));
+
+        let mut manager = KlineManager::new(klines[..1].to_vec(), vec![Box::new(build(&klines[..1]))]);
+        for kline in &klines[1..] {
+            manager.add_kline(kline.clone());
+        }
+        let incremental = manager.get_indicator::<T>().expect("indicator registered in the manager");
+        self.check(&klines, &outputs(incremental));
+    }
+
+    // Calculer l'indicateur par lot sur les colonnes de la fixture
+    pub fn check_batch(&self, spec: IndicatorSpec) {
+        let klines = load_klines(GOLDEN_FIXTURE);
+        let series = spec.compute(&Candles::from_klines(&klines)).expect("valid indicator parameters");
+        let outputs: Outputs = series.outputs().map(|output| (output, series.get(output).unwrap().to_vec())).collect();
+        self.check(&klines, &outputs);
+    }
+
+    // Comparer des séries alignées sur `klines` au fichier attendu
+    pub fn check(&self, klines: &[KlineSummary], outputs: &[(&'static str, Vec<f64>)]) {
+        for (column, values) in outputs {
+            assert_eq!(values.len(), klines.len(), "{}.{}: one value per kline expected", self.name, column);
+        }
+
+        if self.source == GoldenSource::Snapshot && env::var_os("UPDATE_GOLDEN").is_some() {
+            self.write(klines, outputs);
+            return;
+        }
+
+        let content = fs::read_to_string(self.path()).unwrap_or_else(|error| {
+            let hint = match self.source {
+                GoldenSource::Reference => "run `python3 tests/fixtures/golden/reference.py`",
+                GoldenSource::Snapshot => "run the tests with UPDATE_GOLDEN=1",
+            };
+            panic!("{}: {} ({})", self.path().display(), error, hint)
+        });
+        let mut lines = content.lines().filter(|line| !line.trim().is_empty());
+
+        let header: Vec<&str> = lines.next().expect("golden file header").split(',').collect();
+        let columns: Vec<&str> = outputs.iter().map(|(column, _)| *column).collect();
+        assert_eq!(header[0], "open_time", "{}: first column must be open_time", self.name);
+        assert_eq!(&header[1..], columns.as_slice(), "{}: columns differ from the golden file", self.name);
+
+        let rows: Vec<&str> = lines.collect();
+        assert_eq!(rows.len(), klines.len(), "{}: golden file and fixture have different lengths", self.name);
+
+        for (index, (row, kline)) in rows.iter().zip(klines).enumerate() {
+            let fields: Vec<&str> = row.split(',').collect();
+            assert_eq!(fields[0].parse::<i64>().ok(), Some(kline.open_time), "{}[{}]: open_time mismatch", self.name, index);
+
+            for ((column, values), field) in outputs.iter().zip(&fields[1..]) {
+                let expected: f64 = field.parse().unwrap_or_else(|_| panic!("{}[{}]: invalid value {:?}", self.name, index, field));
+                assert_golden(&self.name, column, index, values[index], expected);
+            }
+        }
+    }
+
+    fn write(&self, klines: &[KlineSummary], outputs: &[(&'static str, Vec<f64>)]) {
+        let mut content = String::from("open_time");
+        for (column, _) in outputs {
+            content.push(',');
+            content.push_str(column);
+        }
+        content.push('\n');
+
+        for (index, kline) in klines.iter().enumerate() {
+            content.push_str(&kline.open_time.to_string());
+            for (_, values) in outputs {
+                // `{:?}` donne la plus courte écriture relue à l'identique (NaN compris)
+                content.push_str(&format!(",{:?}", values[index]));
+            }
+            content.push('\n');
+        }
+
+        let path = self.path();
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+}
+
+fn assert_golden(name: &str, column: &str, index: usize, actual: f64, expected: f64) {
+    if expected.is_nan() {
+        assert!(actual.is_nan(), "{}.{}[{}]: expected NaN, got {}", name, column, index, actual);
+        return;
+    }
+
+    let tolerance = (RELATIVE_TOLERANCE * expected.abs()).max(ABSOLUTE_TOLERANCE);
+    assert!(
+        (actual - expected).abs() <= tolerance,
+        "{}.{}[{}]: expected {}, got {}",
+        name,
+        column,
+        index,
+        expected,
+        actual
+    );
+}
+
+// Convertir une série quelconque (booléens, directions, niveaux optionnels) en colonne f64
+pub fn column<T>(values: impl IntoIterator<Item = T>, f: impl Fn(T) -> f64) -> Vec<f64> {
+    values.into_iter().map(f).collect()
+}
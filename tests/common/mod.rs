@@ -0,0 +1,81 @@
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::PathBuf;
+
+use binance::model::KlineSummary;
+
+pub mod golden;
+
+pub fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixtures").join(name)
+}
+
+// Charger un fichier CSV de bougies au format des colonnes de `KlineSummary`
+pub fn load_klines(name: &str) -> Vec<KlineSummary> {
+    let content = fs::read_to_string(fixture_path(name)).unwrap();
+
+    content
+        .lines()
+        .skip(1)
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let fields: Vec<&str> = line.split(',').collect();
+            KlineSummary {
+                open_time: fields[0].parse().unwrap(),
+                open: fields[1].to_string(),
+                high: fields[2].to_string(),
+                low: fields[3].to_string(),
+                close: fields[4].to_string(),
+                volume: fields[5].to_string(),
+                close_time: fields[6].parse().unwrap(),
+                quote_asset_volume: fields[7].to_string(),
+                number_of_trades: fields[8].parse().unwrap(),
+                taker_buy_base_asset_volume: fields[9].to_string(),
+                taker_buy_quote_asset_volume: fields[10].to_string(),
+            }
+        })
+        .collect()
+}
+
+// Comparer une valeur calculée à la référence (NaN attendu pour les valeurs non définies)
+pub fn assert_close(name: &str, index: usize, actual: f64, expected: f64) {
+    if expected.is_nan() {
+        assert!(actual.is_nan(), "{}[{}]: expected NaN, got {}", name, index, actual);
+        return;
+    }
+
+    let tolerance = 1e-9 * expected.abs().max(1.0);
+    assert!(
+        (actual - expected).abs() <= tolerance,
+        "{}[{}]: expected {}, got {}",
+        name,
+        index,
+        expected,
+        actual
+    );
+}
+
+pub fn assert_series(name: &str, series: &[f64], expected: &[(usize, f64)]) {
+    for (index, value) in expected {
+        assert_close(name, *index, series[*index], *value);
+    }
+}
+
+// Bougie horaire construite à partir de son OHLC, pour les tests de forme
+pub fn candle(index: i64, open: f64, high: f64, low: f64, close: f64) -> KlineSummary {
+    let open_time = 1_704_067_200_000 + index * 3_600_000;
+    KlineSummary {
+        open_time,
+        open: open.to_string(),
+        high: high.to_string(),
+        low: low.to_string(),
+        close: close.to_string(),
+        volume: "1".to_string(),
+        close_time: open_time + 3_599_999,
+        quote_asset_volume: close.to_string(),
+        number_of_trades: 1,
+        taker_buy_base_asset_volume: "0.5".to_string(),
+        taker_buy_quote_asset_volume: (close / 2.0).to_string(),
+    }
+}
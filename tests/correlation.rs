@@ -0,0 +1,155 @@
+mod common;
+
+use binance::model::KlineSummary;
+use common::{assert_close, candle, load_klines};
+use root::indicator::{Autocorrelation, HurstExponent, PriceSource, RollingCorrelation};
+use root::strategy::KlineManager;
+
+const FIXTURE: &str = "synthetic_1h.csv";
+const LENGTH: usize = 20;
+
+// Référence : la fixture avec une clôture légèrement perturbée
+fn reference(klines: &[KlineSummary]) -> Vec<KlineSummary> {
+    klines
+        .iter()
+        .enumerate()
+        .map(|(index, kline)| {
+            let mut kline = kline.clone();
+            let close = PriceSource::Close.extract(&kline) * (1.0 + 0.002 * (index as f64 / 3.0).sin());
+            kline.close = close.to_string();
+            kline
+        })
+        .collect()
+}
+
+#[test]
+fn reference_is_fed_inside_the_manager() {
+    let klines = load_klines(FIXTURE);
+    let reference = reference(&klines);
+    let expected = RollingCorrelation::new(&klines, &reference, LENGTH, PriceSource::Close).unwrap();
+
+    let initial = klines[..60].to_vec();
+    let indicator = RollingCorrelation::new(&initial, &reference[..60], LENGTH, PriceSource::Close).unwrap();
+    let mut manager = KlineManager::new(initial, vec![Box::new(indicator)]);
+    for (kline, reference) in klines.iter().zip(&reference).skip(60) {
+        manager.get_indicator_mut::<RollingCorrelation>().unwrap().add_reference(std::slice::from_ref(reference));
+        manager.add_kline(kline.clone());
+    }
+
+    let correlation = manager.get_indicator::<RollingCorrelation>().unwrap();
+    assert_eq!(correlation.correlation.len(), expected.correlation.len());
+    for (index, (actual, expected)) in correlation.correlation.iter().zip(expected.correlation.iter()).enumerate() {
+        assert_close("correlation", index, *actual, *expected);
+    }
+    assert!(correlation.correlation.as_slice()[60..].iter().all(|value| value.is_finite()));
+}
+
+#[test]
+fn missing_reference_resets_the_correlation() {
+    let klines = load_klines(FIXTURE);
+    let reference = reference(&klines);
+    // La série de référence s'arrête à la bougie 120, puis reprend à la bougie 150
+    let stop = 120;
+    let resume = 150;
+
+    let initial = klines[..100].to_vec();
+    let indicator = RollingCorrelation::new(&initial, &reference[..100], LENGTH, PriceSource::Close).unwrap();
+    let mut manager = KlineManager::new(initial, vec![Box::new(indicator)]);
+    for (index, (kline, reference)) in klines.iter().zip(&reference).enumerate().skip(100) {
+        if !(stop..resume).contains(&index) {
+            manager.get_indicator_mut::<RollingCorrelation>().unwrap().add_reference(std::slice::from_ref(reference));
+        }
+        manager.add_kline(kline.clone());
+    }
+
+    let correlation = manager.get_indicator::<RollingCorrelation>().unwrap();
+    let (values, beta) = (correlation.correlation.as_slice(), correlation.beta.as_slice());
+    assert!(values[stop - 1].is_finite());
+    // NaN tant que `LENGTH` couples de rendements n'ont pas été appariés après la reprise
+    assert!(values[stop..resume + LENGTH].iter().all(|value| value.is_nan()));
+    assert!(beta[stop..resume + LENGTH].iter().all(|value| value.is_nan()));
+    assert!(values[resume + LENGTH].is_finite());
+}
+
+// Bougies dont toutes les valeurs valent la clôture
+fn closes(values: &[f64]) -> Vec<KlineSummary> {
+    values.iter().enumerate().map(|(index, &close)| candle(index as i64, close, close, close, close)).collect()
+}
+
+// Clôtures successives à partir de 100 avec les rendements simples donnés
+fn compound(returns: impl IntoIterator<Item = f64>) -> Vec<f64> {
+    let mut close = 100.0;
+    std::iter::once(close)
+        .chain(returns.into_iter().map(|rate| {
+            close *= 1.0 + rate;
+            close
+        }))
+        .collect()
+}
+
+// Générateur pseudo-aléatoire reproductible (xorshift), rendements gaussiens approchés
+fn random_returns(count: usize, volatility: f64) -> Vec<f64> {
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut uniform = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state >> 11) as f64 / (1u64 << 53) as f64
+    };
+    (0..count).map(|_| volatility * ((0..12).map(|_| uniform()).sum::<f64>() - 6.0)).collect()
+}
+
+// Fixture dont la clôture suit les rendements simples `transform(r)` de la fixture
+fn mirrored(klines: &[KlineSummary], transform: impl Fn(f64) -> f64) -> Vec<KlineSummary> {
+    let closes: Vec<f64> = klines.iter().map(|kline| PriceSource::Close.extract(kline)).collect();
+    let reference = compound(closes.windows(2).map(|pair| transform(pair[1] / pair[0] - 1.0)));
+    klines
+        .iter()
+        .zip(reference)
+        .map(|(kline, close)| KlineSummary { close: close.to_string(), ..kline.clone() })
+        .collect()
+}
+
+#[test]
+fn correlation_of_linked_series() {
+    let klines = load_klines(FIXTURE);
+    // Référence aux rendements identiques, opposés et doublés
+    for (scale, expected_correlation, expected_beta) in [(1.0, 1.0, 1.0), (-1.0, -1.0, -1.0), (2.0, 1.0, 0.5)] {
+        let reference = mirrored(&klines, |rate| scale * rate);
+        let correlation = RollingCorrelation::new(&klines, &reference, LENGTH, PriceSource::Close).unwrap();
+        for index in LENGTH + 1..klines.len() {
+            assert_close("correlation", index, correlation.correlation[index], expected_correlation);
+            assert_close("beta", index, correlation.beta[index], expected_beta);
+        }
+    }
+}
+
+#[test]
+fn autocorrelation_of_alternating_returns() {
+    // Rendements alternés : opposés d'une bougie sur l'autre, identiques à deux bougies d'écart
+    let klines = closes(&compound((0..60).map(|index| if index % 2 == 0 { 0.01 } else { -0.01 })));
+    let lag_one = Autocorrelation::new(&klines, LENGTH, 1, PriceSource::Close).unwrap();
+    let lag_two = Autocorrelation::new(&klines, LENGTH, 2, PriceSource::Close).unwrap();
+
+    for index in LENGTH + 2..klines.len() {
+        assert_close("lag 1", index, lag_one.values[index], -1.0);
+        assert_close("lag 2", index, lag_two.values[index], 1.0);
+    }
+}
+
+#[test]
+fn hurst_exponent_of_known_series() {
+    // Marche aléatoire : H proche de 0,5 (l'analyse R/S surestime légèrement sur des fenêtres courtes)
+    let length = 256;
+    let klines = closes(&compound(random_returns(2000, 0.01)));
+    let hurst = HurstExponent::new(&klines, length, PriceSource::Close).unwrap();
+    let values = &hurst.values.as_slice()[length + 1..];
+    assert!(values.iter().all(|value| value.is_finite()));
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    assert!((mean - 0.5).abs() < 0.1, "random walk: H = {}", mean);
+
+    // Rendements alternés : l'étendue des écarts cumulés ne croît pas avec la taille des blocs
+    let klines = closes(&compound((0..300).map(|index| if index % 2 == 0 { 0.01 } else { -0.01 })));
+    let hurst = HurstExponent::new(&klines, length, PriceSource::Close).unwrap();
+    assert!(hurst.values.as_slice()[length + 1..].iter().all(|value| value.abs() < 0.05));
+}
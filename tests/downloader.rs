@@ -0,0 +1,199 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use binance::config::Config;
+use root::data::{DownloadEvent, Downloader, Interval};
+
+const HOUR_MS: u64 = 60 * 60 * 1000;
+const SERVER_ERROR: &str = "500 Internal Server Error";
+
+fn query_param(path: &str, name: &str) -> Option<u64> {
+    let query = path.split('?').nth(1)?;
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .and_then(|(_, value)| value.parse().ok())
+}
+
+// Serveur local imitant /api/v3/klines en 1h : `failures` premières requêtes en erreur `status`,
+// puis des bougies jusqu'à `available` bougies au total
+fn spawn_mock_server(failures: usize, status: &'static str, available: u64, requests: Arc<AtomicUsize>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = match stream {
+                Ok(stream) => stream,
+                Err(_) => continue,
+            };
+
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut header = String::new();
+            while reader.read_line(&mut header).unwrap_or(0) > 2 {
+                header.clear();
+            }
+
+            let count = requests.fetch_add(1, Ordering::SeqCst);
+            let path = request_line.split_whitespace().nth(1).unwrap_or("");
+
+            let (status, body) = if path.contains("symbol=UNKNOWN") {
+                ("400 Bad Request", r#"{"code":-1121,"msg":"Invalid symbol."}"#.to_string())
+            } else if count < failures {
+                (status, String::new())
+            } else {
+                let start = query_param(path, "startTime").unwrap_or(0);
+                let end = query_param(path, "endTime").unwrap_or(u64::MAX);
+                let limit = query_param(path, "limit").unwrap_or(500);
+
+                let first = start.div_ceil(HOUR_MS);
+                let rows: Vec<String> = (first..available)
+                    .map(|i| i * HOUR_MS)
+                    .filter(|open_time| *open_time <= end)
+                    .take(limit as usize)
+                    .map(|open_time| {
+                        format!(
+                            r#"[{},"1.0","2.0","0.5","1.5","10.0",{},"15.0",3,"5.0","7.5","0"]"#,
+                            open_time,
+                            open_time + HOUR_MS - 1
+                        )
+                    })
+                    .collect();
+                ("200 OK", format!("[{}]", rows.join(",")))
+            };
+
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+
+    format!("http://{}", address)
+}
+
+fn downloader(endpoint: String) -> Downloader {
+    Downloader::with_config(Config::default().set_rest_api_endpoint(endpoint))
+        .with_page_limit(3)
+        .with_retries(3, Duration::from_millis(1), Duration::from_millis(5))
+}
+
+#[test]
+fn download_retries_and_stops_at_end_time() {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let endpoint = spawn_mock_server(2, SERVER_ERROR, 100, requests.clone());
+
+    let klines = downloader(endpoint)
+        .download("ETHBTC", Interval::Hours(1), 0, 10 * HOUR_MS - 1)
+        .unwrap();
+
+    let open_times: Vec<i64> = klines.iter().map(|kline| kline.open_time).collect();
+    let expected: Vec<i64> = (0..10).map(|i| (i * HOUR_MS) as i64).collect();
+    assert_eq!(open_times, expected);
+    // 2 échecs + 4 pages de 3 bougies au plus
+    assert_eq!(requests.load(Ordering::SeqCst), 6);
+}
+
+#[test]
+fn download_stops_when_no_more_klines_are_available() {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let endpoint = spawn_mock_server(0, SERVER_ERROR, 5, requests.clone());
+
+    let klines = downloader(endpoint)
+        .download("ETHBTC", Interval::Hours(1), 0, 100 * HOUR_MS)
+        .unwrap();
+
+    assert_eq!(klines.len(), 5);
+}
+
+#[test]
+fn download_error_keeps_progress_for_resume() {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let endpoint = spawn_mock_server(0, SERVER_ERROR, 100, requests.clone());
+    let downloader = downloader(endpoint);
+
+    let error = downloader
+        .download("UNKNOWN", Interval::Hours(1), 0, 10 * HOUR_MS)
+        .unwrap_err();
+    assert_eq!(error.progress.next_start, 0);
+    assert!(error.klines.is_empty());
+    // Les erreurs de requête ne sont pas réessayées
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+    let mut progress = error.progress;
+    progress.symbol = "ETHBTC".to_string();
+    let klines = downloader.resume(progress, error.klines).unwrap();
+    assert_eq!(klines.len(), 11);
+}
+
+#[test]
+fn download_many_keeps_symbol_order() {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let endpoint = spawn_mock_server(0, SERVER_ERROR, 100, requests);
+
+    let results = downloader(endpoint).download_many(&["ETHBTC", "UNKNOWN", "BNBBTC"], Interval::Hours(1), 0, 5 * HOUR_MS);
+
+    let symbols: Vec<&str> = results.iter().map(|(symbol, _)| symbol.as_str()).collect();
+    assert_eq!(symbols, vec!["ETHBTC", "UNKNOWN", "BNBBTC"]);
+    assert_eq!(results[0].1.as_ref().unwrap().len(), 6);
+    assert!(results[1].1.is_err());
+    assert_eq!(results[2].1.as_ref().unwrap().len(), 6);
+}
+
+#[test]
+fn download_stops_on_ip_ban() {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let endpoint = spawn_mock_server(1, "418 I'm a teapot", 100, requests.clone());
+
+    let error = downloader(endpoint).download("ETHBTC", Interval::Hours(1), 0, 10 * HOUR_MS).unwrap_err();
+    assert!(error.klines.is_empty());
+    // Un 418 n'est jamais réessayé
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn download_stops_on_other_client_errors() {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let endpoint = spawn_mock_server(1, "403 Forbidden", 100, requests.clone());
+
+    assert!(downloader(endpoint).download("ETHBTC", Interval::Hours(1), 0, 10 * HOUR_MS).is_err());
+    // Seuls les 429 et les erreurs serveur sont réessayés
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn download_backs_off_on_rate_limit() {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let endpoint = spawn_mock_server(2, "429 Too Many Requests", 100, requests.clone());
+    let retries = Arc::new(Mutex::new(Vec::new()));
+    let pages = Arc::new(AtomicUsize::new(0));
+    let (reported_retries, reported_pages) = (retries.clone(), pages.clone());
+
+    let started = Instant::now();
+    let klines = downloader(endpoint)
+        .with_rate_limit_backoff(Duration::from_millis(50))
+        .with_report(move |event| match event {
+            DownloadEvent::Retry { attempt, delay, .. } => reported_retries.lock().unwrap().push((*attempt, *delay)),
+            DownloadEvent::Page { .. } => {
+                reported_pages.fetch_add(1, Ordering::SeqCst);
+            }
+        })
+        .download("ETHBTC", Interval::Hours(1), 0, 5 * HOUR_MS)
+        .unwrap();
+
+    assert_eq!(klines.len(), 6);
+    // L'attente après un 429 remplace le backoff exponentiel de quelques millisecondes
+    assert!(started.elapsed() >= Duration::from_millis(100));
+    assert_eq!(*retries.lock().unwrap(), [(1, Duration::from_millis(50)), (2, Duration::from_millis(50))]);
+    assert_eq!(pages.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,647 @@
+#!/usr/bin/env python3
+# Valeurs de référence des indicateurs, recalculées indépendamment du code Rust avec les
+# formules des fonctions `ta.*` de Pine Script (TradingView), ou avec la formule publiée de
+# l'indicateur quand TradingView n'en a pas. Seule la bibliothèque standard est utilisée.
+#
+#     python3 tests/fixtures/golden/reference.py
+#
+# Chaque fichier `reference/<nom>.csv` contient une ligne par bougie de `synthetic_1h.csv` :
+# `open_time` puis une colonne par série, `NaN` pendant l'initialisation.
+
+import csv
+import math
+import os
+
+NA = None
+HERE = os.path.dirname(os.path.abspath(__file__))
+FIXTURE = os.path.join(HERE, "..", "synthetic_1h.csv")
+OUTPUT = os.path.join(HERE, "reference")
+
+
+def load():
+    with open(FIXTURE) as f:
+        rows = list(csv.DictReader(f))
+    column = lambda key: [float(row[key]) for row in rows]
+    return {
+        "time": [int(row["open_time"]) for row in rows],
+        "open": column("open"),
+        "high": column("high"),
+        "low": column("low"),
+        "close": column("close"),
+        "volume": column("volume"),
+        "taker_buy": column("taker_buy_base_asset_volume"),
+    }
+
+
+# --- Fonctions de base, avec la propagation des `na` de Pine -------------------------------
+
+def na(*values):
+    return any(v is NA for v in values)
+
+
+def lift(f, *series):
+    return [NA if na(*values) else f(*values) for values in zip(*series)]
+
+
+def window(src, i, n):
+    if i < n - 1:
+        return None
+    values = src[i - n + 1:i + 1]
+    return None if na(*values) else values
+
+
+def sma(src, n):
+    out = []
+    for i in range(len(src)):
+        w = window(src, i, n)
+        out.append(NA if w is None else sum(w) / n)
+    return out
+
+
+def math_sum(src, n):
+    out = []
+    for i in range(len(src)):
+        w = window(src, i, n)
+        out.append(NA if w is None else sum(w))
+    return out
+
+
+def wma(src, n):
+    weights = range(1, n + 1)
+    out = []
+    for i in range(len(src)):
+        w = window(src, i, n)
+        out.append(NA if w is None else sum(a * b for a, b in zip(w, weights)) / sum(weights))
+    return out
+
+
+def _exponential(src, n, alpha):
+    # Initialisation par la SMA des `n` premières valeurs définies (`ta.ema`, `ta.rma`)
+    out, previous, seed = [], NA, []
+    for x in src:
+        if x is NA:
+            out.append(NA)
+        elif previous is NA:
+            seed.append(x)
+            if len(seed) == n:
+                previous = sum(seed) / n
+            out.append(previous)
+        else:
+            previous = alpha * x + (1 - alpha) * previous
+            out.append(previous)
+    return out
+
+
+def ema(src, n):
+    return _exponential(src, n, 2 / (n + 1))
+
+
+def rma(src, n):
+    return _exponential(src, n, 1 / n)
+
+
+def stdev(src, n):
+    out = []
+    for i in range(len(src)):
+        w = window(src, i, n)
+        if w is None:
+            out.append(NA)
+            continue
+        mean = sum(w) / n
+        out.append(math.sqrt(sum((x - mean) ** 2 for x in w) / n))
+    return out
+
+
+def highest(src, n):
+    return [NA if window(src, i, n) is None else max(window(src, i, n)) for i in range(len(src))]
+
+
+def lowest(src, n):
+    return [NA if window(src, i, n) is None else min(window(src, i, n)) for i in range(len(src))]
+
+
+def shift(src, n):
+    return [NA] * n + src[:len(src) - n]
+
+
+def change(src):
+    return [NA] + [NA if na(a, b) else b - a for a, b in zip(src, src[1:])]
+
+
+def compact(f, src, *args):
+    # Appliquer `f` sur la partie définie d'une série qui commence par des `na`
+    values = [x for x in src if x is not NA]
+    return [NA] * (len(src) - len(values)) + f(values, *args)
+
+
+def true_range(d, handle_na):
+    # `ta.tr(handle_na)` : sans clôture précédente, high - low si handle_na, sinon na
+    out = []
+    for i in range(len(d["close"])):
+        high, low = d["high"][i], d["low"][i]
+        if i == 0:
+            out.append(high - low if handle_na else NA)
+        else:
+            previous = d["close"][i - 1]
+            out.append(max(high - low, abs(high - previous), abs(low - previous)))
+    return out
+
+
+def atr(d, n):
+    return rma(true_range(d, True), n)
+
+
+def stoch(src, high, low, n):
+    out = []
+    for i in range(len(src)):
+        hi, lo = window(high, i, n), window(low, i, n)
+        if hi is None or lo is None or src[i] is NA or max(hi) == min(lo):
+            out.append(NA)
+        else:
+            out.append(100 * (src[i] - min(lo)) / (max(hi) - min(lo)))
+    return out
+
+
+def rsi(src, n):
+    ch = change(src)
+    up = rma([NA if c is NA else max(c, 0) for c in ch], n)
+    down = rma([NA if c is NA else -min(c, 0) for c in ch], n)
+
+    def value(u, d):
+        if d == 0:
+            return 100.0
+        if u == 0:
+            return 0.0
+        return 100 - 100 / (1 + u / d)
+
+    return lift(value, up, down)
+
+
+# --- Indicateurs ---------------------------------------------------------------------------
+
+def moving_averages(d):
+    c = d["close"]
+    hma_half = wma(c, 10)
+    hma_full = wma(c, 20)
+    e1 = ema(c, 20)
+    e2 = compact(ema, e1, 20)
+    e3 = compact(ema, e2, 20)
+    return {
+        "sma_20": {"value": sma(c, 20)},
+        "ema_20": {"value": e1},
+        "rma_14": {"value": rma(c, 14)},
+        "wma_20": {"value": hma_full},
+        "hma_20": {"value": compact(wma, lift(lambda a, b: 2 * a - b, hma_half, hma_full), 4)},
+        "dema_20": {"value": lift(lambda a, b: 2 * a - b, e1, e2)},
+        "tema_20": {"value": lift(lambda a, b, x: 3 * a - 3 * b + x, e1, e2, e3)},
+        "vwma_20": {"value": lift(lambda a, b: a / b, sma([x * v for x, v in zip(c, d["volume"])], 20), sma(d["volume"], 20))},
+    }
+
+
+def momentum(d):
+    c, h, l = d["close"], d["high"], d["low"]
+    hlc3 = [(a + b + x) / 3 for a, b, x in zip(h, l, c)]
+
+    k = compact(sma, stoch(c, h, l, 14), 3)
+    r = rsi(c, 14)
+    rsi_k = compact(sma, compact(lambda v: stoch(v, v, v, 14), r), 3)
+
+    fast, slow = ema(c, 12), ema(c, 26)
+    macd = lift(lambda a, b: a - b, fast, slow)
+    signal = compact(ema, macd, 9)
+
+    basis = sma(hlc3, 20)
+    cci = []
+    for i, mean in enumerate(basis):
+        if mean is NA:
+            cci.append(NA)
+            continue
+        deviation = sum(abs(x - mean) for x in hlc3[i - 19:i + 1]) / 20
+        cci.append(0.0 if deviation == 0 else (hlc3[i] - mean) / (0.015 * deviation))
+
+    hh, ll = highest(h, 14), lowest(l, 14)
+    return {
+        "rsi_14": {"value": r},
+        "stochastic_14_3_3": {"k": k, "d": compact(sma, k, 3)},
+        "stochastic_rsi_14_14_3_3": {"k": rsi_k, "d": compact(sma, rsi_k, 3)},
+        "macd_12_26_9": {"macd": macd, "signal": signal, "histogram": lift(lambda a, b: a - b, macd, signal)},
+        "cci_20": {"value": cci},
+        "williams_r_14": {"value": lift(lambda a, b, x: 100 * (x - a) / (a - b), hh, ll, c)},
+        "rate_of_change_9": {"value": lift(lambda a, b: 100 * (b - a) / a, shift(c, 9), c)},
+    }
+
+
+def volatility(d):
+    c, h, l = d["close"], d["high"], d["low"]
+    a14 = atr(d, 14)
+
+    basis = sma(c, 20)
+    deviation = stdev(c, 20)
+    upper = lift(lambda b, s: b + 2 * s, basis, deviation)
+    lower = lift(lambda b, s: b - 2 * s, basis, deviation)
+
+    keltner_basis = ema(c, 20)
+    a10 = atr(d, 10)
+
+    # Squeeze : bandes de Bollinger (20, 2) strictement dans le canal de Keltner (20, 20, 1,5) ;
+    # `released` sur la première bougie hors squeeze
+    a20 = atr(d, 20)
+    squeeze_on = [
+        0.0 if na(u, lo, b, a) else float(u < b + 1.5 * a and lo > b - 1.5 * a)
+        for u, lo, b, a in zip(upper, lower, keltner_basis, a20)
+    ]
+    released = [float(i > 0 and squeeze_on[i - 1] == 1.0 and squeeze_on[i] == 0.0) for i in range(len(c))]
+    hl2 = [(a + b) / 2 for a, b in zip(h, l)]
+
+    tr = true_range(d, True)
+    chop = []
+    for i, (total, hi, lo) in enumerate(zip(math_sum(tr, 14), highest(h, 14), lowest(l, 14))):
+        chop.append(NA if na(total, hi, lo) else 100 * math.log10(total / (hi - lo)) / math.log10(14))
+
+    return {
+        "atr_14": {"value": a14},
+        "atr_stop_loss_14_1.5": {
+            "long": lift(lambda x, a: x - 1.5 * a, c, a14),
+            "short": lift(lambda x, a: x + 1.5 * a, c, a14),
+        },
+        "bollinger_bands_20_2": {
+            "basis": basis,
+            "upper": upper,
+            "lower": lower,
+            "percent_b": lift(lambda x, u, lo: (x - lo) / (u - lo), c, upper, lower),
+            "bandwidth": lift(lambda u, lo, b: (u - lo) / b, upper, lower, basis),
+        },
+        "keltner_channel_20_10_2": {
+            "basis": keltner_basis,
+            "upper": lift(lambda b, a: b + 2 * a, keltner_basis, a10),
+            "lower": lift(lambda b, a: b - 2 * a, keltner_basis, a10),
+        },
+        "squeeze_20_2_1.5": {"squeeze_on": squeeze_on, "released": released},
+        "atr_bands_14_2": {
+            "basis": hl2,
+            "upper": lift(lambda b, a: b + 2 * a, hl2, a14),
+            "lower": lift(lambda b, a: b - 2 * a, hl2, a14),
+        },
+        "donchian_channel_20_20": {
+            "upper": shift(highest(h, 20), 20),
+            "lower": shift(lowest(l, 20), 20),
+            "basis": lift(lambda a, b: (a + b) / 2, shift(highest(h, 20), 20), shift(lowest(l, 20), 20)),
+        },
+        "choppiness_index_14": {"value": chop},
+        "standard_deviation_20": {"value": deviation},
+        "z_score_20": {"value": lift(lambda x, b, s: (x - b) / s, c, basis, deviation)},
+    }
+
+
+def trend(d):
+    c, h, l = d["close"], d["high"], d["low"]
+    n = len(c)
+
+    # ta.dmi(14, 14)
+    up, down = change(h), [NA if x is NA else -x for x in change(l)]
+    plus_dm = lift(lambda u, v: u if u > v and u > 0 else 0.0, up, down)
+    minus_dm = lift(lambda u, v: v if v > u and v > 0 else 0.0, up, down)
+    trur = rma(true_range(d, False), 14)
+    plus = lift(lambda a, b: 100 * a / b, rma(plus_dm, 14), trur)
+    minus = lift(lambda a, b: 100 * a / b, rma(minus_dm, 14), trur)
+    adx = [NA if x is NA else 100 * x for x in rma(lift(lambda a, b: abs(a - b) / (1 if a + b == 0 else a + b), plus, minus), 14)]
+
+    # Aroon : position du plus haut / plus bas sur `length + 1` bougies (la plus récente en cas d'égalité)
+    def aroon(src, better):
+        out = []
+        for i in range(n):
+            if i < 14:
+                out.append(NA)
+                continue
+            w = src[i - 14:i + 1]
+            since = min(k for k in range(15) if all(not better(x, w[14 - k]) for x in w))
+            out.append(100 * (14 - since) / 14)
+        return out
+
+    aroon_up = aroon(h, lambda a, b: a > b)
+    aroon_down = aroon(l, lambda a, b: a < b)
+
+    # Vortex(14) : ta.atr(1) pour le True Range
+    tr = true_range(d, True)
+    vmp = math_sum(lift(lambda a, b: abs(a - b), h, shift(l, 1)), 14)
+    vmm = math_sum(lift(lambda a, b: abs(a - b), l, shift(h, 1)), 14)
+    tr_sum = math_sum(tr, 14)
+    tr_sum = [NA if vmp[i] is NA else tr_sum[i] for i in range(n)]
+
+    # ta.supertrend(3, 10), direction au signe inversé : 1 en tendance haussière
+    a10 = atr(d, 10)
+    values, direction = [], []
+    previous = None
+    for i in range(n):
+        if a10[i] is NA:
+            values.append(NA)
+            direction.append(0.0)
+            continue
+        hl2 = (h[i] + l[i]) / 2
+        upper, lower = hl2 + 3 * a10[i], hl2 - 3 * a10[i]
+        if previous is None:
+            trend_direction = -1
+        else:
+            previous_upper, previous_lower, previous_close, previous_direction = previous
+            if not (lower > previous_lower or previous_close < previous_lower):
+                lower = previous_lower
+            if not (upper < previous_upper or previous_close > previous_upper):
+                upper = previous_upper
+            if previous_direction < 0:
+                trend_direction = 1 if c[i] > upper else -1
+            else:
+                trend_direction = -1 if c[i] < lower else 1
+        values.append(lower if trend_direction > 0 else upper)
+        direction.append(float(trend_direction))
+        previous = (upper, lower, c[i], trend_direction)
+
+    # ta.linreg(close, 20, 0) et la droite des moindres carrés associée
+    linreg = {"value": [], "slope": [], "intercept": [], "r_squared": []}
+    for i in range(n):
+        w = window(c, i, 20)
+        if w is None:
+            for series in linreg.values():
+                series.append(NA)
+            continue
+        xs = range(20)
+        mean_x, mean_y = sum(xs) / 20, sum(w) / 20
+        sxx = sum((x - mean_x) ** 2 for x in xs)
+        syy = sum((y - mean_y) ** 2 for y in w)
+        sxy = sum((x - mean_x) * (y - mean_y) for x, y in zip(xs, w))
+        slope = sxy / sxx
+        intercept = mean_y - slope * mean_x
+        linreg["value"].append(intercept + slope * 19)
+        linreg["slope"].append(slope)
+        linreg["intercept"].append(intercept)
+        linreg["r_squared"].append(sxy * sxy / (sxx * syy))
+
+    return {
+        "adx_14_14": {"plus_di": plus, "minus_di": minus, "adx": adx},
+        "aroon_14": {"up": aroon_up, "down": aroon_down, "oscillator": lift(lambda a, b: a - b, aroon_up, aroon_down)},
+        "vortex_14": {"plus": lift(lambda a, b: a / b, vmp, tr_sum), "minus": lift(lambda a, b: a / b, vmm, tr_sum)},
+        "supertrend_10_3": {"value": values, "direction": direction},
+        "linear_regression_20": linreg,
+    }
+
+
+def volume(d):
+    c, h, l, v = d["close"], d["high"], d["low"], d["volume"]
+    n = len(c)
+
+    obv, total = [], 0.0
+    for i in range(n):
+        if i > 0:
+            total += v[i] if c[i] > c[i - 1] else -v[i] if c[i] < c[i - 1] else 0.0
+        obv.append(total)
+
+    # ta.mfi(hlc3, 14) ; la première bougie, sans variation connue, n'entre dans aucune somme
+    hlc3 = [(a + b + x) / 3 for a, b, x in zip(h, l, c)]
+    ch = change(hlc3)
+    positive = [NA if x is NA else (0.0 if x <= 0 else p * q) for x, p, q in zip(ch, hlc3, v)]
+    negative = [NA if x is NA else (0.0 if x >= 0 else p * q) for x, p, q in zip(ch, hlc3, v)]
+    mfi = lift(lambda a, b: 100.0 if b == 0 else 100 - 100 / (1 + a / b), math_sum(positive, 14), math_sum(negative, 14))
+
+    # Accumulation/Distribution et Chaikin Money Flow
+    ad = [0.0 if (x == a and x == b) or a == b else ((2 * x - b - a) / (a - b)) * q for x, a, b, q in zip(c, h, l, v)]
+    accumulation, total = [], 0.0
+    for x in ad:
+        total += x
+        accumulation.append(total)
+    cmf = lift(lambda a, b: a / b, math_sum(ad, 20), math_sum(v, 20))
+
+    # Sans équivalent TradingView (volume acheteur des bougies Binance) : volume acheteur sur
+    # volume vendeur et part acheteuse, cumulés sur 14 bougies
+    buys, volumes = math_sum(d["taker_buy"], 14), math_sum(v, 14)
+    ratio = lift(lambda b, t: b / (t - b) if t - b > 0 else NA, buys, volumes)
+    buy_share = lift(lambda b, t: b / t if t > 0 else NA, buys, volumes)
+
+    return {
+        "taker_buy_sell_ratio_14": {"ratio": ratio, "buy_share": buy_share},
+        "obv": {"value": obv},
+        "mfi_14": {"value": mfi},
+        "accumulation_distribution": {"value": accumulation},
+        "chaikin_money_flow_20": {"value": cmf},
+    }
+
+
+def composite(d):
+    c, h, l, v = d["close"], d["high"], d["low"], d["volume"]
+    n = len(c)
+
+    # Ichimoku(9, 26, 52, 26) : nuage affiché `displacement - 1` bougies après son calcul
+    def donchian(length):
+        return lift(lambda a, b: (a + b) / 2, highest(h, length), lowest(l, length))
+
+    conversion, base, lead2 = donchian(9), donchian(26), donchian(52)
+    lead1 = lift(lambda a, b: (a + b) / 2, conversion, base)
+
+    # ta.vwap(hlc3, anchor, 2) : cumuls remis à zéro à chaque ancre
+    hlc3 = [(a + b + x) / 3 for a, b, x in zip(h, l, c)]
+
+    def vwap(starts):
+        columns = {"value": [], "upper": [], "lower": [], "deviation": []}
+        sums = None
+        for i in range(n):
+            if starts[i] is NA:
+                for series in columns.values():
+                    series.append(NA)
+                continue
+            if i == 0 or starts[i] != starts[i - 1]:
+                sums = [0.0, 0.0, 0.0]
+            sums = [sums[0] + v[i], sums[1] + hlc3[i] * v[i], sums[2] + hlc3[i] ** 2 * v[i]]
+            value = sums[1] / sums[0]
+            deviation = math.sqrt(max(sums[2] / sums[0] - value ** 2, 0.0))
+            columns["value"].append(value)
+            columns["upper"].append(value + 2 * deviation)
+            columns["lower"].append(value - 2 * deviation)
+            columns["deviation"].append(deviation)
+        return columns
+
+    day = 24 * 3600 * 1000
+    anchor = d["time"][100]
+
+    # Kaufman (10, 2, 30) : kama := alpha * src + (1 - alpha) * nz(kama[1], src)
+    fast, slow = 2 / 3, 2 / 31
+    kama, previous = [], NA
+    for i in range(n):
+        if i < 10:
+            kama.append(NA)
+            continue
+        volatility = sum(abs(c[j] - c[j - 1]) for j in range(i - 9, i + 1))
+        efficiency = 0 if volatility == 0 else abs(c[i] - c[i - 10]) / volatility
+        alpha = (efficiency * (fast - slow) + slow) ** 2
+        previous = alpha * c[i] + (1 - alpha) * (c[i] if previous is NA else previous)
+        kama.append(previous)
+
+    return {
+        "ichimoku_9_26_52_26": {
+            "tenkan_sen": conversion,
+            "kijun_sen": base,
+            "senkou_span_a": shift(lead1, 25),
+            "senkou_span_b": shift(lead2, 25),
+        },
+        "vwap_session_1d_2": vwap([t // day for t in d["time"]]),
+        "vwap_anchored_100_2": vwap([anchor if t >= anchor else NA for t in d["time"]]),
+        "kama_10_2_30": {"value": kama},
+    }
+
+
+def stops(d):
+    c, h, l = d["close"], d["high"], d["low"]
+    n = len(c)
+
+    # ta.sar(0.02, 0.02, 0.2), et le sens de la tendance (1 : SAR sous le prix)
+    sar, long = [NA], [0.0]
+    result = max_min = acceleration = is_below = None
+    for i in range(1, n):
+        first_trend_bar = False
+        if i == 1:
+            is_below = c[1] > c[0]
+            max_min = h[1] if is_below else l[1]
+            result = l[0] if is_below else h[0]
+            first_trend_bar = True
+            acceleration = 0.02
+        result = result + acceleration * (max_min - result)
+        if is_below:
+            if result > l[i]:
+                first_trend_bar, is_below = True, False
+                result = max(h[i], max_min)
+                max_min, acceleration = l[i], 0.02
+        elif result < h[i]:
+            first_trend_bar, is_below = True, True
+            result = min(l[i], max_min)
+            max_min, acceleration = h[i], 0.02
+        if not first_trend_bar:
+            if is_below and h[i] > max_min:
+                max_min, acceleration = h[i], min(acceleration + 0.02, 0.2)
+            elif not is_below and l[i] < max_min:
+                max_min, acceleration = l[i], min(acceleration + 0.02, 0.2)
+        if is_below:
+            result = min(result, l[i - 1], l[i - 2] if i > 1 else l[i - 1])
+        else:
+            result = max(result, h[i - 1], h[i - 2] if i > 1 else h[i - 1])
+        sar.append(result)
+        long.append(1.0 if is_below else 0.0)
+
+    # Chandelier Exit(22, 22, 3) sans cliquet
+    a22 = atr(d, 22)
+    return {
+        "parabolic_sar_0.02_0.02_0.2": {"value": sar, "long": long},
+        "chandelier_exit_22_22_3": {
+            "long": lift(lambda x, a: x - 3 * a, highest(h, 22), a22),
+            "short": lift(lambda x, a: x + 3 * a, lowest(l, 22), a22),
+        },
+    }
+
+
+def structures(d):
+    c, h, l = d["close"], d["high"], d["low"]
+    n = len(c)
+
+    # Points pivots journaliers (`ta.pivot_point_levels`) : niveaux de la journée UTC précédente,
+    # appliqués à toutes les bougies de la journée suivante
+    day = 24 * 3600 * 1000
+    days = [t // day for t in d["time"]]
+    sessions = {}
+    for i, key in enumerate(days):
+        high, low, _ = sessions.get(key, (h[i], l[i], c[i]))
+        sessions[key] = (max(high, h[i]), min(low, l[i]), c[i])
+
+    def pivot_points(levels):
+        names = list(levels(1.0, 1.0, 1.0))
+        columns = {name: [] for name in names}
+        for key in days:
+            previous = sessions.get(key - 1)
+            values = levels(*previous) if previous else dict.fromkeys(names, NA)
+            for name in names:
+                columns[name].append(values[name])
+        return columns
+
+    def traditional(high, low, close):
+        p = (high + low + close) / 3
+        return {
+            "pivot": p,
+            "r1": 2 * p - low,
+            "r2": p + (high - low),
+            "r3": high + 2 * (p - low),
+            "s1": 2 * p - high,
+            "s2": p - (high - low),
+            "s3": low - 2 * (high - p),
+        }
+
+    def fibonacci(high, low, close):
+        p, r = (high + low + close) / 3, high - low
+        return {
+            "pivot": p,
+            "r1": p + 0.382 * r,
+            "r2": p + 0.618 * r,
+            "r3": p + r,
+            "s1": p - 0.382 * r,
+            "s2": p - 0.618 * r,
+            "s3": p - r,
+        }
+
+    def camarilla(high, low, close):
+        r = high - low
+        return {
+            "pivot": (high + low + close) / 3,
+            "r1": close + r * 1.1 / 12,
+            "r2": close + r * 1.1 / 6,
+            "r3": close + r * 1.1 / 4,
+            "r4": close + r * 1.1 / 2,
+            "s1": close - r * 1.1 / 12,
+            "s2": close - r * 1.1 / 6,
+            "s3": close - r * 1.1 / 4,
+            "s4": close - r * 1.1 / 2,
+        }
+
+    # Fractales : `ta.pivothigh(k, k)` / `ta.pivotlow(k, k)`, pivot strictement au-dessus (au-dessous)
+    # des `k` bougies de chaque côté, publié sur la bougie qui le confirme
+    def pivots(src, k, better):
+        out = [NA] * n
+        for i in range(2 * k, n):
+            candidate = src[i - k]
+            if all(better(candidate, src[j]) for j in range(i - 2 * k, i + 1) if j != i - k):
+                out[i] = candidate
+        return out
+
+    # Dernière valeur définie (`ta.valuewhen(not na(x), x, 0)`)
+    def forward_fill(src):
+        out, last = [], NA
+        for x in src:
+            last = last if x is NA else x
+            out.append(last)
+        return out
+
+    return {
+        "pivot_points_classic_1d": pivot_points(traditional),
+        "pivot_points_fibonacci_1d": pivot_points(fibonacci),
+        "pivot_points_camarilla_1d": pivot_points(camarilla),
+        "fractals_2_2": {"high": pivots(h, 2, lambda a, b: a > b), "low": pivots(l, 2, lambda a, b: a < b)},
+        # Stop sous le dernier creux de swing (3, 3) pour un long, au-dessus du dernier sommet pour un short
+        "swing_stop_3_3": {
+            "long": forward_fill(pivots(l, 3, lambda a, b: a < b)),
+            "short": forward_fill(pivots(h, 3, lambda a, b: a > b)),
+        },
+    }
+
+
+def write(name, time, columns):
+    path = os.path.join(OUTPUT, name + ".csv")
+    with open(path, "w") as f:
+        f.write(",".join(["open_time"] + list(columns)) + "\n")
+        for i, open_time in enumerate(time):
+            values = ["NaN" if series[i] is NA else repr(float(series[i])) for series in columns.values()]
+            f.write(",".join([str(open_time)] + values) + "\n")
+
+
+def main():
+    d = load()
+    os.makedirs(OUTPUT, exist_ok=True)
+    for family in (moving_averages, momentum, volatility, trend, volume, composite, stops, structures):
+        for name, columns in family(d).items():
+            write(name, d["time"], columns)
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,426 @@
+mod common;
+
+use binance::model::KlineSummary;
+use common::golden::{column, Golden, Outputs, GOLDEN_FIXTURE};
+use common::load_klines;
+use root::data::Interval;
+use root::indicator::*;
+
+// Chaque indicateur est calculé d'un bloc puis bougie par bougie sur `synthetic_1h.csv`.
+// Un nouvel indicateur ajoute ici un test : `Golden::reference` s'il existe sur TradingView ou
+// si sa formule se recalcule simplement (valeurs ajoutées à `reference.py`), `Golden::snapshot` sinon.
+
+fn values(series: &[f64]) -> Vec<f64> {
+    series.to_vec()
+}
+
+fn moving_average<M: MovingAverage + 'static>(name: &str, build: impl Fn() -> M) {
+    Golden::reference(name).check_observer(
+        |klines| MovingAverageIndicator::new(klines, build(), PriceSource::Close),
+        |indicator| vec![("value", values(&indicator.values))],
+    );
+}
+
+// Swings publiés sous la bougie du pivot (NaN ailleurs) : une colonne par type de swing
+fn swing_columns(klines: &[KlineSummary], swings: &[Swing]) -> Outputs {
+    let price_at = |kind: SwingKind| {
+        column(klines, |kline| {
+            swings
+                .iter()
+                .find(|swing| swing.kind == kind && swing.open_time == kline.open_time)
+                .map_or(f64::NAN, |swing| swing.price)
+        })
+    };
+    vec![("swing_high", price_at(SwingKind::High)), ("swing_low", price_at(SwingKind::Low))]
+}
+
+#[test]
+fn moving_averages_match_tradingview() {
+    moving_average("sma_20", || Sma::new(20).unwrap());
+    moving_average("ema_20", || Ema::new(20).unwrap());
+    moving_average("rma_14", || Rma::new(14).unwrap());
+    moving_average("wma_20", || Wma::new(20).unwrap());
+    moving_average("hma_20", || Hma::new(20).unwrap());
+    moving_average("dema_20", || Dema::new(20).unwrap());
+    moving_average("tema_20", || Tema::new(20).unwrap());
+    moving_average("vwma_20", || Vwma::new(20).unwrap());
+    moving_average("kama_10_2_30", || Kama::new(10, 2, 30).unwrap());
+}
+
+#[test]
+fn momentum_oscillators_match_tradingview() {
+    Golden::reference("rsi_14").check_observer(
+        |klines| Rsi::new(klines, 14, PriceSource::Close).unwrap(),
+        |rsi| vec![("value", values(&rsi.values))],
+    );
+    Golden::reference("stochastic_14_3_3").check_observer(
+        |klines| Stochastic::new(klines, 14, 3, 3).unwrap(),
+        |stochastic| vec![("k", values(&stochastic.k)), ("d", values(&stochastic.d))],
+    );
+    Golden::reference("stochastic_rsi_14_14_3_3").check_observer(
+        |klines| StochasticRsi::new(klines, 14, 14, 3, 3, PriceSource::Close).unwrap(),
+        |stochastic| vec![("k", values(&stochastic.k)), ("d", values(&stochastic.d))],
+    );
+    Golden::reference("macd_12_26_9").check_observer(
+        |klines| Macd::new(klines, 12, 26, 9, PriceSource::Close).unwrap(),
+        |macd| {
+            vec![
+                ("macd", values(&macd.macd)),
+                ("signal", values(&macd.signal)),
+                ("histogram", values(&macd.histogram)),
+            ]
+        },
+    );
+    Golden::reference("cci_20").check_observer(
+        |klines| Cci::new(klines, 20, PriceSource::HLC3).unwrap(),
+        |cci| vec![("value", values(&cci.values))],
+    );
+    Golden::reference("williams_r_14").check_observer(
+        |klines| WilliamsR::new(klines, 14).unwrap(),
+        |williams_r| vec![("value", values(&williams_r.values))],
+    );
+    Golden::reference("rate_of_change_9").check_observer(
+        |klines| RateOfChange::new(klines, 9, PriceSource::Close).unwrap(),
+        |roc| vec![("value", values(&roc.values))],
+    );
+}
+
+#[test]
+fn volatility_indicators_match_tradingview() {
+    Golden::reference("atr_14").check_observer(
+        |klines| Atr::new(klines, 14).unwrap(),
+        |atr| vec![("value", values(&atr.values))],
+    );
+    Golden::reference("bollinger_bands_20_2").check_observer(
+        |klines| BollingerBands::new(klines, 20, 2.0, PriceSource::Close).unwrap(),
+        |bands| {
+            vec![
+                ("basis", values(&bands.basis)),
+                ("upper", values(&bands.upper_band)),
+                ("lower", values(&bands.lower_band)),
+                ("percent_b", values(&bands.percent_b)),
+                ("bandwidth", values(&bands.bandwidth)),
+            ]
+        },
+    );
+    Golden::reference("keltner_channel_20_10_2").check_observer(
+        |klines| KeltnerChannel::new(klines, 20, 10, 2.0, PriceSource::Close).unwrap(),
+        |channel| {
+            vec![
+                ("basis", values(&channel.basis)),
+                ("upper", values(&channel.upper_band)),
+                ("lower", values(&channel.lower_band)),
+            ]
+        },
+    );
+    Golden::reference("atr_bands_14_2").check_observer(
+        |klines| AtrBands::new(klines, 14, 2.0, PriceSource::HL2).unwrap(),
+        |bands| {
+            vec![
+                ("basis", values(&bands.basis)),
+                ("upper", values(&bands.upper_band)),
+                ("lower", values(&bands.lower_band)),
+            ]
+        },
+    );
+    // Bandes de Bollinger (20, 2) dans le canal de Keltner (20, 20, 1.5)
+    Golden::reference("squeeze_20_2_1.5").check_observer(
+        |klines| Squeeze::new(klines, 20, 2.0, 1.5).unwrap(),
+        |squeeze| {
+            vec![
+                ("squeeze_on", column(squeeze.squeeze_on.iter(), |on| f64::from(u8::from(*on)))),
+                ("released", column(squeeze.released.iter(), |released| f64::from(u8::from(*released)))),
+            ]
+        },
+    );
+    Golden::reference("standard_deviation_20").check_observer(
+        |klines| StandardDeviation::new(klines, 20, PriceSource::Close).unwrap(),
+        |deviation| vec![("value", values(&deviation.values))],
+    );
+    Golden::reference("z_score_20").check_observer(
+        |klines| ZScore::new(klines, 20, PriceSource::Close).unwrap(),
+        |z_score| vec![("value", values(&z_score.values))],
+    );
+}
+
+#[test]
+fn strategy_indicators_match_tradingview() {
+    Golden::reference("choppiness_index_14").check_observer(
+        |klines| ChoppinessIndex::new(klines, 14).unwrap(),
+        |choppiness| vec![("value", values(&choppiness.values))],
+    );
+    Golden::reference("donchian_channel_20_20").check_observer(
+        |klines| DonchianChannel::new(klines, 20, 20).unwrap(),
+        |channel| {
+            vec![
+                ("upper", values(&channel.upper_band)),
+                ("lower", values(&channel.lower_band)),
+                ("basis", values(&channel.basis)),
+            ]
+        },
+    );
+    Golden::reference("atr_stop_loss_14_1.5").check_observer(
+        |klines| ATRStopLoss::new(klines, 14, 1.5).unwrap(),
+        |stop| vec![("long", values(&stop.stop_losses)), ("short", values(&stop.short_stop_losses))],
+    );
+}
+
+#[test]
+fn trend_indicators_match_tradingview() {
+    Golden::reference("adx_14_14").check_observer(
+        |klines| Adx::new(klines, 14, 14).unwrap(),
+        |adx| {
+            vec![
+                ("plus_di", values(&adx.plus_di)),
+                ("minus_di", values(&adx.minus_di)),
+                ("adx", values(&adx.adx)),
+            ]
+        },
+    );
+    Golden::reference("aroon_14").check_observer(
+        |klines| Aroon::new(klines, 14).unwrap(),
+        |aroon| {
+            vec![
+                ("up", values(&aroon.up)),
+                ("down", values(&aroon.down)),
+                ("oscillator", values(&aroon.oscillator)),
+            ]
+        },
+    );
+    Golden::reference("vortex_14").check_observer(
+        |klines| Vortex::new(klines, 14).unwrap(),
+        |vortex| vec![("plus", values(&vortex.plus)), ("minus", values(&vortex.minus))],
+    );
+    Golden::reference("supertrend_10_3").check_observer(
+        |klines| SuperTrend::new(klines, 10, 3.0).unwrap(),
+        |supertrend| {
+            vec![
+                ("value", values(&supertrend.values)),
+                ("direction", column(supertrend.direction.iter(), |direction| f64::from(*direction))),
+            ]
+        },
+    );
+    Golden::reference("linear_regression_20").check_observer(
+        |klines| LinearRegression::new(klines, 20, PriceSource::Close).unwrap(),
+        |regression| {
+            vec![
+                ("value", values(&regression.values)),
+                ("slope", values(&regression.slope)),
+                ("intercept", values(&regression.intercept)),
+                ("r_squared", values(&regression.r_squared)),
+            ]
+        },
+    );
+}
+
+#[test]
+fn volume_indicators_match_tradingview() {
+    Golden::reference("obv").check_observer(Obv::new, |obv| vec![("value", values(&obv.values))]);
+    Golden::reference("mfi_14").check_observer(
+        |klines| Mfi::new(klines, 14).unwrap(),
+        |mfi| vec![("value", values(&mfi.values))],
+    );
+    Golden::reference("accumulation_distribution").check_observer(
+        AccumulationDistribution::new,
+        |indicator| vec![("value", values(&indicator.values))],
+    );
+    Golden::reference("chaikin_money_flow_20").check_observer(
+        |klines| ChaikinMoneyFlow::new(klines, 20).unwrap(),
+        |cmf| vec![("value", values(&cmf.values))],
+    );
+    // Sans équivalent TradingView : formule de l'indicateur dans `reference.py`
+    Golden::reference("taker_buy_sell_ratio_14").check_observer(
+        |klines| TakerBuySellRatio::new(klines, 14).unwrap(),
+        |taker| vec![("ratio", values(&taker.ratio)), ("buy_share", values(&taker.buy_share))],
+    );
+}
+
+// Bandes de la VWAP : `ta.vwap(hlc3, anchor, 2)`
+fn vwap_columns(vwap: &Vwap) -> Outputs {
+    vec![
+        ("value", values(&vwap.values)),
+        ("upper", values(&vwap.upper_band)),
+        ("lower", values(&vwap.lower_band)),
+        ("deviation", values(&vwap.deviation)),
+    ]
+}
+
+#[test]
+fn composite_indicators_match_tradingview() {
+    Golden::reference("ichimoku_9_26_52_26").check_observer(
+        |klines| Ichimoku::standard(klines).unwrap(),
+        |ichimoku| {
+            vec![
+                ("tenkan_sen", values(&ichimoku.tenkan_sen)),
+                ("kijun_sen", values(&ichimoku.kijun_sen)),
+                ("senkou_span_a", values(&ichimoku.senkou_span_a)),
+                ("senkou_span_b", values(&ichimoku.senkou_span_b)),
+            ]
+        },
+    );
+    Golden::reference("vwap_session_1d_2").check_observer(
+        |klines| Vwap::new(klines, VwapAnchor::Session(Interval::Days(1)), 2.0, PriceSource::HLC3).unwrap(),
+        vwap_columns,
+    );
+    let anchor = load_klines(GOLDEN_FIXTURE)[100].open_time;
+    Golden::reference("vwap_anchored_100_2").check_observer(
+        |klines| Vwap::new(klines, VwapAnchor::Anchored(anchor), 2.0, PriceSource::HLC3).unwrap(),
+        vwap_columns,
+    );
+}
+
+#[test]
+fn composite_indicators_snapshot() {
+    Golden::snapshot("volume_profile_48_24_0.7").check_observer(
+        |klines| VolumeProfile::new(klines, 48, 24, 0.7).unwrap(),
+        |profile| {
+            vec![
+                ("poc", values(&profile.poc)),
+                ("value_area_high", values(&profile.value_area_high)),
+                ("value_area_low", values(&profile.value_area_low)),
+            ]
+        },
+    );
+}
+
+#[test]
+fn stop_indicators_match_tradingview() {
+    Golden::reference("parabolic_sar_0.02_0.02_0.2").check_observer(
+        |klines| ParabolicSar::new(klines, 0.02, 0.02, 0.2).unwrap(),
+        |sar| {
+            vec![
+                ("value", values(&sar.values)),
+                ("long", column(sar.long.iter(), |long| f64::from(u8::from(*long)))),
+            ]
+        },
+    );
+    Golden::reference("chandelier_exit_22_22_3").check_observer(
+        |klines| ChandelierExit::new(klines, 22, 22, 3.0).unwrap(),
+        |exit| vec![("long", values(&exit.long_stop)), ("short", values(&exit.short_stop))],
+    );
+    // Dernier `ta.pivotlow(3, 3)` / `ta.pivothigh(3, 3)` confirmé
+    Golden::reference("swing_stop_3_3").check_observer(
+        |klines| SwingStop::new(klines, 3, 3).unwrap(),
+        |stop| vec![("long", values(&stop.long_stop)), ("short", values(&stop.short_stop))],
+    );
+}
+
+// Niveaux de `ta.pivot_point_levels` sur la journée précédente (r4/s4 pour Camarilla seulement)
+fn pivot_columns(pivots: &PivotPoints) -> Outputs {
+    let level = |f: fn(&PivotLevels) -> f64| column(pivots.levels.iter(), move |levels| levels.as_ref().map_or(f64::NAN, f));
+    let mut columns = vec![
+        ("pivot", level(|levels| levels.pivot)),
+        ("r1", level(|levels| levels.r1)),
+        ("r2", level(|levels| levels.r2)),
+        ("r3", level(|levels| levels.r3)),
+    ];
+    if pivots.kind() == PivotKind::Camarilla {
+        columns.push(("r4", level(|levels| levels.r4.unwrap())));
+    }
+    columns.extend([
+        ("s1", level(|levels| levels.s1)),
+        ("s2", level(|levels| levels.s2)),
+        ("s3", level(|levels| levels.s3)),
+    ]);
+    if pivots.kind() == PivotKind::Camarilla {
+        columns.push(("s4", level(|levels| levels.s4.unwrap())));
+    }
+    columns
+}
+
+#[test]
+fn swing_structures_match_tradingview() {
+    for (name, kind) in [
+        ("pivot_points_classic_1d", PivotKind::Classic),
+        ("pivot_points_fibonacci_1d", PivotKind::Fibonacci),
+        ("pivot_points_camarilla_1d", PivotKind::Camarilla),
+    ] {
+        Golden::reference(name).check_observer(|klines| PivotPoints::new(klines, Interval::Days(1), kind).unwrap(), pivot_columns);
+    }
+
+    // `ta.pivothigh(2, 2)` / `ta.pivotlow(2, 2)` : prix publié sur la bougie de confirmation
+    let klines = load_klines(GOLDEN_FIXTURE);
+    Golden::reference("fractals_2_2").check_observer(
+        |klines| Fractals::new(klines, 2, 2).unwrap(),
+        |fractals| {
+            let price_at = |kind: SwingKind| {
+                column(&klines, |kline| {
+                    fractals
+                        .swings
+                        .iter()
+                        .find(|swing| swing.kind == kind && swing.confirmed_at == kline.open_time)
+                        .map_or(f64::NAN, |swing| swing.price)
+                })
+            };
+            vec![("high", price_at(SwingKind::High)), ("low", price_at(SwingKind::Low))]
+        },
+    );
+}
+
+#[test]
+fn swing_structures_snapshot() {
+    let klines = load_klines(GOLDEN_FIXTURE);
+
+    Golden::snapshot("zigzag_1").check_observer(
+        |klines| ZigZag::new(klines, 1.0).unwrap(),
+        |zigzag| swing_columns(&klines, &zigzag.swings),
+    );
+    Golden::snapshot("candlestick_patterns").check_observer(
+        |klines| CandlestickPatterns::new(klines, PatternTolerance::default()).unwrap(),
+        |patterns| {
+            // Un bit par motif, dans l'ordre de `CandlePattern::ALL`
+            let bits = |flags: &PatternFlags| {
+                CandlePattern::ALL
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, pattern)| flags.contains(**pattern))
+                    .map(|(bit, _)| f64::from(1u16 << bit))
+                    .sum()
+            };
+            vec![("flags", column(patterns.patterns.iter(), bits))]
+        },
+    );
+}
+
+#[test]
+fn support_resistance_snapshot() {
+    // Les zones n'ont pas d'historique : on relève la zone la plus proche après chaque bougie
+    let klines = load_klines(GOLDEN_FIXTURE);
+    let mut zones = SupportResistance::new(&[], 2, 2, 0.5, 2).unwrap();
+    let (mut support, mut resistance) = (Vec::new(), Vec::new());
+    for end in 1..=klines.len() {
+        zones.add(&klines[..end]);
+        let close = PriceSource::Close.extract(&klines[end - 1]);
+        support.push(zones.nearest_support(close).map_or(f64::NAN, |zone| zone.center()));
+        resistance.push(zones.nearest_resistance(close).map_or(f64::NAN, |zone| zone.center()));
+    }
+
+    Golden::snapshot("support_resistance_2_2_0.5_2").check(&klines, &[("support", support), ("resistance", resistance)]);
+}
+
+#[test]
+fn statistical_indicators_snapshot() {
+    Golden::snapshot("hurst_exponent_64").check_observer(
+        |klines| HurstExponent::new(klines, 64, PriceSource::Close).unwrap(),
+        |hurst| vec![("value", values(&hurst.values))],
+    );
+    Golden::snapshot("autocorrelation_20_1").check_observer(
+        |klines| Autocorrelation::new(klines, 20, 1, PriceSource::Close).unwrap(),
+        |autocorrelation| vec![("value", values(&autocorrelation.values))],
+    );
+
+    // Référence : la fixture avec une clôture légèrement perturbée
+    let reference: Vec<KlineSummary> = load_klines(GOLDEN_FIXTURE)
+        .into_iter()
+        .enumerate()
+        .map(|(index, mut kline)| {
+            let close = PriceSource::Close.extract(&kline) * (1.0 + 0.002 * (index as f64 / 3.0).sin());
+            kline.close = close.to_string();
+            kline
+        })
+        .collect();
+    Golden::snapshot("rolling_correlation_20").check_observer(
+        |klines| RollingCorrelation::new(klines, &reference, 20, PriceSource::Close).unwrap(),
+        |correlation| vec![("correlation", values(&correlation.correlation)), ("beta", values(&correlation.beta))],
+    );
+}
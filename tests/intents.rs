@@ -0,0 +1,212 @@
+mod common;
+
+use common::load_klines;
+use root::indicator::Side;
+use root::strategy::{
+    Executor, Intent, MarketState, Mode, OrderSide, PositionRules, RuleStrategy, SignalStrategy, SizeHint, TradingStrategy,
+    TurtleStrategy,
+};
+use root::Error;
+
+const FIXTURE: &str = "synthetic_1h.csv";
+const WARMUP: usize = 100;
+
+fn enter(side: Side, stop: Option<f64>, size: SizeHint) -> Intent {
+    Intent::Enter { side, stop, size }
+}
+
+#[test]
+fn signals_depend_only_on_market_state() {
+    let klines = load_klines(FIXTURE);
+    let strategy = RuleStrategy::parse("entry_long: close > sma(10)\nexit_long: close < sma(10)\nstop_long: low - 1").unwrap();
+    let mut manager = strategy.prepare(&klines).unwrap();
+    let sma = strategy.indicators()[0].key();
+
+    let (mut entries, mut exits) = (0, 0);
+    for kline in &klines[WARMUP..] {
+        manager.add_kline(kline.clone());
+        let market = MarketState::new(kline, &manager, None).unwrap();
+        let intents = strategy.signals(&market).unwrap();
+        assert_eq!(strategy.signals(&market).unwrap(), intents);
+
+        let average = market.series(&sma, "value");
+        if market.close > average {
+            entries += 1;
+            let low: f64 = kline.low.parse().unwrap();
+            assert_eq!(intents, [enter(Side::Long, Some(low - 1.0), SizeHint::Capital(1.0))]);
+        } else {
+            assert!(intents.is_empty());
+        }
+
+        // Avec une position ouverte, seule la règle de sortie est évaluée
+        let mut executor = Executor::new(Mode::Backtest, "ETHBTC", 1.0);
+        executor.apply(0, 1.0, &[enter(Side::Long, None, SizeHint::Quantity(1.0))], &PositionRules::default()).unwrap();
+        let market = MarketState::new(kline, &manager, executor.position()).unwrap();
+        let intents = strategy.signals(&market).unwrap();
+        if market.close < average {
+            exits += 1;
+            assert_eq!(intents, [Intent::Exit { side: Side::Long }]);
+        } else {
+            assert!(intents.is_empty());
+        }
+    }
+    assert!(entries > 0 && exits > 0);
+}
+
+#[test]
+fn intents_become_orders() {
+    let rules = PositionRules::new(2, 0.5, Vec::new()).unwrap();
+    let mut executor = Executor::new(Mode::Backtest, "ETHBTC", 1000.0);
+
+    let orders = executor.apply(0, 100.0, &[enter(Side::Long, Some(95.0), SizeHint::Capital(0.4))], &rules).unwrap();
+    assert_eq!(orders.len(), 1);
+    assert_eq!((orders[0].side, orders[0].quantity, orders[0].price), (OrderSide::Buy, 4.0, 100.0));
+    assert_eq!(executor.capital(), 600.0);
+    assert_eq!(executor.equity(), 1000.0);
+
+    // Entrée contraire ignorée, unité ajoutée dans le sens de la position
+    let intents = [
+        enter(Side::Short, None, SizeHint::Quantity(1.0)),
+        enter(Side::Long, Some(98.0), SizeHint::Risk { fraction: 0.01, distance: 2.5 }),
+        Intent::ModifyStop { side: Side::Long, stop: 99.0 },
+    ];
+    let orders = executor.apply(1, 104.0, &intents, &rules).unwrap();
+    assert_eq!(orders.len(), 1);
+    assert_eq!(orders[0].quantity, 4.0);
+    let position = executor.position().unwrap();
+    assert_eq!(position.units().len(), 2);
+    assert!(position.units().iter().all(|unit| unit.stop == Some(99.0)));
+
+    // Stop franchi : les deux unités sortent au prix moyen
+    let orders = executor.apply(2, 98.5, &[], &rules).unwrap();
+    assert_eq!((orders[0].side, orders[0].quantity), (OrderSide::Sell, 8.0));
+    assert!(executor.position().is_none());
+    assert_eq!(executor.trades()[0].entry_price, 102.0);
+    assert_eq!(executor.capital(), 1000.0 + executor.trades()[0].profit());
+    assert_eq!(executor.orders().len(), 3);
+
+    let intents = [enter(Side::Long, None, SizeHint::Risk { fraction: 0.01, distance: f64::NAN })];
+    assert!(matches!(executor.apply(3, 100.0, &intents, &rules), Err(Error::InvalidParameter { name: "size", .. })));
+}
+
+#[test]
+fn invalid_stops_fail_the_intent() {
+    let invalid_stop = |result| matches!(result, Err(Error::InvalidParameter { name: "stop", .. }));
+    let targets = PositionRules::new(1, 0.5, PositionRules::parse_take_profits("1:1").unwrap()).unwrap();
+    let mut executor = Executor::new(Mode::Backtest, "ETHBTC", 1000.0);
+
+    // Aucun ordre n'est envoyé pour une entrée refusée
+    assert!(invalid_stop(executor.apply(0, 100.0, &[enter(Side::Long, Some(f64::NAN), SizeHint::Quantity(1.0))], &targets)));
+    assert!(invalid_stop(executor.apply(0, 100.0, &[enter(Side::Long, Some(101.0), SizeHint::Quantity(1.0))], &targets)));
+    assert!(invalid_stop(executor.apply(0, 100.0, &[enter(Side::Long, None, SizeHint::Quantity(1.0))], &targets)));
+    assert!(executor.position().is_none() && executor.orders().is_empty());
+    assert_eq!(executor.capital(), 1000.0);
+
+    // Sans prise de profit, une entrée sans stop reste ouverte jusqu'à sa sortie
+    let rules = PositionRules::default();
+    executor.apply(1, 100.0, &[enter(Side::Long, None, SizeHint::Quantity(1.0))], &rules).unwrap();
+    assert!(executor.apply(2, 1.0, &[], &rules).unwrap().is_empty());
+    let modify = Intent::ModifyStop { side: Side::Long, stop: f64::NAN };
+    assert!(invalid_stop(executor.apply(3, 100.0, &[modify], &rules)));
+    assert_eq!(executor.position().unwrap().units()[0].stop, None);
+}
+
+#[test]
+fn risk_sizing_is_capped_by_the_available_capital() {
+    let rules = PositionRules::new(4, 0.5, Vec::new()).unwrap();
+    let mut executor = Executor::new(Mode::Backtest, "ETHBTC", 1000.0);
+    let size = SizeHint::Risk { fraction: 0.2, distance: 10.0 };
+
+    // 20 unités risquent 20 % du capital sur 10, mais seules 10 unités sont payables
+    executor.apply(0, 100.0, &[enter(Side::Long, Some(90.0), size)], &rules).unwrap();
+    assert_eq!(executor.position().unwrap().quantity(), 10.0);
+    // Tout le capital est engagé : l'unité suivante est ignorée sans erreur
+    assert!(executor.apply(1, 110.0, &[enter(Side::Long, Some(100.0), size)], &rules).unwrap().is_empty());
+    assert_eq!(executor.position().unwrap().units().len(), 1);
+    assert_eq!(executor.capital(), 0.0);
+
+    // En dessous du plafond, la taille au risque est inchangée
+    let mut executor = Executor::new(Mode::Backtest, "ETHBTC", 1000.0);
+    let size = SizeHint::Risk { fraction: 0.01, distance: 10.0 };
+    executor.apply(0, 100.0, &[enter(Side::Short, Some(110.0), size)], &rules).unwrap();
+    assert_eq!(executor.position().unwrap().quantity(), 1.0);
+}
+
+#[test]
+fn short_positions_are_bought_back() {
+    let rules = PositionRules::default();
+    let mut executor = Executor::new(Mode::Backtest, "ETHBTC", 1000.0);
+
+    let orders = executor.apply(0, 100.0, &[enter(Side::Short, Some(110.0), SizeHint::Quantity(2.0))], &rules).unwrap();
+    assert_eq!(orders[0].side, OrderSide::Sell);
+    assert_eq!(executor.equity(), 1000.0);
+
+    assert!(executor.apply(1, 90.0, &[Intent::Exit { side: Side::Long }], &rules).unwrap().is_empty());
+    let orders = executor.apply(2, 90.0, &[Intent::Exit { side: Side::Short }], &rules).unwrap();
+    assert_eq!((orders[0].side, orders[0].quantity, orders[0].price), (OrderSide::Buy, 2.0, 90.0));
+    assert_eq!(executor.capital(), 1020.0);
+    assert_eq!(executor.trades()[0].profit(), 20.0);
+}
+
+#[test]
+fn execution_logs_can_be_bounded() {
+    let rules = PositionRules::default();
+    let round_trip = |executor: &mut Executor, time: i64| {
+        executor.apply(time, 100.0, &[enter(Side::Long, None, SizeHint::Quantity(1.0))], &rules).unwrap();
+        executor.apply(time + 1, 101.0, &[Intent::Exit { side: Side::Long }], &rules).unwrap();
+    };
+
+    let mut executor = Executor::new(Mode::Backtest, "ETHBTC", 1000.0).with_log_capacity(Some(3));
+    for time in (0..20).step_by(2) {
+        round_trip(&mut executor, time);
+    }
+    // Seules les dernières entrées sont conservées, le capital tient compte de toutes les transactions
+    assert_eq!(executor.trades().iter().map(|trade| trade.exit_time).collect::<Vec<_>>(), [15, 17, 19]);
+    assert_eq!(executor.orders().iter().map(|order| order.time).collect::<Vec<_>>(), [17, 18, 19]);
+    assert_eq!(executor.capital(), 1010.0);
+
+    // Journal complet par défaut en backtest
+    let mut executor = Executor::new(Mode::Backtest, "ETHBTC", 1000.0);
+    for time in (0..20).step_by(2) {
+        round_trip(&mut executor, time);
+    }
+    assert_eq!((executor.trades().len(), executor.orders().len()), (10, 20));
+}
+
+#[test]
+fn intents_can_be_logged() {
+    assert_eq!(
+        enter(Side::Long, Some(95.5), SizeHint::Capital(1.0)).to_string(),
+        "enter Long (Capital(1.0)), stop at 95.5"
+    );
+    assert_eq!(Intent::Exit { side: Side::Short }.to_string(), "exit Short");
+    assert_eq!(Intent::ModifyStop { side: Side::Long, stop: 99.0 }.to_string(), "move Long stop to 99");
+    assert_eq!(Intent::ModifyStop { side: Side::Short, stop: 99.0 }.side(), Side::Short);
+}
+
+#[test]
+fn strategies_replay_through_a_separate_executor() {
+    let klines = load_klines(FIXTURE);
+    let mut strategy = TurtleStrategy::with_lengths(10, 5, Mode::Backtest, 1000.0).unwrap().with_atr_length(14);
+    let rules = PositionRules::new(4, 0.5, Vec::new()).unwrap();
+    let mut manager = strategy.prepare(&klines).unwrap();
+    let mut replay_manager = strategy.prepare(&klines).unwrap();
+    let mut executor = Executor::new(Mode::Backtest, "ETHBTC", 1000.0);
+
+    let mut logged = Vec::new();
+    for kline in &klines[WARMUP..] {
+        replay_manager.add_kline(kline.clone());
+        let market = MarketState::new(kline, &replay_manager, executor.position()).unwrap();
+        let intents = strategy.signals(&market).unwrap();
+        let close = market.close;
+        executor.apply(kline.open_time, close, &intents, &rules).unwrap();
+        logged.extend(intents);
+
+        strategy.execute(kline.clone(), &mut manager).unwrap();
+    }
+
+    assert!(logged.iter().any(|intent| matches!(intent, Intent::ModifyStop { .. })));
+    assert!(!executor.trades().is_empty());
+    assert_eq!(executor.trades(), strategy.trades());
+    assert_eq!(executor.orders(), strategy.executor().orders());
+}
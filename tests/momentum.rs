@@ -0,0 +1,145 @@
+mod common;
+
+use common::{assert_series, load_klines};
+use root::indicator::{Cci, Macd, PriceSource, RateOfChange, Rsi, Stochastic, StochasticRsi, WilliamsR};
+
+// Valeurs de référence calculées avec les définitions Pine Script de TradingView
+// (ta.rsi, ta.stoch, ta.macd, ta.cci, ta.wpr, ta.roc) sur le fixture synthétique. Ces points
+// relevés à la main recoupent les séries complètes générées par `reference.py` (tests/golden.rs).
+const FIXTURE: &str = "synthetic_1h.csv";
+
+#[test]
+fn rsi_matches_tradingview() {
+    let klines = load_klines(FIXTURE);
+    let rsi = Rsi::new(&klines, 14, PriceSource::Close).unwrap();
+
+    assert_eq!(rsi.values.len(), klines.len());
+    assert_series(
+        "rsi",
+        &rsi.values,
+        &[
+            (13, f64::NAN),
+            (14, 91.25364570426424),
+            (33, 32.91461366330704),
+            (60, 68.46092309570126),
+            (150, 31.55583686721566),
+            (199, 33.92363945742163),
+        ],
+    );
+}
+
+#[test]
+fn stochastic_matches_tradingview() {
+    let klines = load_klines(FIXTURE);
+    let stochastic = Stochastic::new(&klines, 14, 3, 3).unwrap();
+
+    assert_series(
+        "stoch_k",
+        &stochastic.k,
+        &[
+            (14, f64::NAN),
+            (15, 88.91395633194516),
+            (33, 14.462359234256846),
+            (60, 89.13564618169796),
+            (199, 9.841005527841377),
+        ],
+    );
+    assert_series(
+        "stoch_d",
+        &stochastic.d,
+        &[(15, f64::NAN), (33, 13.349521380627351), (150, 10.939027312878347), (199, 8.649432897914751)],
+    );
+}
+
+#[test]
+fn stochastic_rsi_matches_tradingview() {
+    let klines = load_klines(FIXTURE);
+    let stochastic_rsi = StochasticRsi::new(&klines, 14, 14, 3, 3, PriceSource::Close).unwrap();
+
+    assert_series(
+        "stochrsi_k",
+        &stochastic_rsi.k,
+        &[(33, 9.975431855294792), (60, 98.64709172352421), (150, 14.597400015745265), (199, 0.8169743802000705)],
+    );
+    assert_series(
+        "stochrsi_d",
+        &stochastic_rsi.d,
+        &[(33, 7.366185638799055), (60, 96.97375683136822), (150, 8.03850273659271), (199, 0.2859339351058731)],
+    );
+}
+
+#[test]
+fn macd_matches_tradingview() {
+    let klines = load_klines(FIXTURE);
+    let macd = Macd::new(&klines, 12, 26, 9, PriceSource::Close).unwrap();
+
+    assert_series(
+        "macd",
+        &macd.macd,
+        &[(24, f64::NAN), (60, 0.0004660045101620852), (150, -0.001186630191290991), (199, -0.00039563608929712307)],
+    );
+    assert_series(
+        "signal",
+        &macd.signal,
+        &[(32, f64::NAN), (33, -0.0006847091944253363), (60, 0.00010951644395024064), (199, 0.00025637220739072074)],
+    );
+    assert_series(
+        "histogram",
+        &macd.histogram,
+        &[(33, -0.00018308664255799114), (150, -0.0004098925850227732), (199, -0.0006520082966878438)],
+    );
+}
+
+#[test]
+fn cci_matches_tradingview() {
+    let klines = load_klines(FIXTURE);
+    let cci = Cci::new(&klines, 20, PriceSource::HLC3).unwrap();
+
+    assert_series(
+        "cci",
+        &cci.values,
+        &[(18, f64::NAN), (33, -67.04176019659855), (60, 83.14150697665644), (150, -71.08315744857347), (199, -108.06080550378596)],
+    );
+}
+
+#[test]
+fn williams_r_matches_tradingview() {
+    let klines = load_klines(FIXTURE);
+    let williams_r = WilliamsR::new(&klines, 14).unwrap();
+
+    assert_series(
+        "wpr",
+        &williams_r.values,
+        &[(12, f64::NAN), (13, -11.479567839511054), (60, -9.140479128482838), (199, -89.17539050154333)],
+    );
+}
+
+#[test]
+fn rate_of_change_matches_tradingview() {
+    let klines = load_klines(FIXTURE);
+    let roc = RateOfChange::new(&klines, 9, PriceSource::Close).unwrap();
+
+    assert_series(
+        "roc",
+        &roc.values,
+        &[(8, f64::NAN), (13, 4.713439964323695), (60, 2.052382133228958), (199, -7.031141562002386)],
+    );
+}
+
+#[test]
+fn incremental_updates_match_batch_computation() {
+    let klines = load_klines(FIXTURE);
+    let batch = Rsi::new(&klines, 14, PriceSource::Close).unwrap();
+
+    let mut incremental = Rsi::new(&klines[..50], 14, PriceSource::Close).unwrap();
+    for end in 51..=klines.len() {
+        incremental.add(&klines[..end]);
+        // Un appel répété sans nouvelle bougie ne doit rien ajouter
+        incremental.add(&klines[..end]);
+    }
+
+    assert_eq!(incremental.values.len(), batch.values.len());
+    for (index, (a, b)) in incremental.values.iter().zip(&batch.values).enumerate().skip(14) {
+        assert_eq!(a, b, "rsi[{}]", index);
+    }
+}
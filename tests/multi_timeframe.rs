@@ -0,0 +1,114 @@
+mod common;
+
+use binance::model::KlineSummary;
+use common::{assert_close, load_klines};
+use root::data::{Interval, Resampler};
+use root::indicator::ChoppinessIndex;
+use root::strategy::interface::Observer;
+use root::strategy::{KlineManager, MultiTimeframeManager};
+
+const FIXTURE: &str = "synthetic_1h.csv";
+const INITIAL: usize = 30;
+const H4: Interval = Interval::Hours(4);
+const D1: Interval = Interval::Days(1);
+
+fn no_observers(_: &[KlineSummary]) -> root::Result<Vec<Box<dyn Observer>>> {
+    Ok(Vec::new())
+}
+
+fn manager(klines: &[KlineSummary]) -> MultiTimeframeManager {
+    MultiTimeframeManager::new(Interval::Hours(1), KlineManager::new(klines[..INITIAL].to_vec(), Vec::new()))
+}
+
+// Champs comparés entre deux sources (`KlineSummary` n'implémente pas `PartialEq`)
+type Fields = (i64, i64, String, String, String, String, String, i64);
+
+fn fields(kline: &KlineSummary) -> Fields {
+    (
+        kline.open_time,
+        kline.close_time,
+        kline.open.clone(),
+        kline.high.clone(),
+        kline.low.clone(),
+        kline.close.clone(),
+        kline.volume.clone(),
+        kline.number_of_trades,
+    )
+}
+
+fn timeframe_fields(manager: &MultiTimeframeManager, interval: Interval) -> Vec<Fields> {
+    manager.timeframe(interval).unwrap().klines.iter().map(fields).collect()
+}
+
+#[test]
+fn partial_bars_are_not_exposed() {
+    let klines = load_klines(FIXTURE);
+    let mut manager = manager(&klines);
+    manager.add_resampled_timeframe(H4, no_observers).unwrap();
+    manager.add_resampled_timeframe(D1, no_observers).unwrap();
+
+    for (index, kline) in klines.iter().enumerate().skip(INITIAL) {
+        manager.add_kline(kline.clone());
+        let received = index + 1;
+
+        // La fixture commence à minuit : seules les bougies entièrement couvertes sont publiées
+        for (interval, hours) in [(H4, 4), (D1, 24)] {
+            let higher = &manager.timeframe(interval).unwrap().klines;
+            assert_eq!(higher.len(), received / hours, "{:?} after {} klines", interval, received);
+            assert!(higher.last().is_none_or(|last| last.close_time <= kline.close_time));
+        }
+    }
+}
+
+#[test]
+fn resampled_and_fetched_feeds_agree() {
+    let klines = load_klines(FIXTURE);
+    let mut resampled = manager(&klines);
+    let mut fetched = manager(&klines);
+    for interval in [H4, D1] {
+        resampled.add_resampled_timeframe(interval, no_observers).unwrap();
+        fetched.add_fetched_timeframe(interval, Resampler::resample(interval, &klines), no_observers).unwrap();
+    }
+
+    for kline in &klines[INITIAL..] {
+        resampled.add_kline(kline.clone());
+        fetched.add_kline(kline.clone());
+        for interval in [H4, D1] {
+            assert_eq!(timeframe_fields(&resampled, interval), timeframe_fields(&fetched, interval));
+        }
+    }
+    assert_eq!(resampled.timeframe(D1).unwrap().klines.len(), klines.len() / 24);
+}
+
+#[test]
+fn higher_timeframe_indicators_update_on_close() {
+    let klines = load_klines(FIXTURE);
+    let mut manager = manager(&klines);
+    manager
+        .add_resampled_timeframe(H4, |closed| Ok(vec![Box::new(ChoppinessIndex::new(closed, 5)?) as Box<dyn Observer>]))
+        .unwrap();
+
+    let values = |manager: &MultiTimeframeManager| {
+        manager.timeframe(H4).unwrap().get_indicator::<ChoppinessIndex>().unwrap().values.as_slice().to_vec()
+    };
+    for (index, kline) in klines.iter().enumerate().skip(INITIAL) {
+        let before = values(&manager);
+        manager.add_kline(kline.clone());
+        let after = values(&manager);
+
+        // Une valeur par bougie 4h, ajoutée à la dernière heure de la bougie
+        if (index + 1) % 4 == 0 {
+            assert_eq!(after.len(), before.len() + 1);
+        } else {
+            // Comparaison bit à bit : les valeurs d'initialisation sont NaN
+            assert!(after.iter().map(|value| value.to_bits()).eq(before.iter().map(|value| value.to_bits())));
+        }
+    }
+
+    let expected = ChoppinessIndex::new(&Resampler::resample(H4, &klines), 5).unwrap();
+    let actual = values(&manager);
+    assert_eq!(actual.len(), expected.values.len());
+    for (index, value) in actual.iter().enumerate() {
+        assert_close("choppiness_4h", index, *value, expected.values[index]);
+    }
+}
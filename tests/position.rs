@@ -0,0 +1,173 @@
+mod common;
+
+use common::load_klines;
+use root::indicator::Side;
+use root::strategy::{
+    Backtester, ChoppinessDonchianAtrStrategy, Mode, Parameters, Position, PositionRules, StrategyRegistry, TakeProfit,
+    TradingStrategy,
+};
+use root::Error;
+
+const FIXTURE: &str = "synthetic_1h.csv";
+const WARMUP: usize = 100;
+
+fn rules(max_units: usize, take_profits: &str) -> PositionRules {
+    PositionRules::new(max_units, 0.5, PositionRules::parse_take_profits(take_profits).unwrap()).unwrap()
+}
+
+#[test]
+fn units_are_added_every_half_atr() {
+    let rules = rules(3, "");
+    let mut position = Position::open(Side::Long, 0, 100.0, 1.0, Some(96.0)).unwrap();
+    assert_eq!(position.risk(), Some(4.0));
+
+    assert!(!position.can_add(100.9, 2.0, &rules));
+    assert!(position.can_add(101.0, 2.0, &rules));
+    assert!(!position.can_add(101.0, f64::NAN, &rules));
+    position.add_unit(1, 101.0, 3.0, Some(98.0)).unwrap();
+    assert!(position.can_add(102.0, 2.0, &rules));
+    position.add_unit(2, 102.0, 1.0, Some(99.0)).unwrap();
+    assert!(!position.can_add(110.0, 2.0, &rules));
+
+    assert_eq!(position.quantity(), 5.0);
+    assert_eq!(position.average_price(), (100.0 + 303.0 + 102.0) / 5.0);
+
+    let mut short = Position::open(Side::Short, 0, 100.0, 1.0, Some(104.0)).unwrap();
+    assert!(short.can_add(99.0, 2.0, &rules));
+    short.add_unit(1, 99.0, 1.0, Some(103.0)).unwrap();
+    assert_eq!(short.average_price(), 99.5);
+}
+
+#[test]
+fn each_unit_has_its_own_stop() {
+    let rules = rules(3, "");
+    let mut position = Position::open(Side::Long, 0, 100.0, 1.0, Some(96.0)).unwrap();
+    position.add_unit(1, 102.0, 1.0, Some(99.0)).unwrap();
+    position.add_unit(2, 104.0, 2.0, Some(101.0)).unwrap();
+
+    // Seule la dernière unité est stoppée
+    let trades = position.update(3, 100.5, &rules);
+    assert_eq!(trades.len(), 1);
+    assert_eq!((trades[0].entry_time, trades[0].entry_price, trades[0].quantity), (2, 104.0, 2.0));
+    assert_eq!(position.units().len(), 2);
+
+    // Les deux unités restantes sortent ensemble, au prix moyen
+    let trades = position.update(4, 95.0, &rules);
+    assert_eq!(trades.len(), 1);
+    assert_eq!((trades[0].entry_time, trades[0].entry_price, trades[0].quantity), (0, 101.0, 2.0));
+    assert!(position.is_closed());
+
+    let mut position = Position::open(Side::Long, 0, 100.0, 1.0, Some(96.0)).unwrap();
+    position.add_unit(1, 102.0, 1.0, Some(99.0)).unwrap();
+    position.move_stops(101.0);
+    assert_eq!(position.update(2, 100.0, &rules).len(), 1);
+    assert!(position.is_closed());
+}
+
+#[test]
+fn take_profits_scale_out_at_r_targets() {
+    let rules = rules(2, "1:0.5, 2:0.5, 3:1");
+    let mut position = Position::open(Side::Short, 0, 100.0, 2.0, Some(104.0)).unwrap();
+    position.add_unit(1, 98.0, 2.0, Some(102.0)).unwrap();
+    assert_eq!(position.target_price(1.0), Some(96.0));
+
+    assert!(position.update(2, 96.0, &rules).is_empty());
+
+    // 1R : la moitié de la position, prise sur la dernière unité entrée
+    let trades = position.update(3, 95.0, &rules);
+    assert_eq!(trades.len(), 1);
+    assert_eq!((trades[0].entry_price, trades[0].quantity), (98.0, 2.0));
+    assert_eq!(trades[0].return_rate(), (98.0 - 95.0) / 98.0);
+
+    // 2R et 3R franchis sur la même bougie
+    let trades = position.update(4, 87.0, &rules);
+    assert_eq!(trades.iter().map(|trade| trade.quantity).collect::<Vec<_>>(), [1.0, 1.0]);
+    assert!(trades.iter().all(|trade| trade.entry_price == 100.0));
+    assert!(position.is_closed());
+
+    // Prix moyen des quantités fermées : le gain est celui des unités
+    let mut position = Position::open(Side::Long, 0, 100.0, 1.0, Some(90.0)).unwrap();
+    position.add_unit(1, 110.0, 1.0, Some(100.0)).unwrap();
+    let trade = position.close(2, 120.0).unwrap();
+    assert_eq!((trade.entry_price, trade.quantity), (105.0, 2.0));
+    assert_eq!(trade.profit(), 20.0 + 10.0);
+    assert!(position.close(3, 120.0).is_none());
+}
+
+#[test]
+fn stops_must_be_on_the_losing_side() {
+    let invalid = |result: Result<Position, Error>| matches!(result, Err(Error::InvalidParameter { name: "stop", .. }));
+    assert!(invalid(Position::open(Side::Long, 0, 100.0, 1.0, Some(f64::NAN))));
+    assert!(invalid(Position::open(Side::Long, 0, 100.0, 1.0, Some(104.0))));
+    assert!(invalid(Position::open(Side::Long, 0, 100.0, 1.0, Some(100.0))));
+    assert!(invalid(Position::open(Side::Short, 0, 100.0, 1.0, Some(96.0))));
+    assert!(invalid(Position::open(Side::Short, 0, 100.0, 1.0, Some(f64::INFINITY))));
+    assert_eq!(Position::open(Side::Short, 0, 100.0, 1.0, Some(104.0)).unwrap().risk(), Some(4.0));
+
+    // Une unité ajoutée avec un stop invalide n'est pas enregistrée
+    let mut position = Position::open(Side::Short, 0, 100.0, 1.0, Some(104.0)).unwrap();
+    assert!(position.add_unit(1, 99.0, 1.0, Some(f64::NAN)).is_err());
+    assert!(position.add_unit(1, 99.0, 1.0, Some(98.0)).is_err());
+    assert_eq!(position.units().len(), 1);
+
+    // Sans stop : ni R, ni cible, la position n'est fermée que par une sortie
+    let mut position = Position::open(Side::Long, 0, 100.0, 1.0, None).unwrap();
+    assert_eq!((position.risk(), position.target_price(1.0)), (None, None));
+    assert!(position.update(1, 1.0, &rules(1, "1:1")).is_empty());
+    assert_eq!(position.units().len(), 1);
+}
+
+#[test]
+fn position_rules_are_validated() {
+    assert_eq!(
+        PositionRules::parse_take_profits(" 1.5:0.5, 3:1 ").unwrap(),
+        [TakeProfit::new(1.5, 0.5).unwrap(), TakeProfit::new(3.0, 1.0).unwrap()]
+    );
+    assert!(PositionRules::parse_take_profits("").unwrap().is_empty());
+    assert!(PositionRules::parse_take_profits("2").is_err());
+    assert!(PositionRules::parse_take_profits("2:1.5").is_err());
+    assert!(TakeProfit::new(0.0, 0.5).is_err());
+
+    let targets = PositionRules::parse_take_profits("2:0.5, 1:1").unwrap();
+    assert!(matches!(PositionRules::new(1, 0.5, targets), Err(Error::InvalidParameter { name: "take_profits", .. })));
+    assert!(matches!(PositionRules::new(0, 0.5, Vec::new()), Err(Error::InvalidParameter { name: "max_units", .. })));
+    assert!(matches!(PositionRules::new(2, 0.0, Vec::new()), Err(Error::InvalidParameter { name: "add_step", .. })));
+}
+
+#[test]
+fn choppiness_strategy_pyramids_and_scales_out() {
+    let klines = load_klines(FIXTURE);
+    let mut strategy = ChoppinessDonchianAtrStrategy::new(Mode::Backtest, "ETHBTC")
+        .with_donchian(10, 1)
+        .with_choppiness(14, 100.0)
+        .with_position_rules(rules(3, "1:0.5, 2:1"));
+    let mut manager = strategy.prepare(&klines).unwrap();
+
+    let mut units = 0;
+    for kline in &klines[WARMUP..] {
+        strategy.execute(kline.clone(), &mut manager).unwrap();
+        units = units.max(strategy.position().map_or(0, |position| position.units().len()));
+    }
+    assert!(units > 1);
+
+    let trades = strategy.trades();
+    assert!(!trades.is_empty());
+    // Capital disponible : initial + gains des sorties - coût des unités encore ouvertes
+    let invested = strategy.position().map_or(0.0, |position| position.average_price() * position.quantity());
+    let profit: f64 = trades.iter().map(|trade| trade.profit()).sum();
+    assert!((strategy.capital() + invested - 0.01 - profit).abs() < 1e-12);
+}
+
+#[test]
+fn position_rules_are_strategy_parameters() {
+    let registry = StrategyRegistry::builtin();
+    let parameters = Parameters::new().with("max_units", "4").with("take_profits", "1:0.5, 2:0.5, 3:1");
+    let strategy = registry.create("choppiness_donchian_atr", &parameters).unwrap();
+    assert!(Backtester::new(strategy).run(&load_klines(FIXTURE)).is_ok());
+
+    let parameters = Parameters::new().with("take_profits", "3");
+    assert!(matches!(
+        registry.create("choppiness_donchian_atr", &parameters),
+        Err(Error::InvalidParameter { name: "take_profits", .. })
+    ));
+}
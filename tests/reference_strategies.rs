@@ -0,0 +1,208 @@
+mod common;
+
+use common::load_klines;
+use root::data::Candles;
+use root::indicator::{IndicatorSpec, MovingAverageKind, PriceSource, Side};
+use root::strategy::reference_strategies::{
+    bollinger_mean_reversion, keltner_squeeze_breakout, moving_average_crossover, rsi_reversal,
+};
+use root::strategy::{Backtester, Mode, Parameters, StrategyRegistry, TradingStrategy, TurtleStrategy, TurtleSystem};
+use root::Error;
+
+const FIXTURE: &str = "synthetic_1h.csv";
+const WARMUP: usize = 100;
+
+// Exécuter la stratégie sur toutes les bougies suivant l'initialisation
+fn run<S: TradingStrategy>(strategy: &mut S) {
+    let klines = load_klines(FIXTURE);
+    let mut manager = strategy.prepare(&klines).unwrap();
+    for kline in &klines[WARMUP..] {
+        strategy.execute(kline.clone(), &mut manager).unwrap();
+    }
+}
+
+fn index(open_time: i64) -> usize {
+    load_klines(FIXTURE).iter().position(|kline| kline.open_time == open_time).unwrap()
+}
+
+#[test]
+fn turtle_units_are_sized_by_atr_and_exit_together() {
+    let klines = load_klines(FIXTURE);
+    let candles = Candles::from_klines(&klines);
+    let entry = IndicatorSpec::DonchianChannel { length: 10, offset: 1 }.compute(&candles).unwrap();
+    let n = IndicatorSpec::Atr { length: 14 }.compute(&candles).unwrap();
+    let n = n.value();
+
+    let mut strategy = TurtleStrategy::with_lengths(10, 5, Mode::Backtest, 1000.0).unwrap().with_atr_length(14);
+    let mut manager = strategy.prepare(&klines).unwrap();
+    let mut pyramided = false;
+    for (i, kline) in klines.iter().enumerate().skip(WARMUP) {
+        let (capital, units) = (strategy.capital(), strategy.position().map_or(0, |position| position.units().len()));
+        let invested = strategy.position().map_or(0.0, |position| position.average_price() * position.quantity());
+        strategy.execute(kline.clone(), &mut manager).unwrap();
+        let Some(position) = strategy.position() else {
+            continue;
+        };
+
+        assert!(position.units().len() <= 4);
+        let unit = position.units().last().unwrap();
+        if position.units().len() == units + 1 {
+            // Unité prise sur cette bougie : 1 % du capital par N, dans la limite du capital disponible
+            let expected = (capital * 0.01 / n[i]).min((capital - invested) / candles.close[i]);
+            assert!((unit.quantity - expected).abs() < 1e-9 * unit.quantity);
+            match position.side() {
+                Side::Long => assert!(units > 0 || candles.close[i] > entry.get("upper").unwrap()[i]),
+                Side::Short => assert!(units > 0 || candles.close[i] < entry.get("lower").unwrap()[i]),
+            }
+        }
+        if position.units().len() > 1 {
+            pyramided = true;
+            let previous = position.units()[position.units().len() - 2];
+            let progress = match position.side() {
+                Side::Long => unit.entry_price - previous.entry_price,
+                Side::Short => previous.entry_price - unit.entry_price,
+            };
+            assert!(progress >= 0.5 * n[index(unit.entry_time)]);
+            // Stop commun, à 2 N de la dernière unité
+            assert!(position.units().iter().all(|other| other.stop == unit.stop));
+        }
+    }
+    assert!(pyramided);
+
+    // Toutes les unités d'une position sortent ensemble, au prix moyen d'entrée
+    let trades = strategy.trades();
+    assert!(!trades.is_empty());
+    assert!(trades.windows(2).all(|pair| pair[0].exit_time < pair[1].entry_time || pair[0].exit_time == pair[1].exit_time));
+    let profit: f64 = trades.iter().map(|trade| trade.profit()).sum();
+    assert!((strategy.capital() - 1000.0 - profit).abs() < 1e-9);
+}
+
+#[test]
+fn turtle_parameters_are_validated() {
+    assert_eq!(TurtleSystem::System1.lengths(), (20, 10));
+    assert_eq!(TurtleSystem::System2.lengths(), (55, 20));
+    assert!(TurtleStrategy::new(TurtleSystem::System1, Mode::Backtest, 0.0).is_err());
+    let strategy = TurtleStrategy::new(TurtleSystem::System2, Mode::Live, 1.0).unwrap();
+    assert!(matches!(strategy.clone().with_units(0.01, 0, 0.5, 2.0), Err(Error::InvalidParameter { name: "max_units", .. })));
+    assert!(matches!(strategy.clone().with_units(1.5, 4, 0.5, 2.0), Err(Error::InvalidParameter { name: "risk", .. })));
+    assert_eq!(strategy.with_symbol("BTCUSDT").executor().mode(), Mode::Live);
+}
+
+#[test]
+fn turtle_units_never_exceed_the_capital() {
+    let klines = load_klines(FIXTURE);
+    // N vaut environ 3 % du prix : une unité risquant 1 % engage un tiers du capital, quatre le dépasseraient
+    let mut strategy = TurtleStrategy::with_lengths(10, 5, Mode::Backtest, 1000.0).unwrap();
+    let mut manager = strategy.prepare(&klines).unwrap();
+    let mut pyramided = false;
+    for kline in &klines[WARMUP..] {
+        strategy.execute(kline.clone(), &mut manager).unwrap();
+        if let Some(position) = strategy.position() {
+            pyramided |= position.units().len() > 1;
+            let invested = position.average_price() * position.quantity();
+            assert!(invested <= strategy.executor().equity() * (1.0 + 1e-9));
+        }
+    }
+    assert!(pyramided);
+}
+
+#[test]
+fn moving_average_crossover_follows_the_averages() {
+    let mut strategy = moving_average_crossover("ema", 5, 20).unwrap();
+    run(&mut strategy);
+
+    let candles = Candles::from_klines(&load_klines(FIXTURE));
+    let average = |period| {
+        IndicatorSpec::MovingAverage { kind: MovingAverageKind::Ema, period, source: PriceSource::Close }
+            .compute(&candles)
+            .unwrap()
+            .value()
+            .to_vec()
+    };
+    let (fast, slow) = (average(5), average(20));
+
+    assert!(!strategy.trades().is_empty());
+    for trade in strategy.trades() {
+        let (entry, exit) = (index(trade.entry_time), index(trade.exit_time));
+        assert!(fast[entry] > slow[entry] && fast[entry - 1] <= slow[entry - 1]);
+        assert!(fast[exit] < slow[exit] && fast[exit - 1] >= slow[exit - 1]);
+    }
+
+    assert!(moving_average_crossover("median", 5, 20).is_err());
+    assert!(moving_average_crossover("sma", 20, 5).is_err());
+}
+
+#[test]
+fn bollinger_mean_reversion_buys_below_the_lower_band() {
+    let mut strategy = bollinger_mean_reversion(20, 1.5).unwrap();
+    run(&mut strategy);
+
+    let candles = Candles::from_klines(&load_klines(FIXTURE));
+    let bands = IndicatorSpec::BollingerBands { length: 20, multiplier: 1.5, source: PriceSource::Close }
+        .compute(&candles)
+        .unwrap();
+
+    assert!(!strategy.trades().is_empty());
+    for trade in strategy.trades() {
+        let (entry, exit) = (index(trade.entry_time), index(trade.exit_time));
+        assert!(candles.close[entry] < bands.get("lower").unwrap()[entry]);
+        assert!(candles.close[exit] > bands.get("basis").unwrap()[exit]);
+    }
+}
+
+#[test]
+fn rsi_reversal_leaves_the_oversold_zone() {
+    let mut strategy = rsi_reversal(14, 40.0, 60.0).unwrap();
+    run(&mut strategy);
+
+    let candles = Candles::from_klines(&load_klines(FIXTURE));
+    let rsi = IndicatorSpec::Rsi { period: 14, source: PriceSource::Close }.compute(&candles).unwrap();
+    let rsi = rsi.value();
+
+    assert!(!strategy.trades().is_empty());
+    for trade in strategy.trades() {
+        let (entry, exit) = (index(trade.entry_time), index(trade.exit_time));
+        assert!(rsi[entry] > 40.0 && rsi[entry - 1] <= 40.0);
+        assert!(rsi[exit] >= 60.0);
+    }
+
+    assert!(rsi_reversal(14, 70.0, 30.0).is_err());
+}
+
+#[test]
+fn keltner_squeeze_breaks_out_of_the_channel() {
+    let mut strategy = keltner_squeeze_breakout(20, 1.5, 2.0).unwrap();
+    run(&mut strategy);
+
+    let candles = Candles::from_klines(&load_klines(FIXTURE));
+    let bollinger = IndicatorSpec::BollingerBands { length: 20, multiplier: 1.5, source: PriceSource::Close }
+        .compute(&candles)
+        .unwrap();
+    let keltner = IndicatorSpec::KeltnerChannel { length: 20, atr_length: 20, multiplier: 2.0, source: PriceSource::Close }
+        .compute(&candles)
+        .unwrap();
+    let band = |series: &root::indicator::Series, output: &str, i: usize| series.get(output).unwrap()[i];
+
+    assert!(!strategy.trades().is_empty());
+    for trade in strategy.trades() {
+        let (entry, exit) = (index(trade.entry_time), index(trade.exit_time));
+        assert!(band(&bollinger, "upper", entry - 1) < band(&keltner, "upper", entry - 1));
+        assert!(band(&bollinger, "lower", entry - 1) > band(&keltner, "lower", entry - 1));
+        assert!(candles.close[entry] > band(&keltner, "upper", entry));
+        assert!(candles.close[exit] < band(&keltner, "basis", exit));
+    }
+}
+
+#[test]
+fn reference_strategies_run_in_the_backtester() {
+    let registry = StrategyRegistry::builtin();
+    let klines = load_klines(FIXTURE);
+
+    for name in ["turtle_system_1", "turtle_system_2", "ma_crossover", "bollinger_mean_reversion", "rsi_reversal", "keltner_squeeze"] {
+        let strategy = registry.create(name, &Parameters::new()).unwrap();
+        assert!(Backtester::new(strategy).run(&klines).is_ok(), "{}", name);
+    }
+
+    let parameters = Parameters::new().with("average", "sma").with("fast", "50").with("slow", "20");
+    assert!(registry.create("ma_crossover", &parameters).is_err());
+}
@@ -0,0 +1,161 @@
+mod common;
+
+use std::fs;
+
+use common::{fixture_path, load_klines};
+use root::indicator::StopConfig;
+use root::strategy::{
+    Backtester, ParameterKind, ParameterSpec, Parameters, RuleStrategy, StrategyConfig, StrategyEntry, StrategyRegistry,
+};
+use root::Error;
+
+const FIXTURE: &str = "synthetic_1h.csv";
+const THRESHOLD: &[ParameterSpec] = &[ParameterSpec::new("threshold", ParameterKind::Number, None, "minimum close")];
+
+fn invalid_parameter(result: root::Result<impl Sized>) -> (&'static str, String) {
+    match result {
+        Err(Error::InvalidParameter { name, reason }) => (name, reason),
+        Err(error) => panic!("expected an invalid parameter, got {}", error),
+        Ok(_) => panic!("expected an invalid parameter"),
+    }
+}
+
+#[test]
+fn builtin_strategies_are_listed_with_their_parameters() {
+    let registry = StrategyRegistry::builtin();
+    let names: Vec<&str> = registry.entries().iter().map(|entry| entry.name).collect();
+    assert!(names.contains(&"choppiness_donchian_atr"));
+    assert!(names.contains(&"rules"));
+
+    let description = registry.describe();
+    assert!(description.contains("choppiness_donchian_atr\n"));
+    assert!(description.contains("chop_threshold"));
+    assert!(description.contains("default: 50"));
+    assert!(description.contains("required"));
+}
+
+#[test]
+fn strategies_are_created_by_name() {
+    let registry = StrategyRegistry::builtin();
+    let klines = load_klines(FIXTURE);
+
+    let parameters = Parameters::new().with("chop_threshold", "45").with("stop", "chandelier(22, 22, 3)");
+    let strategy = registry.create("choppiness_donchian_atr", &parameters).unwrap();
+    assert!(Backtester::new(strategy).run(&klines).is_ok());
+
+    let (name, reason) = invalid_parameter(registry.create("unknown", &Parameters::new()));
+    assert_eq!(name, "strategy");
+    assert!(reason.contains("available: choppiness_donchian_atr"));
+
+    let (_, reason) = invalid_parameter(registry.create("choppiness_donchian_atr", &Parameters::new().with("length", "3")));
+    assert_eq!(reason, "choppiness_donchian_atr has no parameter 'length'");
+    assert_eq!(
+        invalid_parameter(registry.create("choppiness_donchian_atr", &Parameters::new().with("chop_length", "-1"))).0,
+        "chop_length"
+    );
+    assert_eq!(
+        invalid_parameter(registry.create("choppiness_donchian_atr", &Parameters::new().with("stop", "atr(14)"))).0,
+        "stop"
+    );
+    assert_eq!(
+        invalid_parameter(registry.create("choppiness_donchian_atr", &Parameters::new().with("mode", "paper"))).0,
+        "mode"
+    );
+    // Longueur refusée par l'indicateur à la préparation du backtest
+    let strategy = registry.create("choppiness_donchian_atr", &Parameters::new().with("donchian_length", "0")).unwrap();
+    assert!(Backtester::new(strategy).run(&klines).is_err());
+}
+
+#[test]
+fn rule_files_are_strategies() {
+    let registry = StrategyRegistry::builtin();
+    assert_eq!(invalid_parameter(registry.create("rules", &Parameters::new())).0, "file");
+
+    let path = std::env::temp_dir().join(format!("rules-{}.txt", std::process::id()));
+    fs::write(&path, "entry_long: crosses_above(close, ema(20))\nexit_long: crosses_below(close, ema(20))\n").unwrap();
+    let parameters = Parameters::new().with("file", path.to_str().unwrap());
+    let strategy = registry.create("rules", &parameters);
+    fs::remove_file(&path).unwrap();
+    assert!(Backtester::new(strategy.unwrap()).run(&load_klines(FIXTURE)).is_ok());
+
+    let missing = Parameters::new().with("file", fixture_path("missing.rules").to_str().unwrap());
+    assert_eq!(invalid_parameter(registry.create("rules", &missing)).0, "file");
+}
+
+#[test]
+fn config_files_select_a_strategy() {
+    let config = StrategyConfig::parse(
+        "# Seuil de choppiness plus strict
+strategy = \"choppiness_donchian_atr\"
+chop_threshold = 45
+stop = \"swing(3, 3)\"
+",
+    )
+    .unwrap();
+
+    assert_eq!(config.strategy, "choppiness_donchian_atr");
+    assert_eq!(config.parameters, Parameters::new().with("chop_threshold", "45").with("stop", "swing(3, 3)"));
+    assert!(config.create(&StrategyRegistry::builtin()).is_ok());
+
+    assert!(StrategyConfig::parse("chop_threshold = 45").is_err());
+    assert!(StrategyConfig::parse("strategy: rules").is_err());
+}
+
+#[test]
+fn config_comments_and_duplicates() {
+    let config = StrategyConfig::parse(
+        "strategy = \"rules\" # règles par défaut
+entry = \"close > 100 # seuil\"  # commentaire après une valeur entre guillemets
+",
+    )
+    .unwrap();
+    assert_eq!(config.strategy, "rules");
+    assert_eq!(config.parameters, Parameters::new().with("entry", "close > 100 # seuil"));
+
+    let (name, reason) = invalid_parameter(StrategyConfig::parse("strategy = \"rules\"\nstrategy = \"turtle\""));
+    assert_eq!(name, "config");
+    assert!(reason.contains("duplicate key \"strategy\""), "{}", reason);
+    let (_, reason) = invalid_parameter(StrategyConfig::parse("strategy = \"rules\"\nentry = 1\nentry = 2"));
+    assert!(reason.contains("duplicate key \"entry\""), "{}", reason);
+    let (_, reason) = invalid_parameter(StrategyConfig::parse("[strategy]\nname = \"rules\""));
+    assert!(reason.contains("sections are not supported"), "{}", reason);
+}
+
+#[test]
+fn stops_are_parsed_from_text() {
+    assert_eq!("atr(14, 1.5)".parse(), Ok(StopConfig::Atr { length: 14, multiplier: 1.5 }));
+    assert_eq!(
+        "chandelier(22, 22, 3)".parse(),
+        Ok(StopConfig::Chandelier { length: 22, atr_length: 22, multiplier: 3.0 })
+    );
+    assert_eq!(
+        "parabolic_sar(0.02, 0.02, 0.2)".parse(),
+        Ok(StopConfig::ParabolicSar { start: 0.02, increment: 0.02, maximum: 0.2 })
+    );
+    assert_eq!(" swing(3, 3) ".parse(), Ok(StopConfig::Swing { left: 3, right: 3 }));
+    assert!("swing(3.5, 3)".parse::<StopConfig>().is_err());
+    assert!("atr 14".parse::<StopConfig>().is_err());
+}
+
+#[test]
+fn strategies_can_be_registered() {
+    let mut registry = StrategyRegistry::new();
+    let entry = StrategyEntry {
+        name: "always_long",
+        description: "Long on every bar",
+        parameters: THRESHOLD,
+        build: |parameters| {
+            let rules = format!("entry_long: close > {}", parameters.number("threshold")?);
+            Ok(Box::new(RuleStrategy::parse(&rules)?))
+        },
+    };
+
+    registry.register(entry).unwrap();
+    assert!(registry.register(entry).is_err());
+    assert_eq!(invalid_parameter(registry.create("always_long", &Parameters::new())).0, "threshold");
+    assert_eq!(
+        invalid_parameter(registry.create("always_long", &Parameters::new().with("threshold", "high"))).0,
+        "threshold"
+    );
+    assert!(registry.create("always_long", &Parameters::new().with("threshold", "0")).is_ok());
+}
@@ -0,0 +1,190 @@
+mod common;
+
+use binance::model::KlineSummary;
+use chrono::NaiveDate;
+use common::candle;
+use root::data::transform::{heikin_ashi, range_bars, renko};
+use root::data::{Interval, Resampler};
+
+const HOUR_MS: i64 = 3_600_000;
+const DAY_MS: i64 = 24 * HOUR_MS;
+
+fn midnight(year: i32, month: u32, day: u32) -> i64 {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap().and_hms_opt(0, 0, 0).unwrap().and_utc().timestamp_millis()
+}
+
+// Bougie de `duration` ms dont les volumes et le nombre de trades dépendent de `volume`
+fn kline(open_time: i64, duration: i64, (open, high, low, close): (f64, f64, f64, f64), volume: f64) -> KlineSummary {
+    KlineSummary {
+        open_time,
+        open: open.to_string(),
+        high: high.to_string(),
+        low: low.to_string(),
+        close: close.to_string(),
+        volume: volume.to_string(),
+        close_time: open_time + duration - 1,
+        quote_asset_volume: (volume * close).to_string(),
+        number_of_trades: volume as i64 * 10,
+        taker_buy_base_asset_volume: (volume / 4.0).to_string(),
+        taker_buy_quote_asset_volume: (volume * close / 4.0).to_string(),
+    }
+}
+
+fn daily_klines(from: i64, days: i64) -> Vec<KlineSummary> {
+    (0..days)
+        .map(|day| {
+            let price = 100.0 + day as f64;
+            kline(from + day * DAY_MS, DAY_MS, (price, price + 2.0, price - 1.0, price + 0.5), 1.0 + day as f64)
+        })
+        .collect()
+}
+
+fn sum(klines: &[KlineSummary], field: fn(&KlineSummary) -> &str) -> f64 {
+    klines.iter().map(|kline| field(kline).parse::<f64>().unwrap()).sum()
+}
+
+fn value(field: &str) -> f64 {
+    field.parse().unwrap()
+}
+
+#[test]
+fn weeks_start_on_monday() {
+    // Du mercredi 2024-01-03 au dimanche 2024-01-21
+    let klines = daily_klines(midnight(2024, 1, 3), 19);
+    let weeks = Resampler::resample(Interval::Weeks(1), &klines);
+
+    // Première semaine incomplète (du mercredi au dimanche) abandonnée
+    let starts: Vec<i64> = weeks.iter().map(|week| week.open_time).collect();
+    assert_eq!(starts, [midnight(2024, 1, 8), midnight(2024, 1, 15)]);
+    assert!(weeks.iter().all(|week| week.close_time == week.open_time + 7 * DAY_MS - 1));
+
+    assert_eq!(weeks[0].open, klines[5].open);
+    assert_eq!(weeks[0].close, klines[11].close);
+    assert_eq!(value(&weeks[1].volume), sum(&klines[12..], |kline| &kline.volume));
+}
+
+#[test]
+fn months_follow_the_calendar() {
+    // Du 2024-01-30 au 2024-03-01 : février bissextile de 29 jours, janvier incomplet abandonné
+    let klines = daily_klines(midnight(2024, 1, 30), 32);
+    let months = Resampler::resample(Interval::Months(1), &klines);
+
+    assert_eq!(months.len(), 1);
+    assert_eq!((months[0].open_time, months[0].close_time), (midnight(2024, 2, 1), midnight(2024, 3, 1) - 1));
+    assert_eq!(months[0].open, klines[2].open);
+    assert_eq!(months[0].close, klines[30].close);
+    assert_eq!(months[0].number_of_trades, klines[2..31].iter().map(|kline| kline.number_of_trades).sum::<i64>());
+
+    // Mars n'est publié qu'avec la bougie incomplète
+    let with_partial = Resampler::resample_with_partial(Interval::Months(1), &klines);
+    assert_eq!(with_partial.len(), 2);
+    assert_eq!(with_partial[1].open_time, midnight(2024, 3, 1));
+    assert_eq!(with_partial[1].volume, klines[31].volume);
+
+    // Février non bissextile
+    assert_eq!(Interval::Months(1).close_time(midnight(2023, 2, 14)), midnight(2023, 3, 1) - 1);
+}
+
+#[test]
+fn fields_are_combined() {
+    let start = midnight(2024, 1, 1);
+    let klines = [
+        kline(start, HOUR_MS, (100.0, 104.0, 99.0, 103.0), 2.0),
+        kline(start + HOUR_MS, HOUR_MS, (103.0, 108.0, 102.0, 106.0), 3.0),
+        kline(start + 2 * HOUR_MS, HOUR_MS, (106.0, 107.0, 95.0, 97.0), 5.0),
+        kline(start + 3 * HOUR_MS, HOUR_MS, (97.0, 101.0, 96.0, 100.5), 7.0),
+    ];
+    let bars = Resampler::resample(Interval::Hours(4), &klines);
+    assert_eq!(bars.len(), 1);
+    let bar = &bars[0];
+
+    assert_eq!((bar.open_time, bar.close_time), (start, start + 4 * HOUR_MS - 1));
+    assert_eq!((value(&bar.open), value(&bar.high), value(&bar.low), value(&bar.close)), (100.0, 108.0, 95.0, 100.5));
+    assert_eq!(value(&bar.volume), 17.0);
+    assert_eq!(bar.number_of_trades, 170);
+    for (actual, field) in [
+        (&bar.quote_asset_volume, (|kline| &kline.quote_asset_volume) as fn(&KlineSummary) -> &str),
+        (&bar.taker_buy_base_asset_volume, |kline| &kline.taker_buy_base_asset_volume),
+        (&bar.taker_buy_quote_asset_volume, |kline| &kline.taker_buy_quote_asset_volume),
+    ] {
+        assert_eq!(value(actual), sum(&klines, field));
+    }
+}
+
+#[test]
+fn incomplete_periods_are_dropped() {
+    // Bougies horaires du 2024-01-01 à 21h au 2024-01-03 à 23h
+    let start = midnight(2024, 1, 1) + 21 * HOUR_MS;
+    let klines: Vec<KlineSummary> =
+        (0..51).map(|hour| kline(start + hour * HOUR_MS, HOUR_MS, (100.0, 101.0, 99.0, 100.0), 1.0)).collect();
+
+    // Le 1er janvier ne compte que trois heures, la période de 4 h commencée à 20h aussi
+    let days = Resampler::resample(Interval::Days(1), &klines);
+    assert_eq!(days.iter().map(|day| day.open_time).collect::<Vec<_>>(), [midnight(2024, 1, 2), midnight(2024, 1, 3)]);
+    assert!(days.iter().all(|day| value(&day.volume) == 24.0));
+    let bars = Resampler::resample(Interval::Hours(4), &klines);
+    assert_eq!((bars.len(), bars[0].open_time), (12, midnight(2024, 1, 2)));
+
+    // Une heure manquante le 2 janvier : seule la journée complète du 3 est publiée
+    let missing = midnight(2024, 1, 2) + 12 * HOUR_MS;
+    let with_gap: Vec<KlineSummary> = klines.iter().filter(|kline| kline.open_time != missing).cloned().collect();
+    let days = Resampler::resample(Interval::Days(1), &with_gap);
+    assert_eq!(days.iter().map(|day| day.open_time).collect::<Vec<_>>(), [midnight(2024, 1, 3)]);
+    assert_eq!(Resampler::resample(Interval::Hours(4), &with_gap).len(), 11);
+}
+
+#[test]
+fn heikin_ashi_smooths_ohlc() {
+    let klines = [candle(0, 100.0, 104.0, 98.0, 102.0), candle(1, 102.0, 103.0, 96.0, 97.0)];
+    let bars = heikin_ashi(&klines);
+
+    let ohlc = |bar: &KlineSummary| (value(&bar.open), value(&bar.high), value(&bar.low), value(&bar.close));
+    // Première bougie : ouverture au milieu du corps
+    assert_eq!(ohlc(&bars[0]), (101.0, 104.0, 98.0, 101.0));
+    // Ouverture au milieu du corps Heikin-Ashi précédent, clôture à la moyenne OHLC
+    assert_eq!(ohlc(&bars[1]), (101.0, 103.0, 96.0, 99.5));
+    assert_eq!((bars[1].open_time, &bars[1].volume), (klines[1].open_time, &klines[1].volume));
+}
+
+#[test]
+fn renko_bricks_have_increasing_times() {
+    let closes = [100.0, 101.0, 103.5, 101.0, 98.0];
+    let klines: Vec<KlineSummary> =
+        (0..).zip(closes).map(|(index, close)| candle(index, close, close + 0.5, close - 0.5, close)).collect();
+    let bricks = renko(&klines, 1.0);
+
+    let ohlc: Vec<(f64, f64)> = bricks.iter().map(|brick| (value(&brick.open), value(&brick.close))).collect();
+    // Le retournement part de l'extrémité opposée de la dernière brique haussière
+    assert_eq!(
+        ohlc,
+        [(100.0, 101.0), (101.0, 102.0), (102.0, 103.0), (102.0, 101.0), (101.0, 100.0), (100.0, 99.0), (99.0, 98.0)]
+    );
+
+    // La première bougie compte dans le volume de la première brique
+    assert_eq!(value(&bricks[0].volume), 2.0);
+    assert_eq!(value(&bricks[2].volume), 0.0);
+
+    // Les briques d'une même bougie se partagent sa durée
+    assert_eq!((bricks[1].open_time, bricks[1].close_time), (klines[2].open_time, klines[2].open_time + HOUR_MS / 2 - 1));
+    assert_eq!((bricks[2].open_time, bricks[2].close_time), (klines[2].open_time + HOUR_MS / 2, klines[2].close_time));
+    assert_eq!(bricks[6].close_time, klines[4].close_time);
+    assert!(bricks.windows(2).all(|pair| pair[0].close_time < pair[1].open_time));
+}
+
+#[test]
+fn range_bars_merge_whole_klines() {
+    let klines = [
+        candle(0, 100.0, 101.0, 99.5, 100.5),
+        candle(1, 100.5, 101.2, 100.0, 101.0),
+        candle(2, 101.0, 103.0, 100.5, 102.5),
+        candle(3, 102.5, 103.0, 102.0, 102.5),
+    ];
+    let bars = range_bars(&klines, 2.0);
+
+    // Les deux premières bougies n'atteignent pas l'amplitude, la troisième la dépasse
+    assert_eq!(bars.len(), 1);
+    assert_eq!((value(&bars[0].high), value(&bars[0].low)), (103.0, 99.5));
+    assert_eq!((bars[0].open_time, bars[0].close_time), (klines[0].open_time, klines[2].close_time));
+    assert_eq!(value(&bars[0].volume), 3.0);
+    assert!(range_bars(&klines, 0.0).is_empty());
+}
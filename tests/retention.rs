@@ -0,0 +1,146 @@
+mod common;
+
+use common::{assert_close, load_klines};
+use root::data::{RetentionPolicy, RingBuffer};
+use root::indicator::{
+    ATRStopLoss, ChoppinessIndex, DonchianChannel, Ichimoku, PriceSource, Rsi, SeriesRsi, Source, SupportResistance,
+};
+use root::strategy::interface::Observer;
+use root::strategy::{KlineManager, SeriesSpec};
+
+const FIXTURE: &str = "synthetic_1h.csv";
+
+fn observers(klines: &[binance::model::KlineSummary]) -> Vec<Box<dyn Observer>> {
+    vec![
+        Box::new(DonchianChannel::new(klines, 20, 20).unwrap()),
+        Box::new(ChoppinessIndex::new(klines, 14).unwrap()),
+        Box::new(ATRStopLoss::new(klines, 14, 1.5).unwrap()),
+        Box::new(Rsi::new(klines, 14, PriceSource::Close).unwrap()),
+    ]
+}
+
+fn manager(klines: &[binance::model::KlineSummary], retention: RetentionPolicy) -> KlineManager {
+    let initial = klines[..60].to_vec();
+    let mut manager = KlineManager::new(initial.clone(), observers(&initial));
+    manager
+        .add_series(vec![SeriesSpec::new("rsi", SeriesRsi::new(14).unwrap(), vec![Source::Price(PriceSource::Close)])])
+        .unwrap();
+    manager.set_retention(retention);
+    for kline in &klines[60..] {
+        manager.add_kline(kline.clone());
+    }
+    manager
+}
+
+#[test]
+fn ring_buffer_keeps_the_most_recent_values() {
+    let mut buffer = RingBuffer::bounded(Some(3));
+    for value in 0..200 {
+        buffer.push(value);
+    }
+
+    assert_eq!(buffer.as_slice(), &[197, 198, 199]);
+    assert_eq!(buffer.last(), Some(&199));
+    assert_eq!(buffer.ago(2), Some(&197));
+    assert_eq!(buffer.ago(3), None);
+
+    buffer.set_capacity(None);
+    buffer.extend(200..205);
+    assert_eq!(buffer.len(), 8);
+}
+
+#[test]
+fn bounded_manager_matches_unbounded_tail() {
+    let klines = load_klines(FIXTURE);
+    let unbounded = manager(&klines, RetentionPolicy::Unbounded);
+    let bounded = manager(&klines, RetentionPolicy::MaxLookback { margin: 5 });
+
+    // Donchian (20, 20) demande 40 bougies
+    assert_eq!(bounded.klines.len(), 45);
+    assert_eq!(bounded.klines.last().unwrap().open_time, klines.last().unwrap().open_time);
+
+    let full = unbounded.get_indicator::<DonchianChannel>().unwrap();
+    let tail = bounded.get_indicator::<DonchianChannel>().unwrap();
+    assert_eq!(tail.upper_band.len(), 45);
+    for n in 0..45 {
+        assert_close("upper_band", n, *tail.upper_band.ago(n).unwrap(), *full.upper_band.ago(n).unwrap());
+    }
+
+    for (name, full, tail) in [
+        (
+            "chop",
+            &unbounded.get_indicator::<ChoppinessIndex>().unwrap().values,
+            &bounded.get_indicator::<ChoppinessIndex>().unwrap().values,
+        ),
+        (
+            "atr_stop",
+            &unbounded.get_indicator::<ATRStopLoss>().unwrap().stop_losses,
+            &bounded.get_indicator::<ATRStopLoss>().unwrap().stop_losses,
+        ),
+        (
+            "rsi",
+            &unbounded.get_indicator::<Rsi>().unwrap().values,
+            &bounded.get_indicator::<Rsi>().unwrap().values,
+        ),
+    ] {
+        assert_eq!(tail.len(), 45, "{}", name);
+        assert_close(name, 0, *tail.last().unwrap(), *full.last().unwrap());
+        assert_close(name, 44, *tail.ago(44).unwrap(), *full.ago(44).unwrap());
+    }
+
+    let full = unbounded.get_series("rsi", "value").unwrap();
+    let tail = bounded.get_series("rsi", "value").unwrap();
+    assert_eq!(tail.len(), 45);
+    assert_close("series_rsi", 0, tail[44], full[full.len() - 1]);
+}
+
+#[test]
+fn fixed_capacity_never_drops_required_klines() {
+    let klines = load_klines(FIXTURE);
+    let bounded = manager(&klines, RetentionPolicy::Capacity(10));
+
+    assert_eq!(bounded.klines.len(), 40);
+    assert_eq!(bounded.get_indicator::<Rsi>().unwrap().values.len(), 10);
+}
+
+#[test]
+fn ichimoku_series_stay_aligned() {
+    let klines = load_klines(FIXTURE);
+    let full = Ichimoku::new(&klines, 9, 26, 52, 26).unwrap();
+    let initial = klines[..60].to_vec();
+    let mut bounded = KlineManager::new(initial.clone(), vec![Box::new(Ichimoku::new(&initial, 9, 26, 52, 26).unwrap())])
+        .with_retention(RetentionPolicy::Capacity(10));
+    for kline in &klines[60..] {
+        bounded.add_kline(kline.clone());
+    }
+
+    // Capacité relevée au décalage de la Chikou pour toutes les séries
+    let ichimoku = bounded.get_indicator::<Ichimoku>().unwrap();
+    assert_eq!(ichimoku.tenkan_sen.len(), 26);
+    let shift = klines.len() - 26;
+    for index in 0..26 {
+        assert_close("tenkan_sen", index, ichimoku.tenkan_sen[index], full.tenkan_sen[shift + index]);
+        assert_eq!(ichimoku.chikou_span_at(index), full.chikou_span_at(shift + index));
+    }
+    assert!(ichimoku.chikou_span_at(0).is_some());
+    assert_eq!(ichimoku.chikou_span_reference(), full.chikou_span_reference());
+}
+
+#[test]
+fn support_resistance_forgets_stale_zones() {
+    let klines = load_klines(FIXTURE);
+    let full = SupportResistance::new(&klines, 2, 2, 0.5, 1).unwrap();
+    let initial = klines[..60].to_vec();
+    let mut bounded = KlineManager::new(initial.clone(), vec![Box::new(SupportResistance::new(&initial, 2, 2, 0.5, 1).unwrap())])
+        .with_retention(RetentionPolicy::Capacity(30));
+    for kline in &klines[60..] {
+        bounded.add_kline(kline.clone());
+    }
+
+    // Seules les zones touchées dans les 30 dernières bougies restent, toujours triées
+    let oldest = klines[klines.len() - 30].open_time;
+    let zones: Vec<_> = bounded.get_indicator::<SupportResistance>().unwrap().zones().copied().collect();
+    assert!(!zones.is_empty() && zones.len() < full.zones().count());
+    assert!(zones.iter().all(|zone| zone.last_touch >= oldest));
+    assert!(zones.windows(2).all(|pair| pair[0].center() <= pair[1].center()));
+}
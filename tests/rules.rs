@@ -0,0 +1,176 @@
+mod common;
+
+use common::load_klines;
+use root::data::Candles;
+use root::indicator::{IndicatorSpec, MovingAverageKind, PriceSource, Side};
+use root::strategy::rules::{BinaryOp, Expr};
+use root::strategy::{Backtester, RuleSet, RuleStrategy, Trade, TradingStrategy};
+use root::Error;
+
+const FIXTURE: &str = "synthetic_1h.csv";
+const WARMUP: usize = 100;
+
+// Exécuter la stratégie sur toutes les bougies suivant l'initialisation
+fn run(strategy: &mut RuleStrategy) -> Vec<Trade> {
+    let klines = load_klines(FIXTURE);
+    let mut manager = strategy.prepare(&klines).unwrap();
+    for kline in &klines[WARMUP..] {
+        strategy.execute(kline.clone(), &mut manager).unwrap();
+    }
+    strategy.trades().to_vec()
+}
+
+fn rule_error(text: &str) -> (String, String) {
+    match RuleStrategy::parse(text) {
+        Err(Error::InvalidRule { rule, reason }) => (rule, reason),
+        other => panic!("expected an invalid rule for {:?}, got {:?}", text, other.map(|_| ())),
+    }
+}
+
+#[test]
+fn expressions_follow_operator_precedence() {
+    let expr = Expr::parse("close - 2 * atr(14) > donchian(20, 20).lower[1] and not rsi(14) >= 70").unwrap();
+    let Expr::Binary { op: BinaryOp::And, left, right } = expr else {
+        panic!("`and` binds last: {:?}", expr);
+    };
+    let Expr::Binary { op: BinaryOp::Greater, left: difference, right: lower } = *left else {
+        panic!("comparison binds after arithmetic");
+    };
+    assert!(matches!(*difference, Expr::Binary { op: BinaryOp::Subtract, .. }));
+    assert!(matches!(*lower, Expr::Offset { bars: 1, .. }));
+    assert!(matches!(*right, Expr::Unary { .. }));
+
+    assert_eq!(Expr::parse("(1 + 2) * 3").unwrap(), Expr::parse("((1 + 2)) * 3").unwrap());
+    assert_eq!(Expr::parse("close >").unwrap_err(), "unexpected end of expression");
+    assert_eq!(Expr::parse("close > 1)").unwrap_err(), "unexpected ')' at column 10");
+    assert_eq!(Expr::parse("close $ 1").unwrap_err(), "unexpected character '$' at column 7");
+}
+
+#[test]
+fn rule_files_are_parsed() {
+    let rules = RuleSet::parse(
+        "# Cassure de Donchian
+name: donchian_chop
+entry_long: crosses_above(close, donchian(20, 20).upper)
+    and chop(100) <= 50   # filtre de tendance
+stop_long: atr_stop(14, 1.5).long
+",
+    )
+    .unwrap();
+
+    assert_eq!(rules.name.as_deref(), Some("donchian_chop"));
+    assert_eq!(
+        rules.entry_long,
+        Some(Expr::parse("crosses_above(close, donchian(20, 20).upper) and chop(100) <= 50").unwrap())
+    );
+    assert!(rules.exit_long.is_none() && rules.entry_short.is_none());
+
+    let strategy = RuleStrategy::new(&rules).unwrap();
+    assert_eq!(strategy.name(), "donchian_chop");
+    assert_eq!(
+        strategy.indicators(),
+        [
+            IndicatorSpec::DonchianChannel { length: 20, offset: 20 },
+            IndicatorSpec::ChoppinessIndex { length: 100 },
+            IndicatorSpec::AtrStopLoss { length: 14, multiplier: 1.5 },
+        ]
+    );
+}
+
+#[test]
+fn invalid_rules_are_rejected() {
+    assert_eq!(rule_error("exit_long: close < 1").1, "at least one entry rule is required");
+    assert_eq!(rule_error("entry_long: close > 1\nentry_long: true").1, "defined twice");
+    assert_eq!(rule_error("entry: close > 1").0, "entry");
+    assert_eq!(rule_error("entry_long close > 1").1, "expected `key: expression`");
+    assert_eq!(rule_error("entry_long: close >").1, "unexpected end of expression");
+    assert_eq!(rule_error("entry_long: close + 1").1, "expected a condition, found a number");
+    assert_eq!(rule_error("entry_long: true\nstop_long: close > 1").1, "expected a number, found a condition");
+    assert_eq!(rule_error("entry_long: price > 1").1, "unknown identifier 'price'");
+    assert!(rule_error("entry_long: foo(3) > 1").1.contains("unknown indicator 'foo'"));
+    assert!(rule_error("entry_long: rsi(0) > 50").1.contains("period"));
+    assert_eq!(
+        rule_error("entry_long: close > donchian(20).middle").1,
+        "donchian has no output 'middle' (outputs: upper, lower, basis)"
+    );
+}
+
+#[test]
+fn crossover_rules_match_batch_series() {
+    let mut strategy = RuleStrategy::parse(
+        "entry_long: crosses_above(close, sma(10))
+exit_long: crosses_below(close, sma(10))
+",
+    )
+    .unwrap();
+    let trades = run(&mut strategy);
+
+    // Mêmes croisements calculés sur les séries par lot
+    let klines = load_klines(FIXTURE);
+    let candles = Candles::from_klines(&klines);
+    let spec = IndicatorSpec::MovingAverage { kind: MovingAverageKind::Sma, period: 10, source: PriceSource::Close };
+    let average = spec.compute(&candles).unwrap();
+    let (close, average) = (&candles.close, average.value());
+
+    let mut expected = Vec::new();
+    let mut entry: Option<usize> = None;
+    for i in WARMUP..klines.len() {
+        match entry {
+            None if close[i] > average[i] && close[i - 1] <= average[i - 1] => entry = Some(i),
+            Some(start) if close[i] < average[i] && close[i - 1] >= average[i - 1] => {
+                expected.push((klines[start].open_time, klines[i].open_time));
+                entry = None;
+            }
+            _ => {}
+        }
+    }
+
+    assert!(!expected.is_empty());
+    let actual: Vec<(i64, i64)> = trades.iter().map(|trade| (trade.entry_time, trade.exit_time)).collect();
+    assert_eq!(actual, expected);
+    assert!(trades.iter().all(|trade| trade.side == Side::Long));
+    assert_eq!(strategy.position().map(|position| position.units()[0].entry_time), entry.map(|start| klines[start].open_time));
+}
+
+#[test]
+fn stops_close_positions() {
+    let mut strategy = RuleStrategy::parse(
+        "entry_short: close < close[1]
+stop_short: atr_stop(14, 1.5).short
+",
+    )
+    .unwrap();
+    let trades = run(&mut strategy);
+    let klines = load_klines(FIXTURE);
+    let candles = Candles::from_klines(&klines);
+    let stops = IndicatorSpec::AtrStopLoss { length: 14, multiplier: 1.5 }.compute(&candles).unwrap();
+    let index = |open_time: i64| klines.iter().position(|kline| kline.open_time == open_time).unwrap();
+
+    assert!(!trades.is_empty());
+    for trade in &trades {
+        assert_eq!(trade.side, Side::Short);
+        // Stop fixé à l'entrée, franchi par la clôture de sortie
+        let stop = stops.get("short").unwrap()[index(trade.entry_time)];
+        assert!(trade.exit_price > stop);
+        assert_eq!(trade.return_rate(), (trade.entry_price - trade.exit_price) / trade.entry_price);
+    }
+}
+
+#[test]
+fn rule_strategies_run_in_the_backtester() {
+    let strategy = RuleStrategy::parse(
+        "name: donchian_chop
+entry_long: crosses_above(close, donchian(20, 20).upper) and chop(100) <= 50
+stop_long: atr_stop(14, 1.5).long
+",
+    )
+    .unwrap();
+    let klines = load_klines(FIXTURE);
+
+    let mut backtester = Backtester::new(Box::new(strategy));
+    assert!(backtester.run(&klines).is_ok());
+    assert!(matches!(
+        backtester.run(&klines[..WARMUP - 1]),
+        Err(Error::InsufficientData { required: WARMUP, .. })
+    ));
+}
@@ -0,0 +1,122 @@
+mod common;
+
+use common::{assert_close, load_klines};
+use root::indicator::{
+    ChoppinessIndex, Ema, ObserverNode, PriceSource, Rsi, SeriesAverage, SeriesChannel, SeriesCombine, SeriesRsi, Source,
+};
+use root::strategy::{KlineManager, SeriesSpec};
+
+const FIXTURE: &str = "synthetic_1h.csv";
+
+// Donchian sur RSI, déclaré avant le RSI, et écart du RSI à sa moyenne
+fn specs() -> Vec<SeriesSpec> {
+    vec![
+        SeriesSpec::new("rsi_channel", SeriesChannel::new(20).unwrap(), vec![Source::series("rsi")]),
+        SeriesSpec::new("rsi", SeriesRsi::new(14).unwrap(), vec![Source::Price(PriceSource::Close)]),
+        SeriesSpec::new(
+            "rsi_spread",
+            SeriesCombine::difference(),
+            vec![Source::series("rsi"), Source::series("rsi_average")],
+        ),
+        SeriesSpec::new("rsi_average", SeriesAverage::new(Ema::new(9).unwrap()), vec![Source::series("rsi")]),
+    ]
+}
+
+#[test]
+fn indicators_of_indicators_follow_dependencies() {
+    let klines = load_klines(FIXTURE);
+    let mut manager = KlineManager::new(klines.clone(), Vec::new());
+    manager.add_series(specs()).unwrap();
+
+    let rsi = Rsi::new(&klines, 14, PriceSource::Close).unwrap();
+    let series_rsi = manager.get_series("rsi", "value").unwrap();
+    assert_eq!(series_rsi.len(), klines.len());
+    for (index, expected) in rsi.values.iter().enumerate() {
+        assert_close("rsi", index, series_rsi[index], *expected);
+    }
+
+    let upper = manager.get_series("rsi_channel", "upper").unwrap();
+    let expected = rsi.values[180..200].iter().copied().fold(f64::MIN, f64::max);
+    assert_close("rsi_channel.upper", 199, upper[199], expected);
+    // 14 valeurs NaN pour le RSI puis 20 valeurs pour remplir le canal
+    assert!(upper[32].is_nan());
+    assert!(!upper[33].is_nan());
+
+    let spread = manager.get_series("rsi_spread", "value").unwrap();
+    let average = manager.get_series("rsi_average", "value").unwrap();
+    assert_close("rsi_spread", 199, spread[199], series_rsi[199] - average[199]);
+}
+
+#[test]
+fn incremental_updates_match_history() {
+    let klines = load_klines(FIXTURE);
+
+    let mut batch = KlineManager::new(klines.clone(), Vec::new());
+    batch.add_series(specs()).unwrap();
+
+    let mut incremental = KlineManager::new(klines[..100].to_vec(), Vec::new());
+    incremental.add_series(specs()).unwrap();
+    for kline in &klines[100..] {
+        incremental.add_kline(kline.clone());
+    }
+
+    for (name, output) in [("rsi", "value"), ("rsi_channel", "lower"), ("rsi_spread", "value")] {
+        let expected = batch.get_series(name, output).unwrap();
+        let actual = incremental.get_series(name, output).unwrap();
+        assert_eq!(actual.len(), expected.len());
+        for index in 0..expected.len() {
+            assert_close(name, index, actual[index], expected[index]);
+        }
+    }
+}
+
+#[test]
+fn observers_can_feed_series() {
+    let klines = load_klines(FIXTURE);
+    let mut manager = KlineManager::new(klines[..150].to_vec(), Vec::new());
+    manager
+        .add_series(vec![
+            SeriesSpec::new("chop_average", SeriesAverage::new(Ema::new(10).unwrap()), vec![Source::series("chop")]),
+            SeriesSpec::new(
+                "chop",
+                ObserverNode::new(ChoppinessIndex::new(&[], 14).unwrap(), |chop: &ChoppinessIndex| &chop.values),
+                vec![],
+            ),
+        ])
+        .unwrap();
+    for kline in &klines[150..] {
+        manager.add_kline(kline.clone());
+    }
+
+    let chop = ChoppinessIndex::new(&klines, 14).unwrap();
+    let series = manager.get_series("chop", "value").unwrap();
+    assert_eq!(series.len(), klines.len());
+    assert_close("chop", 199, series[199], chop.values[199]);
+    assert!(!manager.get_series("chop_average", "value").unwrap()[199].is_nan());
+}
+
+#[test]
+fn rejects_invalid_graphs() {
+    let mut manager = KlineManager::new(load_klines(FIXTURE), Vec::new());
+
+    let cycle = vec![
+        SeriesSpec::new("a", SeriesRsi::new(14).unwrap(), vec![Source::series("b")]),
+        SeriesSpec::new("b", SeriesRsi::new(14).unwrap(), vec![Source::series("a")]),
+    ];
+    assert!(manager.add_series(cycle).is_err());
+
+    let unknown = vec![SeriesSpec::new("a", SeriesRsi::new(14).unwrap(), vec![Source::series("missing")])];
+    assert!(manager.add_series(unknown).is_err());
+
+    let bad_output = vec![
+        SeriesSpec::new("rsi", SeriesRsi::new(14).unwrap(), vec![Source::Price(PriceSource::Close)]),
+        SeriesSpec::new("channel", SeriesChannel::new(5).unwrap(), vec![Source::output("rsi", "upper")]),
+    ];
+    assert!(manager.add_series(bad_output).is_err());
+    assert!(manager.get_series("rsi", "value").is_none());
+
+    let rsi = vec![SeriesSpec::new("rsi", SeriesRsi::new(14).unwrap(), vec![Source::Price(PriceSource::Close)])];
+    manager.add_series(rsi).unwrap();
+    let duplicate = vec![SeriesSpec::new("rsi", SeriesRsi::new(7).unwrap(), vec![Source::Price(PriceSource::Close)])];
+    assert!(manager.add_series(duplicate).is_err());
+}
@@ -0,0 +1,67 @@
+mod common;
+
+use binance::model::KlineSummary;
+use common::candle;
+use root::indicator::{SupportResistance, SwingKind, ZigZag};
+
+// Bougies (plus haut, plus bas), ouverture et clôture au milieu
+fn klines(ranges: &[(f64, f64)]) -> Vec<KlineSummary> {
+    ranges
+        .iter()
+        .enumerate()
+        .map(|(index, &(high, low))| candle(index as i64, (high + low) / 2.0, high, low, (high + low) / 2.0))
+        .collect()
+}
+
+// Bougies de deux points autour de chaque valeur : swings hauts à `value + 1`, bas à `value - 1`
+fn around(values: &[f64]) -> Vec<KlineSummary> {
+    klines(&values.iter().map(|value| (value + 1.0, value - 1.0)).collect::<Vec<_>>())
+}
+
+#[test]
+fn zigzag_reverses_at_the_deviation_threshold() {
+    let klines = klines(&[
+        (100.0, 99.0),
+        (103.9, 99.5),  // +4,95 % depuis le creux : pas encore de retournement
+        (104.0, 103.0), // +5,05 % : creux de 99 confirmé
+        (110.0, 105.0), // nouveau sommet
+        (109.5, 104.6), // -4,9 % : sommet conservé
+        (106.0, 104.5), // -5 % : sommet de 110 confirmé
+        (104.5, 100.0), // creux en cours
+        (104.9, 101.0), // +4,9 % : creux non confirmé
+    ]);
+    let zigzag = ZigZag::new(&klines, 5.0).unwrap();
+
+    let swings: Vec<(SwingKind, f64, i64, i64)> =
+        zigzag.swings.iter().map(|swing| (swing.kind, swing.price, swing.open_time, swing.confirmed_at)).collect();
+    let time = |index: usize| klines[index].open_time;
+    assert_eq!(swings, [(SwingKind::Low, 99.0, time(0), time(2)), (SwingKind::High, 110.0, time(3), time(5))]);
+    assert_eq!(zigzag.pending(), Some((SwingKind::Low, 100.0)));
+
+    // Seuil plus large : aucun retournement
+    let zigzag = ZigZag::new(&klines, 12.0).unwrap();
+    assert!(zigzag.swings.is_empty() && zigzag.pending().is_none());
+    assert!(ZigZag::new(&klines, 0.0).is_err());
+}
+
+#[test]
+fn close_swings_merge_into_zones() {
+    // Creux à 99, 99,8 et 94 ; sommets à 111, 111 et 121
+    let klines = around(&[110.0, 100.0, 110.0, 100.8, 110.0, 95.0, 110.0, 120.0, 110.0]);
+
+    let zones = SupportResistance::new(&klines, 1, 1, 1.0, 2).unwrap();
+    let significant: Vec<(f64, f64, usize)> = zones.zones().map(|zone| (zone.lower, zone.upper, zone.touches)).collect();
+    assert_eq!(significant, [(99.0, 99.8, 2), (111.0, 111.0, 2)]);
+
+    let support = zones.nearest_support(105.0).unwrap();
+    assert_eq!((support.center(), support.last_touch), ((99.0 + 99.8) / 2.0, klines[3].open_time));
+    assert_eq!(zones.nearest_resistance(105.0).unwrap().center(), 111.0);
+    // Zones d'un seul swing ignorées
+    assert!(zones.nearest_support(98.0).is_none());
+    assert!(zones.nearest_resistance(115.0).is_none());
+
+    // 0,8 d'écart dépasse 0,5 % de 99 : les deux creux restent séparés
+    let zones = SupportResistance::new(&klines, 1, 1, 0.5, 2).unwrap();
+    assert!(zones.nearest_support(105.0).is_none());
+    assert_eq!(SupportResistance::new(&klines, 1, 1, 0.5, 1).unwrap().zones().count(), 5);
+}
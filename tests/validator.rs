@@ -0,0 +1,159 @@
+mod common;
+
+use binance::model::KlineSummary;
+use common::candle;
+use root::data::validator::IssueKind;
+use root::data::{Interval, RepairPolicy, Validator};
+
+const HOUR_MS: i64 = 3_600_000;
+
+// Bougies horaires ouvrant à la clôture précédente
+fn series(closes: &[f64]) -> Vec<KlineSummary> {
+    let mut open = closes[0];
+    (0..)
+        .zip(closes)
+        .map(|(index, &close)| {
+            let kline = candle(index, open, open.max(close) + 0.2, open.min(close) - 0.2, close);
+            open = close;
+            kline
+        })
+        .collect()
+}
+
+// Clôtures oscillant autour de 100, puis `shift` ajouté à partir de la bougie `from`
+fn closes(count: usize, from: usize, shift: f64) -> Vec<f64> {
+    (0..count)
+        .map(|index| {
+            let close = 100.0 + if index % 2 == 0 { 0.1 } else { -0.1 } + index as f64 * 0.01;
+            if index >= from { close + shift } else { close }
+        })
+        .collect()
+}
+
+fn validator(policy: RepairPolicy) -> Validator {
+    Validator::new(Interval::Hours(1), policy).with_spike_detection(5, 4.0)
+}
+
+fn issues(klines: &[KlineSummary]) -> Vec<(usize, IssueKind)> {
+    validator(RepairPolicy::Fail).validate(klines).issues.into_iter().map(|issue| (issue.index, issue.kind)).collect()
+}
+
+#[test]
+fn clean_series_passes() {
+    let klines = series(&closes(20, 20, 0.0));
+    assert!(validator(RepairPolicy::Fail).validate(&klines).is_clean());
+    assert_eq!(validator(RepairPolicy::Fail).repair(&klines).unwrap().len(), klines.len());
+}
+
+#[test]
+fn ordering_checks() {
+    let mut klines = series(&closes(10, 10, 0.0));
+    klines.remove(3);
+    klines.remove(3);
+    let duplicate = klines[5].clone();
+    klines.insert(6, duplicate);
+    let earlier = klines[1].clone();
+    klines.insert(8, earlier);
+
+    assert_eq!(
+        issues(&klines),
+        [(3, IssueKind::MissingIntervals { missing: 2 }), (6, IssueKind::DuplicateOpenTime), (8, IssueKind::OutOfOrder)]
+    );
+    assert_eq!(validator(RepairPolicy::Fail).validate(&klines).missing_intervals(), 2);
+}
+
+#[test]
+fn bar_checks() {
+    let mut klines = series(&closes(10, 10, 0.0));
+    klines[2].volume = "0".to_string();
+    (klines[4].high, klines[4].low) = ("99".to_string(), "101".to_string());
+    klines[6].close = "150".to_string();
+    klines[7].open = "50".to_string();
+    klines[8].close = "abc".to_string();
+
+    assert_eq!(
+        issues(&klines),
+        [
+            (2, IssueKind::ZeroVolume),
+            (4, IssueKind::HighBelowLow),
+            (6, IssueKind::CloseOutsideRange),
+            (7, IssueKind::OpenOutsideRange),
+            (8, IssueKind::Unparseable { field: "close" }),
+        ]
+    );
+}
+
+#[test]
+fn level_shift_is_flagged_once() {
+    // Un saut de 10 % qui se maintient : seule la première bougie est un pic
+    let klines = series(&closes(20, 10, 10.0));
+    let issues = issues(&klines);
+    assert_eq!(issues.len(), 1);
+    assert!(matches!(issues[0], (10, IssueKind::OutlierSpike { z_score }) if z_score > 4.0));
+}
+
+#[test]
+fn forward_fill_repairs_gaps_and_invalid_bars() {
+    let mut klines = series(&closes(12, 12, 0.0));
+    klines.remove(3);
+    (klines[6].high, klines[6].low) = ("99".to_string(), "101".to_string());
+    let duplicate = klines[8].clone();
+    klines.insert(9, duplicate);
+
+    let repaired = validator(RepairPolicy::ForwardFill).repair(&klines).unwrap();
+    assert_eq!(repaired.len(), 12);
+    assert!(repaired.windows(2).all(|pair| pair[1].open_time == pair[0].open_time + HOUR_MS));
+
+    // Bougie manquante et bougie invalide : plates à la clôture précédente, sans volume
+    for index in [3, 7] {
+        let filled = &repaired[index];
+        assert_eq!(filled.open_time, klines[0].open_time + index as i64 * HOUR_MS);
+        assert_eq!(filled.close_time, filled.open_time + HOUR_MS - 1);
+        for price in [&filled.open, &filled.high, &filled.low, &filled.close] {
+            assert_eq!(price, &repaired[index - 1].close);
+        }
+        assert_eq!((filled.volume.as_str(), filled.number_of_trades), ("0", 0));
+    }
+    assert_eq!(repaired[8].close, klines[7].close);
+}
+
+#[test]
+fn forward_fill_keeps_spikes() {
+    let klines = series(&closes(20, 10, 10.0));
+    let repaired = validator(RepairPolicy::ForwardFill).repair(&klines).unwrap();
+
+    // Le pic peut être un vrai mouvement de marché : la bougie est conservée telle quelle
+    assert_eq!(repaired.len(), klines.len());
+    assert_eq!((&repaired[10].close, &repaired[10].volume), (&klines[10].close, &klines[10].volume));
+}
+
+#[test]
+fn drop_removes_invalid_and_suspicious_bars() {
+    let mut klines = series(&closes(20, 10, 10.0));
+    klines.remove(3);
+    klines[1].volume = "0".to_string();
+    (klines[5].high, klines[5].low) = ("99".to_string(), "101".to_string());
+
+    let repaired = validator(RepairPolicy::Drop).repair(&klines).unwrap();
+    let removed: Vec<usize> = klines
+        .iter()
+        .enumerate()
+        .filter(|(_, kline)| !repaired.iter().any(|kept| kept.open_time == kline.open_time))
+        .map(|(index, _)| index)
+        .collect();
+    // Sans volume, invalide et pic (la bougie 10 de la série d'origine) ; le trou reste
+    assert_eq!(removed, [1, 5, 9]);
+    assert_eq!(repaired.len(), klines.len() - 3);
+}
+
+#[test]
+fn fail_returns_the_report() {
+    let mut klines = series(&closes(10, 10, 0.0));
+    klines[4].close = "150".to_string();
+
+    let validator = validator(RepairPolicy::Fail);
+    let report = validator.repair(&klines).unwrap_err();
+    assert_eq!(report, validator.validate(&klines));
+    assert_eq!(report.count(|kind| *kind == IssueKind::CloseOutsideRange), 1);
+    assert!(report.to_string().starts_with("1 data quality issue(s)"));
+}
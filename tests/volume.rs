@@ -0,0 +1,43 @@
+mod common;
+
+use binance::model::KlineSummary;
+use common::candle;
+use root::data::Candles;
+use root::indicator::{IndicatorSpec, VolumeProfile};
+
+// Bougie de `low` à `high` avec le volume donné
+fn bar(index: i64, low: f64, high: f64, volume: f64) -> KlineSummary {
+    KlineSummary { volume: volume.to_string(), ..candle(index, low, high, low, high) }
+}
+
+#[test]
+fn volume_profile_of_a_known_distribution() {
+    // Dix niveaux de 1 entre 100 et 110 : 1 par niveau pour la première bougie, puis 20, 12 et 6
+    // sur les niveaux 104, 105 et 103
+    let klines = [
+        bar(0, 100.0, 110.0, 10.0),
+        bar(1, 104.2, 104.8, 20.0),
+        bar(2, 105.2, 105.8, 12.0),
+        bar(3, 103.2, 103.8, 6.0),
+    ];
+
+    // Value area de 70 % (33,6 sur 48) : POC (21), puis le niveau voisin le plus traité (13)
+    let profile = VolumeProfile::new(&klines, 4, 10, 0.7).unwrap();
+    assert!(profile.poc.as_slice()[..3].iter().all(|poc| poc.is_nan()));
+    assert_eq!((profile.poc[3], profile.value_area_low[3], profile.value_area_high[3]), (104.5, 104.0, 106.0));
+
+    // 80 % (38,4) : le niveau 103 (7) passe avant le niveau 106 (1)
+    let profile = VolumeProfile::new(&klines, 4, 10, 0.8).unwrap();
+    assert_eq!((profile.poc[3], profile.value_area_low[3], profile.value_area_high[3]), (104.5, 103.0, 106.0));
+
+    let spec = IndicatorSpec::VolumeProfile { length: 4, bins: 10, value_area: 0.8 };
+    let series = spec.compute(&Candles::from_klines(&klines)).unwrap();
+    assert_eq!(series.get("poc").unwrap()[3], 104.5);
+    assert_eq!(series.get("value_area_low").unwrap()[3], 103.0);
+    assert_eq!(series.get("value_area_high").unwrap()[3], 106.0);
+
+    // Fenêtre glissante : la première bougie sort, une bougie identique entre, le profil est inchangé
+    let mut profile = VolumeProfile::new(&klines, 4, 10, 0.7).unwrap();
+    profile.add(&[klines.to_vec(), vec![bar(4, 100.0, 110.0, 10.0)]].concat());
+    assert_eq!((profile.poc[4], profile.value_area_low[4], profile.value_area_high[4]), (104.5, 104.0, 106.0));
+}